@@ -34,6 +34,12 @@ mod cli {
             /// Enable Serde support.
             #[arg(short = 's', long, default_value_t = false)]
             serde: bool,
+            /// Enable Arbitrary support (available behind `arbitrary` feature of generated crate).
+            #[arg(long, default_value_t = false)]
+            arbitrary: bool,
+            /// Generate async message handlers (available behind `async` feature of generated crate).
+            #[arg(long, default_value_t = false)]
+            async_handlers: bool,
             /// Microservices to generate.
             ///
             /// This option will filter out irrelevant messages, enums, and enum entries.
@@ -71,6 +77,48 @@ mod cli {
             /// Generate tests.
             #[arg(short = 't', long, default_value_t = false)]
             generate_tests: bool,
+            /// Generate constructors and fluent builders for messages.
+            #[arg(long, default_value_t = false)]
+            generate_builders: bool,
+            /// Represent message fields with MAVLink units as typed quantities.
+            #[arg(long, default_value_t = false)]
+            typed_units: bool,
+            /// Policy for work-in-progress messages and enum entries.
+            #[arg(long, value_parser = ["include", "exclude", "warn"])]
+            wip: Option<String>,
+            /// Representation of enums and bitmasks in human-readable serde formats (requires `--serde`).
+            #[arg(long, value_parser = ["derive", "numeric", "name", "flags"])]
+            serde_enums: Option<String>,
+            /// Files with test vectors (JSON or CSV) for known-answer tests (requires `--generate-tests`).
+            #[arg(long, value_parser, num_args = 1.., value_delimiter = DELIMITER)]
+            test_vectors: Option<Vec<String>>,
+            /// Layout of generated files.
+            #[arg(long, value_parser = ["modules", "single_file", "file_per_dialect", "workspace"])]
+            output_layout: Option<String>,
+            /// Path to local `mavspec` crate for crates generated with `--output-layout workspace`.
+            #[arg(long)]
+            mavspec_path: Option<String>,
+            /// Check that output path contains up-to-date bindings without writing anything.
+            ///
+            /// Reports missing, extra, and differing files with a unified diff and exits with non-zero code.
+            #[arg(long, default_value_t = false)]
+            check: bool,
+        },
+        /// Generate deterministic sample test vectors (JSON) for a MAVLink dialect
+        #[cfg(feature = "rust")]
+        TestVectors {
+            /// Dialect name (i.e. `common`).
+            #[arg(short = 'd', long)]
+            dialect: String,
+            /// Seed for pseudo-random field values.
+            #[arg(long, default_value_t = 0)]
+            seed: u64,
+            /// Output file (default is `<out>/<dialect>.json`).
+            #[arg(short = 'f', long)]
+            file: Option<String>,
+            /// Policy for work-in-progress messages and enum entries.
+            #[arg(long, value_parser = ["include", "exclude", "warn"])]
+            wip: Option<String>,
         },
     }
 }
@@ -89,7 +137,11 @@ mod process {
 
                 let out_path = std::path::Path::new(&cli.out).to_path_buf();
 
-                if cli.clean {
+                let check = matches!(command, Commands::Rust { check: true, .. });
+
+                if cli.clean && check {
+                    log::warn!("Output path is not cleaned in check mode.");
+                } else if cli.clean {
                     log::warn!("Output path will be cleaned.");
                     if let Err(err) = remove_dir_all(&out_path) {
                         log::debug!("Error cleaning directory: {err:?}.");
@@ -100,13 +152,27 @@ mod process {
                     #[cfg(feature = "rust")]
                     Commands::Rust {
                         serde,
+                        arbitrary,
+                        async_handlers,
                         microservices,
                         messages,
                         enums,
                         commands,
                         generate_tests,
+                        generate_builders,
+                        typed_units,
+                        wip,
+                        serde_enums,
+                        test_vectors,
+                        output_layout,
+                        mavspec_path,
+                        check,
                     } => {
-                        log::info!("Writing Rust bindings to output path: {:?}", out_path);
+                        if *check {
+                            log::info!("Checking Rust bindings in output path: {:?}", out_path);
+                        } else {
+                            log::info!("Writing Rust bindings to output path: {:?}", out_path);
+                        }
 
                         let mut builder = mavspec::rust::gen::BuildHelper::builder(&out_path);
 
@@ -122,14 +188,68 @@ mod process {
                         if let Some(commands) = commands {
                             builder.set_commands(commands);
                         };
+                        if let Some(wip) = wip {
+                            builder.set_wip(wip.parse()?);
+                        };
+                        if let Some(serde_enums) = serde_enums {
+                            builder.set_serde_enums(serde_enums.parse()?);
+                        };
+                        if let Some(test_vectors) = test_vectors {
+                            builder.set_test_vectors(test_vectors);
+                        };
+                        if let Some(output_layout) = output_layout {
+                            builder.set_output_layout(output_layout.parse()?);
+                        };
+                        if let Some(mavspec_path) = mavspec_path {
+                            builder.set_mavspec_path(mavspec_path);
+                        };
 
                         let sources: Vec<&str> = cli.src.iter().map(|s| s.as_str()).collect();
 
-                        builder
+                        let helper = builder
                             .set_sources(&sources)
                             .set_serde(*serde)
+                            .set_arbitrary(*arbitrary)
+                            .set_async_handlers(*async_handlers)
                             .set_generate_tests(*generate_tests)
-                            .generate()
+                            .set_generate_builders(*generate_builders)
+                            .set_typed_units(*typed_units)
+                            .build()?;
+
+                        if *check {
+                            helper.verify()
+                        } else {
+                            helper.generate()
+                        }
+                    }
+                    #[cfg(feature = "rust")]
+                    Commands::TestVectors {
+                        dialect,
+                        seed,
+                        file,
+                        wip,
+                    } => {
+                        let file = match file {
+                            Some(file) => std::path::PathBuf::from(file),
+                            None => out_path.join(format!("{dialect}.json")),
+                        };
+                        log::info!("Writing test vectors to: {:?}", file);
+
+                        let mut builder = mavspec::rust::gen::BuildHelper::builder(&out_path);
+                        if let Some(wip) = wip {
+                            builder.set_wip(wip.parse()?);
+                        };
+
+                        let sources: Vec<&str> = cli.src.iter().map(|s| s.as_str()).collect();
+                        let vectors = builder
+                            .set_sources(&sources)
+                            .build()?
+                            .generate_test_vectors(dialect, *seed)?;
+
+                        if let Some(parent) = file.parent() {
+                            std::fs::create_dir_all(parent)?;
+                        }
+                        mavspec::rust::gen::test_vectors::write_test_vectors(&vectors, &file)
                     }
                 }
             }
@@ -149,5 +269,8 @@ fn main() {
 
     // Process CLI commands
     #[cfg(feature = "rust_gen")]
-    process::process(&cli).unwrap();
+    if let Err(err) = process::process(&cli) {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
 }
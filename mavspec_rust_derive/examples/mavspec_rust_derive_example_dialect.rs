@@ -3,6 +3,7 @@ use mavspec::rust::spec::{IntoPayload, MavLinkVersion};
 
 #[repr(u8)]
 #[derive(Copy, Clone, Debug, Default, Enum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum Mood {
     #[default]
     Serious = 0,
@@ -12,6 +13,7 @@ enum Mood {
 }
 
 #[derive(Clone, Debug, Message)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[message_id(42)]
 struct Howdy {
     #[base_type(u8)]
@@ -19,6 +21,7 @@ struct Howdy {
 }
 
 #[derive(Clone, Debug, Message)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[message_id(43)]
 struct Good {
     #[base_type(u8)]
@@ -26,6 +29,7 @@ struct Good {
 }
 
 #[derive(Clone, Debug, Message)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[message_id(44)]
 struct AndYou {
     #[base_type(u8)]
@@ -36,6 +40,7 @@ mod extra {
     use super::*;
 
     #[derive(Clone, Debug, Message)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[message_id(1001)]
     pub struct FeelingBad {
         #[base_type(u8)]
@@ -43,6 +48,7 @@ mod extra {
     }
 
     #[derive(Clone, Debug, Message)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[message_id(1002)]
     pub struct HelpPackage {
         #[base_type(u8)]
@@ -50,6 +56,7 @@ mod extra {
     }
 
     #[derive(Clone, Debug, Message)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[message_id(1003)]
     pub struct Thanks {
         #[base_type(u8)]
@@ -58,6 +65,7 @@ mod extra {
 }
 
 #[derive(Dialect)]
+#[cfg_attr(feature = "serde", serde_tagged)]
 #[dialect(1099)]
 #[version(99)]
 enum SmallTalk {
@@ -86,6 +94,21 @@ fn encode_decode() {
     }
 }
 
+#[cfg(feature = "serde")]
+fn serde_round_trip() {
+    let message = SmallTalk::AndYou(AndYou { mood: Mood::Grumpy });
+
+    // Dialect messages are tagged with their names
+    let json = serde_json::to_string(&message).unwrap();
+    assert_eq!(json, r#"{"message":"AND_YOU","fields":{"mood":"Grumpy"}}"#);
+
+    let decoded_message: SmallTalk = serde_json::from_str(&json).unwrap();
+    match decoded_message {
+        SmallTalk::AndYou(_) => {}
+        _ => panic!("invalid message!"),
+    }
+}
+
 pub fn main() {
     // Setup logger
     env_logger::builder()
@@ -95,9 +118,16 @@ pub fn main() {
 
     // Encode/decode custom dialect
     encode_decode();
+
+    // Serialize/deserialize custom dialect
+    #[cfg(feature = "serde")]
+    serde_round_trip();
 }
 
 #[test]
 fn test_example() {
     encode_decode();
+
+    #[cfg(feature = "serde")]
+    serde_round_trip();
 }
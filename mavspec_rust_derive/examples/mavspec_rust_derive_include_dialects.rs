@@ -0,0 +1,42 @@
+use mavspec::rust::spec::{Dialect, IntoPayload, MavLinkVersion};
+
+mod mavlink {
+    mavspec::rust::derive::include_dialects! {
+        sources = ["../message_definitions/standard"],
+        include_dialects = ["minimal"],
+    }
+}
+use mavlink::dialects::minimal;
+
+fn encode_decode() {
+    let message = minimal::messages::Heartbeat {
+        custom_mode: 42,
+        ..Default::default()
+    };
+    let payload = message.encode(MavLinkVersion::V2).unwrap();
+    log::info!("Payload: {payload:?}");
+
+    let decoded_message = minimal::Minimal::decode(&payload).unwrap();
+    log::info!("Decoded message: {decoded_message:#?}");
+
+    match decoded_message {
+        minimal::Minimal::Heartbeat(heartbeat) => assert_eq!(heartbeat.custom_mode, 42),
+        _ => panic!("invalid message: {decoded_message:?}"),
+    }
+}
+
+pub fn main() {
+    // Setup logger
+    env_logger::builder()
+        .filter_level(log::LevelFilter::Info) // Suppress everything below `info` for third-party modules.
+        .filter_module(env!("CARGO_PKG_NAME"), log::LevelFilter::Trace) // Allow everything from current package
+        .init();
+
+    // Encode/decode message of a dialect generated at compile time
+    encode_decode();
+}
+
+#[test]
+fn test_example() {
+    encode_decode();
+}
@@ -2,8 +2,16 @@ pub(crate) const MESSAGE_ID_MAX: u32 = 2u32.pow(24);
 
 pub(crate) const ATTR_MESSAGE_ID: &str = "message_id";
 pub(crate) const ATTR_CRC_EXTRA: &str = "crc_extra";
+pub(crate) const ATTR_BUILDER: &str = "builder";
 pub(crate) const ATTR_EXTENSION: &str = "extension";
+pub(crate) const ATTR_INSTANCE: &str = "instance";
 pub(crate) const ATTR_BITMASK: &str = "bitmask";
+pub(crate) const ATTR_QUANTITY: &str = "quantity";
+pub(crate) const ATTR_DEFAULT_VALUE: &str = "default_value";
 pub(crate) const ATTR_BASE_TYPE: &str = "base_type";
 pub(crate) const ATTR_REPR_TYPE: &str = "repr_type";
 pub(crate) const ATTR_REPR: &str = "repr";
+pub(crate) const ATTR_CANONICAL_NAME: &str = "canonical_name";
+pub(crate) const ATTR_STRIPPED_NAME: &str = "stripped_name";
+pub(crate) const ATTR_DESCRIPTION: &str = "description";
+pub(crate) const ATTR_NO_STR: &str = "no_str";
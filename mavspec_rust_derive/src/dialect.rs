@@ -1,9 +1,14 @@
 use quote::{format_ident, quote, ToTokens};
 
+use crate::consts::ATTR_CANONICAL_NAME;
 use crate::errors::{DialectError, Error};
 
 pub(crate) struct Dialect {
     ident: syn::Ident,
+    vis: syn::Visibility,
+    handler: bool,
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    serde: bool,
     name: String,
     dialect_id: Option<u32>,
     version: Option<u8>,
@@ -14,6 +19,20 @@ pub(crate) struct Dialect {
 struct Variant {
     ident: syn::Ident,
     message_type: proc_macro2::TokenStream,
+    /// Canonical MAVLink message name (i.e. `HEARTBEAT`).
+    name: String,
+}
+
+impl Variant {
+    /// Method identifier from `prefix` and snake case variant identifier (i.e. `as_heartbeat`).
+    fn method_ident(&self, prefix: &str) -> syn::Ident {
+        let snake_name = heck::AsSnakeCase(self.ident.to_string()).to_string();
+        format_ident!("{prefix}_{}", snake_name.trim_matches('_'))
+    }
+
+    fn handler_ident(&self) -> syn::Ident {
+        self.method_ident("on")
+    }
 }
 
 struct DialectAttrs {
@@ -25,6 +44,8 @@ struct DialectAttrs {
 const ATTR_DIALECT_NAME: &str = "name";
 const ATTR_DIALECT_ID: &str = "dialect";
 const ATTR_DIALECT_VERSION: &str = "version";
+const ATTR_DIALECT_HANDLER: &str = "handler";
+const ATTR_DIALECT_SERDE: &str = "serde_tagged";
 
 impl TryFrom<syn::DeriveInput> for Dialect {
     type Error = Error;
@@ -47,6 +68,15 @@ impl Dialect {
 
         Ok(Self {
             ident: value.ident.clone(),
+            vis: value.vis.clone(),
+            handler: value
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident(ATTR_DIALECT_HANDLER)),
+            serde: value
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident(ATTR_DIALECT_SERDE)),
             name: attrs.name,
             dialect_id: attrs.dialect_id,
             version: attrs.version,
@@ -116,7 +146,17 @@ impl Dialect {
             #[allow(unreachable_code)]
         };
 
+        let serde_impl = self.serde_impl();
+        let accessors_impl = self.accessors_impl();
+        let handler_impl = self.handler_impl();
+        let async_handler_impl = self.async_handler_impl();
+
         quote! {
+            #serde_impl
+            #accessors_impl
+            #handler_impl
+            #async_handler_impl
+
             const #message_spec_const_ident: [mavspec::rust::spec::MessageInfo; #messages_count] = [#(#messages_specs,)*];
             const #dialect_spec_const_ident: mavspec::rust::spec::DialectSpec = mavspec::rust::spec::DialectSpec::new(
                 #dialect_name,
@@ -206,6 +246,312 @@ impl Dialect {
         }
     }
 
+    /// Implements dialect constants, `message_name()`, `stream_key()`, `as_<message>()` accessors, and
+    /// `TryFrom<Dialect>` for messages.
+    fn accessors_impl(&self) -> proc_macro2::TokenStream {
+        let dialect_enum_ident = self.ident.clone();
+
+        let message_name_arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let name = &variant.name;
+            quote! { #dialect_enum_ident::#ident(_) => #name, }
+        });
+
+        let stream_key_arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            quote! {
+                #dialect_enum_ident::#ident(message) => mavspec::rust::spec::Message::stream_key(message),
+            }
+        });
+
+        let accessors = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let message_type = &variant.message_type;
+            let accessor_ident = variant.method_ident("as");
+            let doc = format!(
+                " Returns reference to the message if this is a `{}` message.",
+                variant.name
+            );
+
+            quote! {
+                #[doc = #doc]
+                #[inline]
+                #[allow(unreachable_patterns)]
+                pub fn #accessor_ident(&self) -> core::option::Option<&#message_type> {
+                    match self {
+                        #dialect_enum_ident::#ident(message) => core::option::Option::Some(message),
+                        _ => core::option::Option::None,
+                    }
+                }
+            }
+        });
+
+        let conversions = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let message_type = &variant.message_type;
+
+            quote! {
+                impl core::convert::TryFrom<#dialect_enum_ident> for #message_type {
+                    type Error = #dialect_enum_ident;
+
+                    #[allow(unreachable_patterns)]
+                    fn try_from(value: #dialect_enum_ident) -> core::result::Result<Self, Self::Error> {
+                        match value {
+                            #dialect_enum_ident::#ident(message) => core::result::Result::Ok(message),
+                            _ => core::result::Result::Err(value),
+                        }
+                    }
+                }
+            }
+        });
+
+        let messages_count = self.messages_count();
+        let payload_sizes = self.variants.iter().map(|variant| {
+            let message_type = &variant.message_type;
+            quote! { <#message_type>::PAYLOAD_SIZE_V2 }
+        });
+
+        quote! {
+            impl #dialect_enum_ident {
+                /// Number of messages in this dialect.
+                pub const MESSAGE_COUNT: usize = #messages_count;
+
+                /// Maximum `MAVLink 2` payload size among messages of this dialect.
+                pub const MAX_PAYLOAD_SIZE: usize = {
+                    let sizes: [usize; #messages_count] = [#(#payload_sizes),*];
+                    let mut max = 0;
+                    let mut i = 0;
+                    while i < sizes.len() {
+                        if sizes[i] > max {
+                            max = sizes[i];
+                        }
+                        i += 1;
+                    }
+                    max
+                };
+
+                /// MAVLink name of the contained message (i.e. `HEARTBEAT`).
+                pub fn message_name(&self) -> &'static str {
+                    match self {
+                        #(#message_name_arms)*
+                    }
+                }
+
+                /// Key of a message stream which combines message ID and message instance (if any).
+                ///
+                /// See [`mavspec::rust::spec::Message::stream_key`] for details.
+                pub fn stream_key(&self) -> mavspec::rust::spec::StreamKey {
+                    match self {
+                        #(#stream_key_arms)*
+                    }
+                }
+
+                #(#accessors)*
+            }
+
+            #(#conversions)*
+        }
+    }
+
+    /// Defines `<Dialect>Handler` trait and `dispatch()` method if dialect is marked with `#[handler]`.
+    fn handler_impl(&self) -> proc_macro2::TokenStream {
+        if !self.handler {
+            return quote!();
+        }
+
+        let dialect_enum_ident = &self.ident;
+        let vis = &self.vis;
+        let handler_ident = format_ident!("{}Handler", self.ident);
+        let handler_doc = format!(" Handler for messages of [`{}`] dialect.", self.ident);
+        let dispatch_doc =
+            format!(" Dispatches message to the corresponding method of [`{handler_ident}`].");
+
+        let handler_methods = self.variants.iter().map(|variant| {
+            let message_type = &variant.message_type;
+            let handler_method_ident = variant.handler_ident();
+            let doc = format!(" Handles `{}` message.", variant.name);
+
+            quote! {
+                #[doc = #doc]
+                fn #handler_method_ident(&mut self, message: &#message_type) {
+                    self.on_unhandled(message)
+                }
+            }
+        });
+        let dispatch_arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let handler_method_ident = variant.handler_ident();
+            quote! { #dialect_enum_ident::#ident(message) => handler.#handler_method_ident(message), }
+        });
+
+        quote! {
+            #[doc = #handler_doc]
+            #vis trait #handler_ident {
+                /// Handles messages without dedicated handler.
+                fn on_unhandled(&mut self, message: &dyn mavspec::rust::spec::Message) {
+                    let _ = message;
+                }
+
+                #(#handler_methods)*
+            }
+
+            impl #dialect_enum_ident {
+                #[doc = #dispatch_doc]
+                pub fn dispatch<H: #handler_ident>(&self, handler: &mut H) {
+                    match self {
+                        #(#dispatch_arms)*
+                    }
+                }
+            }
+        }
+    }
+
+    /// Defines `<Dialect>AsyncHandler` trait and `dispatch_async()` method if dialect is marked with `#[handler]`.
+    #[cfg(feature = "async")]
+    fn async_handler_impl(&self) -> proc_macro2::TokenStream {
+        if !self.handler {
+            return quote!();
+        }
+
+        let dialect_enum_ident = &self.ident;
+        let vis = &self.vis;
+        let handler_ident = format_ident!("{}AsyncHandler", self.ident);
+        let handler_doc = format!(" Async handler for messages of [`{}`] dialect.", self.ident);
+        let dispatch_doc =
+            format!(" Dispatches message to the corresponding method of [`{handler_ident}`].");
+
+        let handler_methods = self.variants.iter().map(|variant| {
+            let message_type = &variant.message_type;
+            let handler_method_ident = variant.handler_ident();
+            let doc = format!(" Handles `{}` message.", variant.name);
+
+            quote! {
+                #[doc = #doc]
+                fn #handler_method_ident(
+                    &mut self,
+                    message: &#message_type,
+                ) -> impl core::future::Future<Output = ()> + Send {
+                    async move { self.on_unhandled(message).await }
+                }
+            }
+        });
+        let dispatch_arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let handler_method_ident = variant.handler_ident();
+            quote! { #dialect_enum_ident::#ident(message) => handler.#handler_method_ident(message).await, }
+        });
+
+        quote! {
+            #[doc = #handler_doc]
+            #vis trait #handler_ident: Send {
+                /// Handles messages without dedicated handler.
+                fn on_unhandled(
+                    &mut self,
+                    message: &(dyn mavspec::rust::spec::Message + Sync),
+                ) -> impl core::future::Future<Output = ()> + Send {
+                    let _ = message;
+                    async {}
+                }
+
+                #(#handler_methods)*
+            }
+
+            impl #dialect_enum_ident {
+                #[doc = #dispatch_doc]
+                pub async fn dispatch_async<H: #handler_ident>(&self, handler: &mut H) {
+                    match self {
+                        #(#dispatch_arms)*
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "async"))]
+    fn async_handler_impl(&self) -> proc_macro2::TokenStream {
+        quote!()
+    }
+
+    /// Implements `serde` traits with `{"message": "HEARTBEAT", "fields": {...}}` representation if dialect is marked
+    /// with `#[serde_tagged]`.
+    ///
+    /// Delegates to private adjacently tagged enums, so the representation is the same as for generated dialects.
+    #[cfg(feature = "serde")]
+    fn serde_impl(&self) -> proc_macro2::TokenStream {
+        if !self.serde || self.variants.is_empty() {
+            return quote!();
+        }
+
+        let dialect_enum_ident = self.ident.clone();
+        let ser_ident = format_ident!("__MavSpecSerialize{}", self.ident);
+        let de_ident = format_ident!("__MavSpecDeserialize{}", self.ident);
+
+        let ser_variants = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let message_type = &variant.message_type;
+            let name = &variant.name;
+            quote! {
+                #[serde(rename = #name)]
+                #ident(&'a #message_type),
+            }
+        });
+        let de_variants = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let message_type = &variant.message_type;
+            let name = &variant.name;
+            quote! {
+                #[serde(rename = #name)]
+                #ident(#message_type),
+            }
+        });
+        let ser_arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            quote! { #dialect_enum_ident::#ident(message) => #ser_ident::#ident(message), }
+        });
+        let de_arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            quote! { #de_ident::#ident(message) => #dialect_enum_ident::#ident(message), }
+        });
+
+        quote! {
+            const _: () = {
+                #[derive(serde::Serialize)]
+                #[serde(tag = "message", content = "fields")]
+                enum #ser_ident<'a> {
+                    #(#ser_variants)*
+                }
+
+                #[derive(serde::Deserialize)]
+                #[serde(tag = "message", content = "fields")]
+                enum #de_ident {
+                    #(#de_variants)*
+                }
+
+                impl serde::Serialize for #dialect_enum_ident {
+                    fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+                        let message = match self {
+                            #(#ser_arms)*
+                        };
+                        serde::Serialize::serialize(&message, serializer)
+                    }
+                }
+
+                impl<'de> serde::Deserialize<'de> for #dialect_enum_ident {
+                    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+                        Ok(match <#de_ident as serde::Deserialize>::deserialize(deserializer)? {
+                            #(#de_arms)*
+                        })
+                    }
+                }
+            };
+        }
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn serde_impl(&self) -> proc_macro2::TokenStream {
+        quote!()
+    }
+
     fn name_literal(&self) -> &str {
         self.name.as_str()
     }
@@ -270,17 +616,38 @@ impl Dialect {
                 return Err(DialectError::MultipleEnumFields.into());
             }
 
+            let name = match Self::get_message_name(&variant.attrs)? {
+                Some(name) => name,
+                None => heck::AsShoutySnakeCase(ident.to_string()).to_string(),
+            };
+
             let field = variant.fields.into_iter().next().unwrap();
             let message_type = field.to_token_stream();
 
             variants.push(Variant {
                 ident,
                 message_type,
+                name,
             });
         }
 
         Ok(variants)
     }
+
+    fn get_message_name(attrs: &[syn::Attribute]) -> Result<Option<String>, Error> {
+        for attr in attrs {
+            if let Some(attr_ident) = attr.path().get_ident() {
+                if attr_ident == ATTR_CANONICAL_NAME {
+                    let lit: syn::LitStr = attr
+                        .parse_args()
+                        .map_err(|_| Error::from(DialectError::InvalidMessageName))?;
+                    return Ok(Some(lit.value()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
 }
 
 impl DialectAttrs {
@@ -325,3 +692,95 @@ impl DialectAttrs {
         })
     }
 }
+
+///////////////////////////////////////////////////////////////////////////////
+/////                               TESTS                                 /////
+///////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_names() {
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            enum SmallTalk {
+                HowdyPartner(HowdyPartner),
+                #[canonical_name("FINE_AND_YOU")]
+                Fine(FineAndYou),
+            }
+        })
+        .unwrap();
+        let dialect = Dialect::try_from(input).unwrap();
+
+        assert_eq!(dialect.name_literal(), "small_talk");
+        assert_eq!(dialect.variants[0].name, "HOWDY_PARTNER");
+        assert_eq!(dialect.variants[1].name, "FINE_AND_YOU");
+    }
+
+    #[test]
+    fn accessors() {
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            enum SmallTalk {
+                HowdyPartner(HowdyPartner),
+                _1stClass(FirstClass),
+            }
+        })
+        .unwrap();
+        let accessors = Dialect::try_from(input)
+            .unwrap()
+            .accessors_impl()
+            .to_string();
+
+        for expected in [
+            quote!(SmallTalk::HowdyPartner(_) => "HOWDY_PARTNER"),
+            quote!(pub fn stream_key(&self) -> mavspec::rust::spec::StreamKey),
+            quote!(pub fn as_howdy_partner(&self)),
+            quote!(pub fn as_1st_class(&self)),
+            quote!(impl core::convert::TryFrom<SmallTalk> for FirstClass),
+        ] {
+            assert!(accessors.contains(&expected.to_string()), "{expected}");
+        }
+    }
+
+    #[test]
+    fn handler() {
+        let input = |attrs: proc_macro2::TokenStream| -> syn::DeriveInput {
+            syn::parse2(quote! {
+                #attrs
+                pub(crate) enum SmallTalk {
+                    HowdyPartner(HowdyPartner),
+                }
+            })
+            .unwrap()
+        };
+
+        let dialect = Dialect::try_from(input(quote!())).unwrap();
+        assert!(dialect.handler_impl().is_empty());
+
+        let dialect = Dialect::try_from(input(quote!(#[handler]))).unwrap();
+        let handler = dialect.handler_impl().to_string();
+        for expected in [
+            quote!(pub(crate) trait SmallTalkHandler),
+            quote!(fn on_howdy_partner(&mut self, message: &HowdyPartner)),
+            quote!(SmallTalk::HowdyPartner(message) => handler.on_howdy_partner(message),),
+        ] {
+            assert!(handler.contains(&expected.to_string()), "{expected}");
+        }
+    }
+
+    #[test]
+    fn invalid_message_name() {
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            enum SmallTalk {
+                #[canonical_name(FINE_AND_YOU)]
+                Fine(FineAndYou),
+            }
+        })
+        .unwrap();
+
+        assert!(matches!(
+            Dialect::try_from(input),
+            Err(Error::Dialect(DialectError::InvalidMessageName))
+        ));
+    }
+}
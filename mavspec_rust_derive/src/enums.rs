@@ -1,4 +1,6 @@
-use crate::consts::ATTR_REPR;
+use crate::consts::{
+    ATTR_CANONICAL_NAME, ATTR_DESCRIPTION, ATTR_NO_STR, ATTR_REPR, ATTR_STRIPPED_NAME,
+};
 use quote::{quote, ToTokens};
 
 use crate::errors::{EnumError, Error};
@@ -9,11 +11,15 @@ pub(crate) struct Enum {
     name: syn::LitStr,
     repr: ScalarType,
     variants: Vec<Variant>,
+    no_str: bool,
 }
 
 pub(crate) struct Variant {
     ident: syn::Ident,
     discriminant: syn::Expr,
+    name: syn::LitStr,
+    name_stripped: syn::LitStr,
+    description: syn::LitStr,
 }
 
 impl Variant {
@@ -24,6 +30,20 @@ impl Variant {
     pub(crate) fn discriminant(&self) -> &syn::Expr {
         &self.discriminant
     }
+
+    /// Canonical MAVLink name of the entry (i.e. `MAV_TYPE_QUADROTOR`).
+    pub(crate) fn name(&self) -> &syn::LitStr {
+        &self.name
+    }
+
+    /// Entry name without enum name prefix (i.e. `QUADROTOR`).
+    pub(crate) fn name_stripped(&self) -> &syn::LitStr {
+        &self.name_stripped
+    }
+
+    pub(crate) fn description(&self) -> &syn::LitStr {
+        &self.description
+    }
 }
 
 impl TryFrom<syn::DeriveInput> for Enum {
@@ -41,6 +61,11 @@ impl Enum {
             _ => return Err(EnumError::NotAnEnum.into()),
         };
 
+        let name = match Self::get_str_attr(value.attrs.as_slice(), ATTR_CANONICAL_NAME)? {
+            Some(name) => name,
+            None => Self::lit_str(heck::AsShoutySnakeCase(value.ident.to_string()).to_string()),
+        };
+
         let mut variants: Vec<Variant> = vec![];
         for variant in data.variants {
             let ident = variant.ident;
@@ -50,24 +75,41 @@ impl Enum {
                 Some((_, discriminant)) => discriminant,
             };
 
+            // Fall back to identifiers if names are not specified explicitly
+            let name_stripped =
+                match Self::get_str_attr(variant.attrs.as_slice(), ATTR_STRIPPED_NAME)? {
+                    Some(name_stripped) => name_stripped,
+                    None => Self::lit_str(heck::AsShoutySnakeCase(ident.to_string()).to_string()),
+                };
+            let variant_name =
+                match Self::get_str_attr(variant.attrs.as_slice(), ATTR_CANONICAL_NAME)? {
+                    Some(variant_name) => variant_name,
+                    None => Self::lit_str(format!("{}_{}", name.value(), name_stripped.value())),
+                };
+            let description = Self::get_str_attr(variant.attrs.as_slice(), ATTR_DESCRIPTION)?
+                .unwrap_or_else(|| Self::lit_str(String::new()));
+
             variants.push(Variant {
                 ident,
                 discriminant,
+                name: variant_name,
+                name_stripped,
+                description,
             });
         }
 
-        let name = syn::parse_str(
-            format!("\"{}\"", heck::AsShoutySnakeCase(value.ident.to_string())).as_str(),
-        )
-        .unwrap();
-
         let repr = Self::get_repr(value.attrs.as_slice())?;
+        let no_str = value
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident(ATTR_NO_STR));
 
         Ok(Self {
             ident: value.ident,
             name,
             repr,
             variants,
+            no_str,
         })
     }
 
@@ -87,7 +129,29 @@ impl Enum {
         self.variants.as_slice()
     }
 
+    /// Whether inherent name accessors, `Display`, and `FromStr` should not be generated.
+    pub(crate) fn no_str(&self) -> bool {
+        self.no_str
+    }
+
     pub(crate) fn to_token_stream(&self) -> proc_macro2::TokenStream {
+        let try_from_impl = self.try_from_impl();
+        let enum_spec_impl = self.enum_spec_impl();
+        let (metadata_impl, str_impl) = if self.no_str() {
+            (quote! {}, quote! {})
+        } else {
+            (self.metadata_impl(), self.str_impl())
+        };
+
+        quote! {
+            #try_from_impl
+            #enum_spec_impl
+            #metadata_impl
+            #str_impl
+        }
+    }
+
+    fn try_from_impl(&self) -> proc_macro2::TokenStream {
         let ident = self.ident();
         let name = self.name();
         let repr = self.repr().to_token_stream();
@@ -119,6 +183,113 @@ impl Enum {
         }
     }
 
+    fn enum_spec_impl(&self) -> proc_macro2::TokenStream {
+        let ident = self.ident();
+        let name = self.name();
+
+        let entries = self.variants().iter().map(|variant| {
+            let ident = variant.ident();
+            let name = variant.name();
+            let name_stripped = variant.name_stripped();
+            let description = variant.description();
+            quote! {
+                mavspec::rust::spec::EnumEntryInfo::new(Self::#ident, #name, #name_stripped, #description)
+            }
+        });
+        let entry_info_arms = self.variants().iter().enumerate().map(|(idx, variant)| {
+            let ident = variant.ident();
+            quote! { Self::#ident => &Self::ENTRIES[#idx] }
+        });
+
+        quote! {
+            impl mavspec::rust::spec::EnumSpec for #ident {
+                const NAME: &'static str = #name;
+                const ENTRIES: &'static [mavspec::rust::spec::EnumEntryInfo<Self>] = &[
+                    #(#entries,)*
+                ];
+
+                fn entry_info(&self) -> Option<&'static mavspec::rust::spec::EnumEntryInfo<Self>> {
+                    Some(match *self {
+                        #(#entry_info_arms,)*
+                    })
+                }
+            }
+        }
+    }
+
+    fn metadata_impl(&self) -> proc_macro2::TokenStream {
+        let ident = self.ident();
+
+        let name_arms = self.variants().iter().map(|variant| {
+            let ident = variant.ident();
+            let name = variant.name();
+            quote! { Self::#ident => #name }
+        });
+        let name_stripped_arms = self.variants().iter().map(|variant| {
+            let ident = variant.ident();
+            let name_stripped = variant.name_stripped();
+            quote! { Self::#ident => #name_stripped }
+        });
+        let description_arms = self.variants().iter().map(|variant| {
+            let ident = variant.ident();
+            let description = variant.description();
+            quote! { Self::#ident => #description }
+        });
+
+        quote! {
+            impl #ident {
+                /// Canonical MAVLink name of this entry.
+                pub const fn name(&self) -> &'static str {
+                    match *self {
+                        #(#name_arms,)*
+                    }
+                }
+
+                /// Name of this entry without enum name prefix.
+                pub const fn name_stripped(&self) -> &'static str {
+                    match *self {
+                        #(#name_stripped_arms,)*
+                    }
+                }
+
+                /// Description of this entry (empty if not specified).
+                pub const fn description(&self) -> &'static str {
+                    match *self {
+                        #(#description_arms,)*
+                    }
+                }
+            }
+        }
+    }
+
+    fn str_impl(&self) -> proc_macro2::TokenStream {
+        let ident = self.ident();
+        let name = self.name();
+
+        quote! {
+            impl core::fmt::Display for #ident {
+                /// Formats entry as its canonical MAVLink name.
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    f.write_str(self.name())
+                }
+            }
+
+            impl core::str::FromStr for #ident {
+                type Err = mavspec::rust::spec::SpecError;
+
+                /// Parses entry from canonical or prefix-stripped MAVLink name (case-insensitive).
+                fn from_str(s: &str) -> Result<Self, mavspec::rust::spec::SpecError> {
+                    match <Self as mavspec::rust::spec::EnumSpec>::entry_by_name(s) {
+                        Some(entry) => Ok(entry.value()),
+                        None => Err(mavspec::rust::spec::SpecError::InvalidEnumName {
+                            enum_name: #name,
+                        }),
+                    }
+                }
+            }
+        }
+    }
+
     fn get_repr(attrs: &[syn::Attribute]) -> Result<ScalarType, Error> {
         for attr in attrs {
             if let Some(attr_ident) = attr.path().get_ident() {
@@ -132,4 +303,99 @@ impl Enum {
 
         Err(EnumError::ReprIsMissing.into())
     }
+
+    fn get_str_attr(
+        attrs: &[syn::Attribute],
+        attr_name: &'static str,
+    ) -> Result<Option<syn::LitStr>, Error> {
+        for attr in attrs {
+            if let Some(attr_ident) = attr.path().get_ident() {
+                if attr_ident == attr_name {
+                    return match attr.parse_args::<syn::LitStr>() {
+                        Ok(value) => Ok(Some(value)),
+                        Err(_) => Err(EnumError::InvalidStrAttribute(attr_name).into()),
+                    };
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn lit_str(value: String) -> syn::LitStr {
+        syn::LitStr::new(value.as_str(), proc_macro2::Span::call_site())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+/////                               TESTS                                 /////
+///////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variant_names() {
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            #[repr(u8)]
+            enum MavMood {
+                Serious = 0,
+                #[canonical_name("MAV_MOOD_VERY_GRUMPY")]
+                #[stripped_name("VERY_GRUMPY")]
+                #[description("Mood before coffee.")]
+                Grumpy = 1,
+            }
+        })
+        .unwrap();
+        let mav_enum = Enum::try_from(input).unwrap();
+
+        assert_eq!(mav_enum.name().value(), "MAV_MOOD");
+
+        let serious = &mav_enum.variants()[0];
+        assert_eq!(serious.name().value(), "MAV_MOOD_SERIOUS");
+        assert_eq!(serious.name_stripped().value(), "SERIOUS");
+        assert_eq!(serious.description().value(), "");
+
+        let grumpy = &mav_enum.variants()[1];
+        assert_eq!(grumpy.name().value(), "MAV_MOOD_VERY_GRUMPY");
+        assert_eq!(grumpy.name_stripped().value(), "VERY_GRUMPY");
+        assert_eq!(grumpy.description().value(), "Mood before coffee.");
+    }
+
+    #[test]
+    fn no_str_attribute() {
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            #[repr(u8)]
+            #[no_str]
+            enum MavMood {
+                Serious = 0,
+            }
+        })
+        .unwrap();
+        let mav_enum = Enum::try_from(input).unwrap();
+
+        assert!(mav_enum.no_str());
+        let tokens = mav_enum.to_token_stream().to_string();
+        assert!(!tokens.contains("Display"));
+        assert!(!tokens.contains("FromStr"));
+    }
+
+    #[test]
+    fn invalid_name_attribute() {
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            #[repr(u8)]
+            #[canonical_name(MAV_MOOD)]
+            enum MavMood {
+                Serious = 0,
+            }
+        })
+        .unwrap();
+
+        assert!(matches!(
+            Enum::try_from(input),
+            Err(Error::Enum(EnumError::InvalidStrAttribute(
+                ATTR_CANONICAL_NAME
+            )))
+        ));
+    }
 }
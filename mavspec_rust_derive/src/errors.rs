@@ -59,6 +59,8 @@ pub(crate) enum TypeError {
     InvalidArrayElement(String),
     #[error("invalid scalar type: {0}")]
     InvalidScalar(String),
+    #[error("invalid string type: {0}, expected `MavString<N>`")]
+    InvalidString(String),
     #[error("type parsing error: {0}")]
     ParseError(syn::Error),
 }
@@ -71,6 +73,14 @@ pub(crate) enum FieldError {
     InvalidBaseTypeArgument(String),
     #[error("enum representation should be an integer numeric type, field: `{0}`")]
     NonIntegerRepr(String),
+    #[error("`quantity` fields require `base_type` attribute, field: `{0}`")]
+    QuantityWithoutBaseType(String),
+    #[error("can't parse `default_value` argument: {0}")]
+    DefaultValueParseError(syn::Error),
+    #[error("`MavString` fields do not support `{1}` attribute, field: `{0}`")]
+    UnsupportedStringAttribute(String, &'static str),
+    #[error("`instance` fields should be unsigned integers up to 32 bits, enums, or `MavString`, field: `{0}`")]
+    InvalidInstanceType(String),
 }
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -83,6 +93,8 @@ pub(crate) enum SpecError {
     CrcExtraNonLiteralArrayLength,
     #[error("`Message` can't calculate `CRC_EXTRA` byte due to an invalid array length")]
     CrcExtraInvalidArrayLength,
+    #[error("`Message` can have at most one `instance` field")]
+    MultipleInstanceFields,
 }
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -95,6 +107,8 @@ pub(crate) enum EnumError {
         "all variants should have explicit discriminants, but enum variant `{0}` is missing one"
     )]
     MissingDiscriminant(String),
+    #[error("`{0}` attribute should be a string literal. For example: #[{0}(\"MAV_TYPE\")]")]
+    InvalidStrAttribute(&'static str),
 }
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -111,4 +125,32 @@ pub(crate) enum DialectError {
     InvalidDialectId,
     #[error("#[version(..)] attribute for `Dialect` should be a u8 integer")]
     InvalidDialectVersion,
+    #[error("#[canonical_name(..)] attribute for `Dialect` variant should be a string")]
+    InvalidMessageName,
+}
+
+#[cfg(feature = "include_dialects")]
+#[derive(Debug, Clone, thiserror::Error)]
+pub(crate) enum IncludeDialectsError {
+    #[error("unknown `include_dialects!` option: `{0}`")]
+    UnknownOption(String),
+    #[error("`include_dialects!` option `{0}` is specified more than once")]
+    DuplicateOption(String),
+    #[error("`include_dialects!` does not support `output_layout` option: generated code is expanded in place as inline modules, which corresponds to `Modules` layout only")]
+    UnsupportedOutputLayout,
+    #[error("`include_dialects!` requires `sources` option. For example: sources = [\"message_definitions/standard\"]")]
+    MissingSources,
+    #[error("`CARGO_MANIFEST_DIR` is not set, `include_dialects!` should be invoked by Cargo")]
+    MissingManifestDir,
+    #[error("source file path is not a valid UTF-8 string: {0:?}")]
+    InvalidPath(std::path::PathBuf),
+    #[error("failed to generate MAVLink dialects: {0}")]
+    Generation(String),
+}
+
+#[cfg(feature = "include_dialects")]
+impl From<mavspec_rust_gen::error::RustGenError> for IncludeDialectsError {
+    fn from(value: mavspec_rust_gen::error::RustGenError) -> Self {
+        Self::Generation(value.to_string())
+    }
 }
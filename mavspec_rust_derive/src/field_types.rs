@@ -22,8 +22,13 @@ pub(crate) enum ScalarType {
     UInt64,
     Float32,
     Float64,
+    /// MAVLink `char`, only available as an item of `MavString<N>`.
+    Char,
 }
 
+/// Name of MAVLink string type which represents `char[N]` fields.
+const MAV_STRING_TYPE_NAME: &str = "MavString";
+
 impl ScalarType {
     pub(crate) fn c_type(&self) -> String {
         match self {
@@ -37,6 +42,7 @@ impl ScalarType {
             ScalarType::UInt64 => "uint64_t".to_string(),
             ScalarType::Float32 => "float".to_string(),
             ScalarType::Float64 => "double".to_string(),
+            ScalarType::Char => "char".to_string(),
         }
     }
 }
@@ -112,11 +118,46 @@ impl FieldType {
 
                 Self::Array(scalar_type, arr.len)
             }
-            syn::Type::Path(path) => Self::Scalar(path.try_into()?),
+            syn::Type::Path(path) => match Self::mav_string_length(&path)? {
+                Some(len) => Self::Array(ScalarType::Char, len),
+                None => Self::Scalar(path.try_into()?),
+            },
             _ => return Err(TypeError::Invalid(value.to_token_stream().to_string()).into()),
         })
     }
 
+    /// Extracts length `N` from `MavString<N>` type path.
+    fn mav_string_length(path: &syn::TypePath) -> Result<Option<syn::Expr>, Error> {
+        let Some(segment) = path.path.segments.last() else {
+            return Ok(None);
+        };
+        if segment.ident != MAV_STRING_TYPE_NAME {
+            return Ok(None);
+        }
+
+        let invalid = || TypeError::InvalidString(path.to_token_stream().to_string());
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return Err(invalid().into());
+        };
+        if args.args.len() != 1 {
+            return Err(invalid().into());
+        }
+
+        match args.args.first().unwrap() {
+            syn::GenericArgument::Const(expr) => Ok(Some(expr.clone())),
+            // Constant names like `MavString<LEN>` are parsed as types
+            syn::GenericArgument::Type(ty) => Ok(Some(
+                syn::parse2(ty.to_token_stream()).map_err(|_| invalid())?,
+            )),
+            _ => Err(invalid().into()),
+        }
+    }
+
+    /// Whether field type is `MavString<N>` which represents MAVLink `char[N]`.
+    pub(crate) fn is_string(&self) -> bool {
+        matches!(self, FieldType::Array(ScalarType::Char, _))
+    }
+
     pub(crate) fn try_from_token_stream(value: proc_macro2::TokenStream) -> Result<Self, Error> {
         match syn::parse2(value) {
             Ok(value) => Self::try_from_syn_type(value),
@@ -208,6 +249,7 @@ impl ScalarType {
             ScalarType::UInt64 => quote!(u64),
             ScalarType::Float32 => quote!(f32),
             ScalarType::Float64 => quote!(f64),
+            ScalarType::Char => quote!(u8),
         }
     }
 
@@ -223,6 +265,7 @@ impl ScalarType {
             ScalarType::UInt64 => quote!(8),
             ScalarType::Float32 => quote!(4),
             ScalarType::Float64 => quote!(8),
+            ScalarType::Char => quote!(1),
         }
     }
 
@@ -238,6 +281,7 @@ impl ScalarType {
             ScalarType::UInt64 => 8,
             ScalarType::Float32 => 4,
             ScalarType::Float64 => 8,
+            ScalarType::Char => 1,
         }
     }
 
@@ -267,6 +311,15 @@ mod tests {
         assert!(matches!(type_, FieldType::Array(ScalarType::Float64, _)));
         assert_eq!(type_.to_token_stream().to_string().as_str(), "[f64 ; 4]");
 
+        let type_ = FieldType::try_from(quote!(mavspec::rust::spec::MavString<16>))?;
+        assert!(matches!(type_, FieldType::Array(ScalarType::Char, _)));
+        assert!(type_.is_string());
+        assert_eq!(type_.to_token_stream().to_string().as_str(), "[u8 ; 16]");
+        assert_eq!(type_.base_type().c_type(), "char");
+
+        assert!(FieldType::try_from(quote!(MavString)).is_err());
+        assert!(FieldType::try_from(quote!(char)).is_err());
+
         Ok(())
     }
 
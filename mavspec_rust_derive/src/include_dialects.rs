@@ -0,0 +1,286 @@
+use std::path::{Path, PathBuf};
+
+use mavspec_rust_gen::{BuildHelper, TypeAttributes, TypeKind};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+
+use crate::errors::IncludeDialectsError;
+
+/// Options which accept an array of string literals.
+const LIST_OPTIONS: &[&str] = &[
+    "sources",
+    "include_dialects",
+    "exclude_dialects",
+    "messages",
+    "enums",
+    "commands",
+    "microservices",
+    "test_vectors",
+];
+/// Options which accept a string literal.
+const STR_OPTIONS: &[&str] = &["manifest_path", "wip", "serde_enums"];
+/// Options which accept a boolean literal.
+const BOOL_OPTIONS: &[&str] = &[
+    "serde",
+    "arbitrary",
+    "async_handlers",
+    "generate_tests",
+    "generate_builders",
+    "typed_units",
+];
+/// Keys of `type_attributes` tables, each accepts an array of string literals.
+const TYPE_ATTRIBUTES_KEYS: &[&str] = &["kinds", "names", "derives", "attributes"];
+
+/// Input of `include_dialects!` macro: comma-separated `option = value` pairs.
+///
+/// Options correspond to [`mavspec_rust_gen::BuildHelperBuilder`] setters.
+pub(crate) struct IncludeDialects {
+    options: Vec<IncludeDialectsOption>,
+}
+
+struct IncludeDialectsOption {
+    ident: syn::Ident,
+    name: String,
+    value: OptionValue,
+}
+
+enum OptionValue {
+    Bool(bool),
+    Str(String),
+    List(Vec<String>),
+    TypeAttributes(Vec<TypeAttributes>),
+}
+
+/// Table of `type_attributes` option: `{ kinds = [..], names = [..], derives = [..], attributes = [..] }`.
+struct TypeAttributesTable(TypeAttributes);
+
+impl Parse for IncludeDialects {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let options = Punctuated::<IncludeDialectsOption, syn::Token![,]>::parse_terminated(input)?;
+
+        let mut names: Vec<&str> = Vec::new();
+        for option in &options {
+            if names.contains(&option.name.as_str()) {
+                return Err(syn::Error::new(
+                    option.ident.span(),
+                    IncludeDialectsError::DuplicateOption(option.name.clone()),
+                ));
+            }
+            names.push(option.name.as_str());
+        }
+
+        Ok(Self {
+            options: options.into_iter().collect(),
+        })
+    }
+}
+
+impl Parse for IncludeDialectsOption {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: syn::Ident = input.parse()?;
+        let name = ident.to_string();
+        input.parse::<syn::Token![=]>()?;
+
+        let value = if LIST_OPTIONS.contains(&name.as_str()) {
+            let content;
+            syn::bracketed!(content in input);
+            let items = Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated(&content)?;
+            OptionValue::List(items.iter().map(syn::LitStr::value).collect())
+        } else if STR_OPTIONS.contains(&name.as_str()) {
+            OptionValue::Str(input.parse::<syn::LitStr>()?.value())
+        } else if BOOL_OPTIONS.contains(&name.as_str()) {
+            OptionValue::Bool(input.parse::<syn::LitBool>()?.value)
+        } else if name == "type_attributes" {
+            let content;
+            syn::bracketed!(content in input);
+            let tables =
+                Punctuated::<TypeAttributesTable, syn::Token![,]>::parse_terminated(&content)?;
+            OptionValue::TypeAttributes(tables.into_iter().map(|table| table.0).collect())
+        } else if name == "output_layout" {
+            return Err(syn::Error::new(
+                ident.span(),
+                IncludeDialectsError::UnsupportedOutputLayout,
+            ));
+        } else {
+            return Err(syn::Error::new(
+                ident.span(),
+                IncludeDialectsError::UnknownOption(name),
+            ));
+        };
+
+        Ok(Self { ident, name, value })
+    }
+}
+
+impl Parse for TypeAttributesTable {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        syn::braced!(content in input);
+
+        let mut type_attributes = TypeAttributes::default();
+        let mut keys: Vec<String> = Vec::new();
+        while !content.is_empty() {
+            let ident: syn::Ident = content.parse()?;
+            let key = ident.to_string();
+            if !TYPE_ATTRIBUTES_KEYS.contains(&key.as_str()) {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    IncludeDialectsError::UnknownOption(format!("type_attributes.{key}")),
+                ));
+            }
+            if keys.contains(&key) {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    IncludeDialectsError::DuplicateOption(format!("type_attributes.{key}")),
+                ));
+            }
+            content.parse::<syn::Token![=]>()?;
+
+            let items;
+            syn::bracketed!(items in content);
+            let items = Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated(&items)?;
+            match key.as_str() {
+                "kinds" => {
+                    for item in &items {
+                        let kind: TypeKind = item
+                            .value()
+                            .parse()
+                            .map_err(|err| syn::Error::new(item.span(), err))?;
+                        type_attributes.kinds.push(kind);
+                    }
+                }
+                "names" => type_attributes.names = items.iter().map(syn::LitStr::value).collect(),
+                "derives" => {
+                    type_attributes.derives = items.iter().map(syn::LitStr::value).collect()
+                }
+                "attributes" => {
+                    type_attributes.attributes = items.iter().map(syn::LitStr::value).collect()
+                }
+                _ => unreachable!("keys are checked above"),
+            }
+            keys.push(key);
+
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<syn::Token![,]>()?;
+        }
+
+        Ok(Self(type_attributes))
+    }
+}
+
+impl IncludeDialects {
+    /// Generates dialects and a constant which includes all source files, so compiler tracks their changes.
+    pub(crate) fn expand(&self) -> Result<proc_macro2::TokenStream, IncludeDialectsError> {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .map_err(|_| IncludeDialectsError::MissingManifestDir)?;
+        let build_helper = self.build_helper(&manifest_dir)?;
+
+        let code: proc_macro2::TokenStream =
+            build_helper
+                .generate_code()?
+                .parse()
+                .map_err(|err: proc_macro2::LexError| {
+                    IncludeDialectsError::Generation(err.to_string())
+                })?;
+
+        let source_files = build_helper
+            .source_files()?
+            .into_iter()
+            .map(|path| {
+                path.to_str()
+                    .map(str::to_string)
+                    .ok_or(IncludeDialectsError::InvalidPath(path))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(quote! {
+            #code
+
+            const _: &[&[u8]] = &[#(include_bytes!(#source_files)),*];
+        })
+    }
+
+    fn build_helper(&self, manifest_dir: &Path) -> Result<BuildHelper, IncludeDialectsError> {
+        let mut builder = BuildHelper::builder(PathBuf::new());
+
+        // Sources should be set first since they discard manifest path
+        match self.option("sources") {
+            Some(OptionValue::List(sources)) => {
+                let sources: Vec<PathBuf> = sources.iter().map(|s| manifest_dir.join(s)).collect();
+                builder.set_sources(&sources);
+            }
+            _ => return Err(IncludeDialectsError::MissingSources),
+        }
+
+        for option in &self.options {
+            match (option.name.as_str(), &option.value) {
+                ("manifest_path", OptionValue::Str(path)) => {
+                    builder.set_manifest_path(&manifest_dir.join(path));
+                }
+                ("include_dialects", OptionValue::List(dialects)) => {
+                    builder.set_include_dialects(dialects);
+                }
+                ("exclude_dialects", OptionValue::List(dialects)) => {
+                    builder.set_exclude_dialects(dialects);
+                }
+                ("messages", OptionValue::List(messages)) => {
+                    builder.set_messages(messages);
+                }
+                ("enums", OptionValue::List(enums)) => {
+                    builder.set_enums(enums);
+                }
+                ("commands", OptionValue::List(commands)) => {
+                    builder.set_commands(commands);
+                }
+                ("microservices", OptionValue::List(microservices)) => {
+                    builder.set_microservices(microservices);
+                }
+                ("test_vectors", OptionValue::List(paths)) => {
+                    let paths: Vec<PathBuf> = paths.iter().map(|p| manifest_dir.join(p)).collect();
+                    builder.set_test_vectors(&paths);
+                }
+                ("wip", OptionValue::Str(wip)) => {
+                    builder.set_wip(wip.parse()?);
+                }
+                ("serde_enums", OptionValue::Str(serde_enums)) => {
+                    builder.set_serde_enums(serde_enums.parse()?);
+                }
+                ("serde", OptionValue::Bool(value)) => {
+                    builder.set_serde(*value);
+                }
+                ("arbitrary", OptionValue::Bool(value)) => {
+                    builder.set_arbitrary(*value);
+                }
+                ("async_handlers", OptionValue::Bool(value)) => {
+                    builder.set_async_handlers(*value);
+                }
+                ("generate_tests", OptionValue::Bool(value)) => {
+                    builder.set_generate_tests(*value);
+                }
+                ("generate_builders", OptionValue::Bool(value)) => {
+                    builder.set_generate_builders(*value);
+                }
+                ("typed_units", OptionValue::Bool(value)) => {
+                    builder.set_typed_units(*value);
+                }
+                ("type_attributes", OptionValue::TypeAttributes(type_attributes)) => {
+                    builder.set_type_attributes(type_attributes);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(builder.build()?)
+    }
+
+    fn option(&self, name: &str) -> Option<&OptionValue> {
+        self.options
+            .iter()
+            .find(|option| option.name == name)
+            .map(|option| &option.value)
+    }
+}
@@ -83,6 +83,8 @@ pub(crate) mod message_field;
 
 mod dialect;
 pub(crate) mod enums;
+#[cfg(feature = "include_dialects")]
+mod include_dialects;
 pub(crate) mod message;
 
 /// Derive MAVLink message from struct.
@@ -120,6 +122,35 @@ pub(crate) mod message;
 /// assert_eq!(CustomMessage::crc_extra(), 32);
 /// ```
 ///
+/// Message metadata is available as associated constants, which can be used in `match` patterns or to size buffers:
+///
+/// ```rust
+/// use mavspec::rust::derive::Message;
+/// use mavspec::rust::spec::MavLinkVersion;
+///
+/// #[derive(Clone, Debug, Message)]
+/// #[message_id(255)]
+/// #[crc_extra(32)]
+/// struct CustomMessage {
+///     scalar_u8: u8,
+///     array_u16_4: [u16; 4],
+///     #[extension]
+///     ext_u32: u32,
+/// }
+///
+/// assert_eq!(CustomMessage::ID, 255);
+/// assert_eq!(CustomMessage::CRC_EXTRA, 32);
+/// assert_eq!(CustomMessage::PAYLOAD_SIZE_V1, 9);
+/// assert_eq!(CustomMessage::PAYLOAD_SIZE_V2, 13);
+/// assert_eq!(CustomMessage::MIN_VERSION, MavLinkVersion::V1);
+///
+/// let buffer = [0u8; CustomMessage::PAYLOAD_SIZE_V2];
+/// match 255 {
+///     CustomMessage::ID => assert_eq!(buffer.len(), 13),
+///     _ => unreachable!(),
+/// }
+/// ```
+///
 /// Auto-calculated `CRC_EXTRA` is not supported for arrays with lengths specified by constants. The
 /// following won't compile:
 ///
@@ -226,9 +257,157 @@ pub(crate) mod message;
 ///     large_array_u16_4: [Flags; 4],
 /// }
 /// ```
+///
+/// ## Units
+///
+/// Fields can be represented as `mavspec::rust::spec::units::Quantity` tagged with MAVLink units. Such fields should
+/// be marked with `#[quantity]` attribute and have `base_type` set to the type of the raw value. Wire representation
+/// is the same as for the raw value.
+///
+/// ```rust
+/// use mavspec::rust::derive::Message;
+/// use mavspec::rust::spec::units::{DegreesE7, MilliMetres, Quantity};
+///
+/// #[derive(Clone, Debug, Message)]
+/// #[message_id(255)]
+/// struct CustomMessage {
+///     #[quantity]
+///     #[base_type(i32)]
+///     lat: Quantity<i32, DegreesE7>,
+///
+///     #[quantity]
+///     #[base_type(i32)]
+///     alt: [Quantity<i32, MilliMetres>; 4],
+/// }
+/// ```
+///
+/// ## Strings
+///
+/// MAVLink `char[N]` fields are represented by `mavspec::rust::spec::MavString<N>`. Wire representation is the same as
+/// for `[u8; N]`, while auto-calculated `CRC_EXTRA` treats such fields as `char` arrays. String fields do not support
+/// `repr_type`, `bitmask`, `quantity`, and `default_value` attributes.
+///
+/// ```rust
+/// use mavspec::rust::derive::Message;
+/// use mavspec::rust::spec::{MavLinkVersion, MavString, IntoPayload};
+///
+/// // `PARAM_REQUEST_READ` message from `common` dialect
+/// #[derive(Clone, Debug, Message)]
+/// #[message_id(20)]
+/// #[crc_extra(214)]
+/// struct ParamRequestRead {
+///     target_system: u8,
+///     target_component: u8,
+///     param_id: MavString<16>,
+///     param_index: i16,
+/// }
+///
+/// let message = ParamRequestRead {
+///     param_id: "SYSID_THISMAV".parse().unwrap(),
+///     param_index: -1,
+///     ..Default::default()
+/// };
+/// let payload = message.encode(MavLinkVersion::V2).unwrap();
+/// let decoded = ParamRequestRead::try_from(&payload).unwrap();
+/// assert_eq!(decoded.param_id, "SYSID_THISMAV");
+/// ```
+///
+/// ## Default values
+///
+/// By default, [`Default`] implementation sets all fields to their default values. Use `#[default_value(..)]` attribute
+/// to specify a raw value of `base_type` instead. For arrays, all items are set to this value. Enum and bitmask values
+/// are converted from the raw value, enums fall back to their default variant if raw value is not a valid
+/// discriminant.
+///
+/// ```rust
+/// use mavspec::rust::derive::Message;
+///
+/// #[derive(Clone, Debug, Message)]
+/// #[message_id(255)]
+/// struct CustomMessage {
+///     #[default_value(u16::MAX)]
+///     heading: u16,
+///
+///     #[default_value(f32::NAN)]
+///     quaternion: [f32; 4],
+/// }
+///
+/// let message = CustomMessage::default();
+/// assert_eq!(message.heading, u16::MAX);
+/// assert!(message.quaternion[0].is_nan());
+/// ```
+///
+/// ## Constructors and builders
+///
+/// Structs marked with `#[builder]` attribute get a `new(..)` constructor which takes all non-extension fields in the
+/// order of their declaration, and a `with_<field>(value)` setter for each field (leading and trailing underscores of
+/// field names are omitted). Extension fields are set to their default values by constructor and can be set by the
+/// corresponding setters.
+///
+/// ```rust
+/// use mavspec::rust::derive::Message;
+///
+/// #[derive(Clone, Debug, Message)]
+/// #[message_id(255)]
+/// #[builder]
+/// struct CustomMessage {
+///     heading: u16,
+///     r#type: u8,
+///
+///     #[extension]
+///     #[default_value(u32::MAX)]
+///     time_boot_ms: u32,
+/// }
+///
+/// let message = CustomMessage::new(90, 1);
+/// assert_eq!(message.heading, 90);
+/// assert_eq!(message.time_boot_ms, u32::MAX);
+///
+/// let message = message.with_type(2).with_time_boot_ms(42);
+/// assert_eq!(message.r#type, 2);
+/// assert_eq!(message.time_boot_ms, 42);
+/// ```
+///
+/// ## Instances
+///
+/// A field which identifies a message stream (i.e. `BATTERY_STATUS.id`) can be marked with `#[instance]` attribute.
+/// Such message gets an `instance()` accessor, and its `mavspec::rust::spec::Message::stream_key` combines message ID
+/// with the value of this field. Instance fields should be `u8`, `u16`, or `u32` integers, enums with such base types,
+/// or strings. At most one field can be marked as `instance`.
+///
+/// ```rust
+/// use mavspec::rust::derive::Message;
+/// use mavspec::rust::spec::{Message as _, MessageInstance, StreamKey};
+///
+/// #[derive(Clone, Debug, Message)]
+/// #[message_id(255)]
+/// struct CustomMessage {
+///     #[instance]
+///     id: u8,
+///     voltage: u16,
+/// }
+///
+/// let message = CustomMessage { id: 2, voltage: 12000 };
+/// assert_eq!(message.instance(), 2);
+/// assert_eq!(
+///     message.stream_key(),
+///     StreamKey::new(255, Some(MessageInstance::Index(2)))
+/// );
+/// ```
 #[proc_macro_derive(
     Message,
-    attributes(message_id, crc_extra, extension, base_type, repr_type, bitmask)
+    attributes(
+        message_id,
+        crc_extra,
+        builder,
+        extension,
+        instance,
+        base_type,
+        repr_type,
+        bitmask,
+        quantity,
+        default_value
+    )
 )]
 pub fn derive_mavlink_message(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input: DeriveInput = syn::parse(input).unwrap();
@@ -279,7 +458,91 @@ pub fn derive_mavlink_message(input: proc_macro::TokenStream) -> proc_macro::Tok
 /// }
 /// ```
 ///
-#[proc_macro_derive(Enum)]
+/// ## Metadata
+///
+/// Derived enums implement `mavspec::rust::spec::EnumSpec` which provides canonical enum name and a static table of
+/// entries with their values, names, and descriptions. Each value also has `name()`, `name_stripped()`, and
+/// `description()` methods.
+///
+/// By default, canonical enum name is a `SHOUTY_SNAKE_CASE` of enum identifier, stripped names of entries are
+/// `SHOUTY_SNAKE_CASE` variant identifiers, and canonical names of entries are stripped names prefixed with enum name.
+/// Use `canonical_name`, `stripped_name`, and `description` attributes to specify them explicitly.
+///
+/// ```rust
+/// use mavspec::rust::derive::Enum;
+/// use mavspec::rust::spec::EnumSpec;
+///
+/// #[repr(u8)]
+/// #[derive(Clone, Copy, Debug, Default, Enum)]
+/// #[canonical_name("MOOD")]
+/// enum CustomEnum {
+///     #[default]
+///     Serious = 0,
+///     #[stripped_name("VERY_GRUMPY")]
+///     #[description("Mood before coffee.")]
+///     Grumpy = 1,
+/// }
+///
+/// assert_eq!(CustomEnum::NAME, "MOOD");
+/// assert_eq!(CustomEnum::ENTRIES.len(), 2);
+/// assert_eq!(CustomEnum::Serious.name(), "MOOD_SERIOUS");
+/// assert_eq!(CustomEnum::Grumpy.name(), "MOOD_VERY_GRUMPY");
+/// assert_eq!(CustomEnum::Grumpy.name_stripped(), "VERY_GRUMPY");
+/// assert_eq!(CustomEnum::Grumpy.description(), "Mood before coffee.");
+/// ```
+///
+/// ## Parsing & Formatting
+///
+/// Derived enums implement [`Display`](core::fmt::Display) which writes canonical name of the entry, and
+/// [`FromStr`](core::str::FromStr) which accepts canonical and stripped names in any case. Unknown names are rejected
+/// with `SpecError::InvalidEnumName`.
+///
+/// ```rust
+/// use mavspec::rust::derive::Enum;
+///
+/// #[repr(u8)]
+/// #[derive(Clone, Copy, Debug, Default, PartialEq, Enum)]
+/// #[canonical_name("MOOD")]
+/// enum CustomEnum {
+///     #[default]
+///     Serious = 0,
+///     Grumpy = 1,
+/// }
+///
+/// assert_eq!(CustomEnum::Grumpy.to_string(), "MOOD_GRUMPY");
+/// assert_eq!("MOOD_GRUMPY".parse::<CustomEnum>().unwrap(), CustomEnum::Grumpy);
+/// assert_eq!("grumpy".parse::<CustomEnum>().unwrap(), CustomEnum::Grumpy);
+/// assert!("MOOD_HAPPY".parse::<CustomEnum>().is_err());
+/// ```
+///
+/// Enums which define their own `name()`, `name_stripped()`, or `description()` methods, or implement
+/// [`Display`](core::fmt::Display) or [`FromStr`](core::str::FromStr) themselves, may opt out of these with `no_str`
+/// attribute. Metadata is still available through `EnumSpec`.
+///
+/// ```rust
+/// use mavspec::rust::derive::Enum;
+/// use mavspec::rust::spec::EnumSpec;
+///
+/// #[repr(u8)]
+/// #[derive(Clone, Copy, Debug, Default, Enum)]
+/// #[canonical_name("MOOD")]
+/// #[no_str]
+/// enum CustomEnum {
+///     #[default]
+///     Serious = 0,
+///     Grumpy = 1,
+/// }
+///
+/// impl core::fmt::Display for CustomEnum {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         f.write_str("mood")
+///     }
+/// }
+///
+/// assert_eq!(CustomEnum::Grumpy.to_string(), "mood");
+/// assert_eq!(CustomEnum::Grumpy.entry_info().unwrap().name(), "MOOD_GRUMPY");
+/// ```
+#[proc_macro_derive(Enum, attributes(canonical_name, stripped_name, description, no_str))]
 pub fn derive_mavlink_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input: DeriveInput = syn::parse(input).unwrap();
 
@@ -332,7 +595,114 @@ pub fn derive_mavlink_enum(input: proc_macro::TokenStream) -> proc_macro::TokenS
 ///     FineAndYou(FineAndYou),
 /// }
 /// ```
-#[proc_macro_derive(Dialect, attributes(name, dialect, version))]
+///
+/// # Accessors and conversions
+///
+/// Derived dialects provide `MESSAGE_COUNT` and `MAX_PAYLOAD_SIZE` constants, `message_name()` which returns canonical
+/// MAVLink name of the contained message, `stream_key()` which combines message ID with message instance (see
+/// `#[instance]` field attribute), and an `as_<variant>()` accessor for each variant (variant identifiers are converted
+/// to `snake_case`). Each message type implements `TryFrom<Dialect>` which returns original value if it contains
+/// another message. Message types should be unique within a dialect.
+///
+/// ```rust
+/// use mavspec::rust::derive::{Dialect, Message};
+///
+/// #[derive(Clone, Debug, Message)]
+/// #[message_id(42)]
+/// struct Howdy {
+///     mood: u8,
+/// }
+///
+/// #[derive(Clone, Debug, Message)]
+/// #[message_id(43)]
+/// struct FineAndYou {
+///     mood: u8,
+/// }
+///
+/// #[derive(Clone, Debug, Dialect)]
+/// enum SmallTalk {
+///     Howdy(Howdy),
+///     #[canonical_name("FINE_AND_YOU")]
+///     Fine(FineAndYou),
+/// }
+///
+/// assert_eq!(SmallTalk::MESSAGE_COUNT, 2);
+/// assert_eq!(SmallTalk::MAX_PAYLOAD_SIZE, 1);
+///
+/// let message = SmallTalk::Fine(FineAndYou { mood: 1 });
+/// assert_eq!(message.message_name(), "FINE_AND_YOU");
+/// assert_eq!(message.stream_key().id(), 43);
+/// assert_eq!(message.as_fine().unwrap().mood, 1);
+/// assert!(message.as_howdy().is_none());
+///
+/// assert!(Howdy::try_from(message.clone()).is_err());
+/// assert_eq!(FineAndYou::try_from(message).unwrap().mood, 1);
+/// ```
+///
+/// # Handlers
+///
+/// Dialects marked with `#[handler]` attribute get a `<Dialect>Handler` trait with an `on_<variant>()` method for each
+/// variant and a `dispatch()` method which calls the corresponding handler method. By default, handler methods fall
+/// through to `on_unhandled()` which does nothing unless overridden. When `async` feature is enabled, the same is
+/// provided by `<Dialect>AsyncHandler` trait and `dispatch_async()` method.
+///
+/// ```rust
+/// use mavspec::rust::derive::{Dialect, Message};
+/// use mavspec::rust::spec::Message;
+///
+/// #[derive(Clone, Debug, Message)]
+/// #[message_id(42)]
+/// struct Howdy {
+///     mood: u8,
+/// }
+///
+/// #[derive(Clone, Debug, Message)]
+/// #[message_id(43)]
+/// struct FineAndYou {
+///     mood: u8,
+/// }
+///
+/// #[derive(Clone, Debug, Dialect)]
+/// #[handler]
+/// enum SmallTalk {
+///     Howdy(Howdy),
+///     FineAndYou(FineAndYou),
+/// }
+///
+/// #[derive(Default)]
+/// struct Listener {
+///     moods: Vec<u8>,
+///     ignored: Vec<u32>,
+/// }
+///
+/// impl SmallTalkHandler for Listener {
+///     fn on_howdy(&mut self, message: &Howdy) {
+///         self.moods.push(message.mood);
+///     }
+///
+///     fn on_unhandled(&mut self, message: &dyn Message) {
+///         self.ignored.push(message.id());
+///     }
+/// }
+///
+/// let mut listener = Listener::default();
+/// SmallTalk::Howdy(Howdy { mood: 1 }).dispatch(&mut listener);
+/// SmallTalk::FineAndYou(FineAndYou { mood: 2 }).dispatch(&mut listener);
+///
+/// assert_eq!(listener.moods, [1]);
+/// assert_eq!(listener.ignored, [43]);
+/// ```
+///
+/// # Serde
+///
+/// When `serde` feature is enabled, dialects marked with `#[serde_tagged]` attribute implement `serde::Serialize` and
+/// `serde::Deserialize` with the same representation as generated dialects: `{"message": "FINE_AND_YOU", "fields":
+/// {...}}`. Message names are `SHOUTY_SNAKE_CASE` variant identifiers unless specified by `#[canonical_name("...")]`
+/// attribute on a variant. All message types of such dialects should implement `serde` traits.
+#[proc_macro_derive(
+    Dialect,
+    attributes(name, dialect, version, canonical_name, handler, serde_tagged)
+)]
 pub fn derive_mavlink_dialect(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input: DeriveInput = syn::parse(input).unwrap();
 
@@ -343,3 +713,80 @@ pub fn derive_mavlink_dialect(input: proc_macro::TokenStream) -> proc_macro::Tok
 
     proc_macro::TokenStream::from(dialect.to_token_stream())
 }
+
+/// Generate MAVLink dialects from XML definitions at compile time.
+///
+/// Expands to the same modules as generated by `BuildHelper` with default `Modules` output layout, inlined at the
+/// invocation site. Requires `include_dialects` feature.
+///
+/// # Usage
+///
+/// ```rust
+/// mod mavlink {
+///     mavspec::rust::derive::include_dialects! {
+///         sources = ["../message_definitions/standard"],
+///         include_dialects = ["minimal"],
+///     }
+/// }
+/// // Dialects are available as `mavlink::dialects::minimal`
+/// ```
+///
+/// The macro accepts comma-separated `option = value` pairs which correspond to `BuildHelperBuilder` setters:
+///
+/// | Option              | Value                    |
+/// |---------------------|--------------------------|
+/// | `sources`           | array of paths, required |
+/// | `manifest_path`     | path                     |
+/// | `include_dialects`  | array of strings         |
+/// | `exclude_dialects`  | array of strings         |
+/// | `microservices`     | array of strings         |
+/// | `messages`          | array of strings         |
+/// | `enums`             | array of strings         |
+/// | `commands`          | array of strings         |
+/// | `test_vectors`      | array of paths           |
+/// | `serde`             | boolean                  |
+/// | `arbitrary`         | boolean                  |
+/// | `async_handlers`    | boolean                  |
+/// | `generate_tests`    | boolean                  |
+/// | `generate_builders` | boolean                  |
+/// | `typed_units`       | boolean                  |
+/// | `wip`               | string                   |
+/// | `serde_enums`       | string                   |
+/// | `type_attributes`   | array of tables          |
+///
+/// Paths are relative to `CARGO_MANIFEST_DIR`. Options override settings of `[package.metadata.mavspec]` section of a
+/// manifest referenced by `manifest_path`. Tables of `type_attributes` accept `kinds`, `names`, `derives`, and
+/// `attributes` arrays of strings, just like `type_attributes` tables of the manifest:
+///
+/// ```rust
+/// mod mavlink {
+///     mavspec::rust::derive::include_dialects! {
+///         sources = ["../message_definitions/standard"],
+///         include_dialects = ["minimal"],
+///         type_attributes = [
+///             { derives = ["PartialEq"] },
+///             { kinds = ["messages"], names = ["HEARTBEAT"], attributes = ["#[doc(alias = \"beat\")]"] },
+///         ],
+///     }
+/// }
+/// ```
+///
+/// There is no `output_layout` option: code is expanded in place, so other layouts, which split code into files or
+/// crates, make no sense for the macro.
+///
+/// Source files found during expansion (XML definitions within `sources`, test vectors, and the manifest) are included
+/// into compilation unit by `include_bytes!`, so changes in these files trigger recompilation. XML files added to
+/// `sources` directories afterwards are not tracked, since they were not found during expansion. Touch the file with
+/// the macro invocation (or run `cargo clean`) to pick them up.
+#[cfg(feature = "include_dialects")]
+#[proc_macro]
+pub fn include_dialects(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as include_dialects::IncludeDialects);
+
+    match input.expand() {
+        Ok(tokens) => tokens.into(),
+        Err(err) => syn::Error::new(proc_macro2::Span::call_site(), err)
+            .into_compile_error()
+            .into(),
+    }
+}
@@ -2,7 +2,9 @@ use crc_any::CRCu16;
 use std::cmp::Ordering;
 
 use quote::{format_ident, quote, ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
 
+use crate::consts::ATTR_BUILDER;
 use crate::errors::{Error, SpecError};
 use crate::field_types::FieldType;
 use crate::message_attributes::{CrcExtra, MessageId};
@@ -10,9 +12,11 @@ use crate::message_field::Field;
 
 pub(crate) struct Message {
     ident: syn::Ident,
+    fields: Vec<Field>,
     ordered_fields: Vec<Field>,
     message_id: MessageId,
     crc_extra: CrcExtra,
+    builder: bool,
 }
 
 enum PayloadType {
@@ -41,7 +45,11 @@ impl Message {
             _ => return Err(SpecError::NotAStruct.into()),
         }
 
-        let mut ordered_fields = fields;
+        if fields.iter().filter(|field| field.is_instance()).count() > 1 {
+            return Err(SpecError::MultipleInstanceFields.into());
+        }
+
+        let mut ordered_fields = fields.clone();
         Self::reorder_fields(&mut ordered_fields);
 
         let crc_extra = match CrcExtra::try_from(&value.attrs) {
@@ -57,9 +65,14 @@ impl Message {
 
         Ok(Self {
             ident: value.ident,
+            fields,
             ordered_fields,
             message_id: MessageId::try_from(&value.attrs)?,
             crc_extra,
+            builder: value
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident(ATTR_BUILDER)),
         })
     }
 
@@ -70,6 +83,8 @@ impl Message {
         let impl_into_payload = self.impl_into_payload();
         let impl_default = self.impl_default();
         let impl_message_impl = self.impl_message_impl();
+        let impl_builder = self.impl_builder();
+        let impl_instance = self.impl_instance();
 
         quote! {
             #impl_message
@@ -78,6 +93,8 @@ impl Message {
             #impl_into_payload
             #impl_message_impl
             #impl_default
+            #impl_builder
+            #impl_instance
         }
     }
 
@@ -106,9 +123,28 @@ impl Message {
         let message_id = self.message_id().literal();
         let crc_extra = self.crc_extra().literal();
         let min_supported_mavlink_version = self.message_id().min_supported_mavlink_version();
+        let payload_size_v1 = self.payload_size_v1();
+        let payload_size_v2 = self.payload_size_v2();
 
         quote! {
             impl #ident {
+                /// Message `ID`.
+                pub const ID: mavspec::rust::spec::types::MessageId = #message_id;
+
+                /// Message `CRC_EXTRA`.
+                pub const CRC_EXTRA: mavspec::rust::spec::types::CrcExtra = #crc_extra;
+
+                /// Payload size without extension fields (as in `MAVLink 1`).
+                pub const PAYLOAD_SIZE_V1: usize = #payload_size_v1;
+
+                /// Maximum payload size in `MAVLink 2` (including extension fields).
+                ///
+                /// Actual payload may be shorter since trailing zero bytes are truncated.
+                pub const PAYLOAD_SIZE_V2: usize = #payload_size_v2;
+
+                /// Minimum supported MAVLink version for this message.
+                pub const MIN_VERSION: mavspec::rust::spec::MavLinkVersion = #min_supported_mavlink_version;
+
                 /// Returns specification for this message.
                 #[inline]
                 pub const fn spec() -> mavspec::rust::spec::MessageInfo {
@@ -258,10 +294,130 @@ impl Message {
         }
     }
 
+    fn impl_builder(&self) -> proc_macro2::TokenStream {
+        if !self.builder {
+            return quote!();
+        }
+
+        let ident = self.ident();
+        let args = self
+            .fields
+            .iter()
+            .filter(|field| !field.is_extension())
+            .map(|field| {
+                let ident = field.ident();
+                let ty = field.ty();
+                quote! { #ident: #ty }
+            });
+        let field_values = self.fields.iter().map(|field| {
+            let ident = field.ident();
+            if field.is_extension() {
+                let default_value = field.default_value();
+                quote! { #ident: #default_value }
+            } else {
+                quote! { #ident }
+            }
+        });
+        let setters = self.fields.iter().map(|field| {
+            let ident = field.ident();
+            let ty = field.ty();
+            let name = ident.unraw().to_string();
+            // Leading and trailing underscores are used to escape numeric and keyword names
+            let setter_ident = format_ident!("with_{}", name.trim_matches('_'));
+            let doc = format!(" Sets `{name}` field.");
+
+            quote! {
+                #[doc = #doc]
+                #[inline]
+                pub fn #setter_ident(mut self, value: #ty) -> Self {
+                    self.#ident = value;
+                    self
+                }
+            }
+        });
+
+        quote! {
+            impl #ident {
+                /// Creates message from non-extension fields in the order of their declaration.
+                ///
+                /// Extension fields are set to their default values.
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(#(#args),*) -> Self {
+                    Self {
+                        #(#field_values),*
+                    }
+                }
+
+                #(#setters)*
+            }
+        }
+    }
+
+    fn instance_field(&self) -> Option<&Field> {
+        self.fields.iter().find(|field| field.is_instance())
+    }
+
+    fn impl_instance(&self) -> proc_macro2::TokenStream {
+        let Some(field) = self.instance_field() else {
+            return quote!();
+        };
+
+        let ident = self.ident();
+        let field_ident = field.ident();
+        let ty = field.ty();
+        let doc = format!(
+            " Instance of a message stream stored in `{}` field.",
+            field_ident.unraw()
+        );
+
+        quote! {
+            impl #ident {
+                #[doc = #doc]
+                ///
+                /// See [`mavspec::rust::spec::Message::stream_key`] for a key which identifies message streams.
+                #[inline]
+                pub fn instance(&self) -> #ty {
+                    self.#field_ident
+                }
+            }
+        }
+    }
+
     fn impl_message_impl(&self) -> proc_macro2::TokenStream {
         let ident = self.ident();
+
+        let Some(field) = self.instance_field() else {
+            return quote! {
+                impl mavspec::rust::spec::Message for #ident {}
+            };
+        };
+
+        let field_ident = field.ident();
+        let instance = if field.is_string() {
+            quote! {
+                mavspec::rust::spec::MessageInstance::from_name(self.#field_ident.raw())
+            }
+        } else if field.custom_type().is_some() {
+            let value_converter = field.encode_value_converter();
+            quote! {
+                mavspec::rust::spec::MessageInstance::Index(u32::from({
+                    let value = self.#field_ident;
+                    #value_converter
+                }))
+            }
+        } else {
+            quote! {
+                mavspec::rust::spec::MessageInstance::Index(u32::from(self.#field_ident))
+            }
+        };
+
         quote! {
-            impl mavspec::rust::spec::Message for #ident {}
+            impl mavspec::rust::spec::Message for #ident {
+                #[inline]
+                fn stream_instance(&self) -> Option<mavspec::rust::spec::MessageInstance> {
+                    Some(#instance)
+                }
+            }
         }
     }
 
@@ -374,6 +530,12 @@ impl Message {
     fn decode_field(field: &Field) -> proc_macro2::TokenStream {
         let field_ident = field.ident();
 
+        if field.is_string() {
+            return quote! {
+                #field_ident: mavspec::rust::spec::MavString::from_raw(reader.read_array().unwrap())
+            };
+        }
+
         match field.field_type() {
             FieldType::Scalar(scalar) => match field.custom_type() {
                 None => quote! {
@@ -492,6 +654,12 @@ impl Message {
     fn encode_field(field: &Field) -> proc_macro2::TokenStream {
         let field_ident = field.ident();
 
+        if field.is_string() {
+            return quote! {
+                writer.write_array(message.#field_ident.into_raw()).unwrap()
+            };
+        }
+
         match field.field_type() {
             FieldType::Scalar(_) => match field.custom_type() {
                 None => quote! {
@@ -748,4 +916,105 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn string_fields_crc_extra() {
+        let crc_extra = |param_id_type: proc_macro2::TokenStream| {
+            let input: syn::DeriveInput = syn::parse2(quote! {
+                #[message_id(20)]
+                struct ParamRequestRead {
+                    target_system: u8,
+                    target_component: u8,
+                    param_id: #param_id_type,
+                    param_index: i16,
+                }
+            })
+            .unwrap();
+            Message::try_from(input)
+                .unwrap()
+                .crc_extra()
+                .literal()
+                .base10_parse::<u8>()
+                .unwrap()
+        };
+
+        // Strings are digested as `char[N]`, while byte arrays are digested as `uint8_t[N]`
+        assert_eq!(crc_extra(quote!(MavString<16>)), 134);
+        assert_eq!(crc_extra(quote!([u8; 16])), 78);
+    }
+
+    #[test]
+    fn builder() {
+        assert!(create_message().impl_builder().is_empty());
+
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            #[message_id(42)]
+            #[builder]
+            struct Message {
+                r#type: u8,
+                scalar_u16: u16,
+                #[extension]
+                #[default_value(u32::MAX)]
+                ext_u32: u32,
+            }
+        })
+        .unwrap();
+        let builder = Message::try_from(input).unwrap().impl_builder().to_string();
+
+        // Constructor takes non-extension fields in the order of declaration
+        assert!(builder.contains(&quote!(pub fn new(r#type: u8, scalar_u16: u16)).to_string()));
+        assert!(builder.contains(&quote!(ext_u32: u32::MAX).to_string()));
+
+        for setter in [
+            quote!(pub fn with_type(mut self, value: u8) -> Self),
+            quote!(pub fn with_ext_u32(mut self, value: u32) -> Self),
+        ] {
+            assert!(builder.contains(&setter.to_string()));
+        }
+    }
+
+    #[test]
+    fn instance() {
+        let message = create_message();
+        assert!(message.impl_instance().is_empty());
+        assert!(!message
+            .impl_message_impl()
+            .to_string()
+            .contains("stream_instance"));
+
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            #[message_id(147)]
+            struct BatteryStatus {
+                #[instance]
+                id: u8,
+                current_consumed: i32,
+            }
+        })
+        .unwrap();
+        let message = Message::try_from(input).unwrap();
+
+        assert!(message
+            .impl_instance()
+            .to_string()
+            .contains(&quote!(pub fn instance(&self) -> u8).to_string()));
+        assert!(message
+            .impl_message_impl()
+            .to_string()
+            .contains(&quote!(MessageInstance::Index(u32::from(self.id))).to_string()));
+
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            #[message_id(147)]
+            struct BatteryStatus {
+                #[instance]
+                id: u8,
+                #[instance]
+                function: u8,
+            }
+        })
+        .unwrap();
+        assert!(matches!(
+            Message::try_from(input),
+            Err(Error::Message(SpecError::MultipleInstanceFields))
+        ));
+    }
 }
@@ -1,4 +1,7 @@
-use crate::consts::{ATTR_BASE_TYPE, ATTR_BITMASK, ATTR_EXTENSION, ATTR_REPR_TYPE};
+use crate::consts::{
+    ATTR_BASE_TYPE, ATTR_BITMASK, ATTR_DEFAULT_VALUE, ATTR_EXTENSION, ATTR_INSTANCE, ATTR_QUANTITY,
+    ATTR_REPR_TYPE,
+};
 use quote::{quote, ToTokens};
 use std::fmt::{Debug, Display, Formatter};
 
@@ -8,11 +11,14 @@ use crate::field_types::{FieldType, ScalarType};
 #[derive(Clone)]
 pub(crate) struct Field {
     ident: syn::Ident,
+    ty: syn::Type,
     field_type: FieldType,
     custom_type: Option<syn::TypePath>,
     repr_type: Option<ScalarType>,
     is_bitmask: bool,
+    is_quantity: bool,
     is_extension: bool,
+    is_instance: bool,
     default_value: proc_macro2::TokenStream,
 }
 
@@ -50,6 +56,8 @@ impl Field {
         let ident = value.ident.unwrap();
         let is_extension = Self::has_attr(value.attrs.as_slice(), ATTR_EXTENSION);
         let is_bitmask = Self::has_attr(value.attrs.as_slice(), ATTR_BITMASK);
+        let is_quantity = Self::has_attr(value.attrs.as_slice(), ATTR_QUANTITY);
+        let is_instance = Self::has_attr(value.attrs.as_slice(), ATTR_INSTANCE);
         let base_type = Self::get_type_from_attr(value.attrs.as_slice(), ATTR_BASE_TYPE)?;
 
         let repr_type = {
@@ -59,24 +67,53 @@ impl Field {
             }
             repr_type
         };
-        Self::validate_enum_repr(repr_type.as_ref(), &ident)?;
+        if is_quantity {
+            if base_type.is_none() {
+                return Err(FieldError::QuantityWithoutBaseType(ident.to_string()).into());
+            }
+        } else {
+            Self::validate_enum_repr(repr_type.as_ref(), &ident)?;
+        }
 
+        let ty = value.ty.clone();
         let (field_type, custom_type) = Self::derive_field_type(value.ty, base_type.as_ref())?;
+        if field_type.is_string() {
+            Self::validate_string_attrs(value.attrs.as_slice(), &ident)?;
+        }
+        if is_instance {
+            Self::validate_instance_type(&field_type, &ident)?;
+        }
 
+        let default_item =
+            match Self::get_expr_from_attr(value.attrs.as_slice(), ATTR_DEFAULT_VALUE)? {
+                Some(raw_value) => Self::default_item_from_raw(
+                    raw_value,
+                    custom_type.as_ref(),
+                    repr_type.as_ref(),
+                    is_bitmask,
+                    is_quantity,
+                ),
+                None => quote! { core::default::Default::default() },
+            };
         let default_value = match &field_type {
-            FieldType::Scalar(_) => quote! { core::default::Default::default() },
+            FieldType::Scalar(_) => default_item,
+            // `MavString` has its own `Default` implementation
+            FieldType::Array(ScalarType::Char, _) => default_item,
             FieldType::Array(_, len) => {
-                quote! { [core::default::Default::default(); #len] }
+                quote! { [#default_item; #len] }
             }
         };
 
         Ok(Self {
             ident,
+            ty,
             field_type,
             custom_type,
             repr_type,
             is_bitmask,
+            is_quantity,
             is_extension,
+            is_instance,
             default_value,
         })
     }
@@ -85,6 +122,11 @@ impl Field {
         &self.ident
     }
 
+    /// Rust type of the field as it is declared in the struct.
+    pub(crate) fn ty(&self) -> &syn::Type {
+        &self.ty
+    }
+
     pub(crate) fn canonical_name(&self) -> String {
         heck::AsSnakeCase(self.ident.to_string()).to_string()
     }
@@ -105,10 +147,24 @@ impl Field {
         self.is_extension
     }
 
+    /// Whether field is marked as an instance of a message stream.
+    pub(crate) fn is_instance(&self) -> bool {
+        self.is_instance
+    }
+
     pub(crate) fn is_bitmask(&self) -> bool {
         self.is_bitmask
     }
 
+    pub(crate) fn is_quantity(&self) -> bool {
+        self.is_quantity
+    }
+
+    /// Whether field is a `MavString<N>` which represents MAVLink `char[N]`.
+    pub(crate) fn is_string(&self) -> bool {
+        self.field_type.is_string()
+    }
+
     pub(crate) fn size_expr(&self) -> proc_macro2::TokenStream {
         self.field_type.size_expr()
     }
@@ -121,6 +177,10 @@ impl Field {
             quote! {
                 #custom_type::from_bits_truncate(raw_value as #repr_type)
             }
+        } else if self.is_quantity() {
+            quote! {
+                <#custom_type>::new(raw_value)
+            }
         } else {
             quote! {
                 #custom_type::try_from(raw_value as #repr_type)?
@@ -136,6 +196,10 @@ impl Field {
             quote! {
                 value.bits() as #base_type
             }
+        } else if self.is_quantity() {
+            quote! {
+                value.raw()
+            }
         } else {
             quote! {
                 (value as #repr_type) as #base_type
@@ -171,6 +235,84 @@ impl Field {
         Ok(())
     }
 
+    fn validate_instance_type(
+        field_type: &FieldType,
+        field_ident: &syn::Ident,
+    ) -> Result<(), Error> {
+        // Instances are `u32` indices, so wider or signed values can't be represented without loss
+        match field_type {
+            FieldType::Scalar(ScalarType::UInt8 | ScalarType::UInt16 | ScalarType::UInt32) => {
+                Ok(())
+            }
+            _ if field_type.is_string() => Ok(()),
+            _ => Err(FieldError::InvalidInstanceType(field_ident.to_string()).into()),
+        }
+    }
+
+    fn validate_string_attrs(
+        attrs: &[syn::Attribute],
+        field_ident: &syn::Ident,
+    ) -> Result<(), Error> {
+        for attr_name in [
+            ATTR_REPR_TYPE,
+            ATTR_BITMASK,
+            ATTR_QUANTITY,
+            ATTR_DEFAULT_VALUE,
+        ] {
+            if Self::has_attr(attrs, attr_name) {
+                return Err(FieldError::UnsupportedStringAttribute(
+                    field_ident.to_string(),
+                    attr_name,
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_expr_from_attr(
+        attrs: &[syn::Attribute],
+        attr_name: &str,
+    ) -> Result<Option<syn::Expr>, Error> {
+        for attr in attrs {
+            if let Some(ident) = attr.path().get_ident() {
+                if ident == attr_name {
+                    return match attr.parse_args::<syn::Expr>() {
+                        Ok(expr) => Ok(Some(expr)),
+                        Err(err) => Err(FieldError::DefaultValueParseError(err).into()),
+                    };
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Converts raw value of a base type into a value of a field item.
+    fn default_item_from_raw(
+        raw_value: syn::Expr,
+        custom_type: Option<&syn::TypePath>,
+        repr_type: Option<&ScalarType>,
+        is_bitmask: bool,
+        is_quantity: bool,
+    ) -> proc_macro2::TokenStream {
+        let (Some(custom_type), Some(repr_type)) = (custom_type, repr_type) else {
+            return raw_value.to_token_stream();
+        };
+        let repr_type = repr_type.to_token_stream();
+
+        if is_bitmask {
+            quote! { #custom_type::from_bits_truncate((#raw_value) as #repr_type) }
+        } else if is_quantity {
+            quote! { <#custom_type>::new(#raw_value) }
+        } else {
+            quote! {
+                #custom_type::try_from((#raw_value) as #repr_type).unwrap_or_default()
+            }
+        }
+    }
+
     fn get_type_from_attr(
         attrs: &[syn::Attribute],
         attr_name: &str,
@@ -387,6 +529,188 @@ mod tests {
         }
     }
 
+    #[test]
+    fn quantity_fields() {
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            struct Struct {
+                #[quantity]
+                #[base_type(f32)]
+                scalar_f32: Quantity<f32, Metres>,
+
+                #[quantity]
+                #[base_type(i16)]
+                array_i16_4: [Quantity<i16, CentiDegrees>; 4],
+
+                #[quantity]
+                missing_base_type: Quantity<u8, Percents>,
+            }
+        })
+        .unwrap();
+
+        let fields = match input.data {
+            Data::Struct(struct_) => struct_.fields.into_iter().collect::<Vec<_>>(),
+            _ => panic!("not a struct: {}", input.to_token_stream()),
+        };
+
+        let scalar = Field::try_from(fields[0].clone()).unwrap();
+        assert!(scalar.is_quantity());
+        assert!(matches!(
+            scalar.field_type(),
+            &FieldType::Scalar(ScalarType::Float32)
+        ));
+
+        let array = Field::try_from(fields[1].clone()).unwrap();
+        assert!(array.is_quantity());
+        assert!(matches!(
+            array.field_type(),
+            &FieldType::Array(ScalarType::Int16, _)
+        ));
+
+        assert!(matches!(
+            Field::try_from(fields[2].clone()),
+            Err(Error::Field(FieldError::QuantityWithoutBaseType(_)))
+        ));
+    }
+
+    #[test]
+    fn default_values() {
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            struct Struct {
+                #[default_value(u16::MAX)]
+                scalar_u16: u16,
+
+                #[default_value(f32::NAN)]
+                array_f32_4: [f32; 4],
+
+                #[base_type(u8)]
+                #[default_value(3u8)]
+                scalar_enum: Variants,
+
+                #[quantity]
+                #[base_type(i16)]
+                #[default_value(-1i16)]
+                scalar_quantity: Quantity<i16, CentiAmperes>,
+
+                no_default: u8,
+
+                #[default_value(not an expression)]
+                invalid: u8,
+            }
+        })
+        .unwrap();
+
+        let fields = match input.data {
+            Data::Struct(struct_) => struct_.fields.into_iter().collect::<Vec<_>>(),
+            _ => panic!("not a struct: {}", input.to_token_stream()),
+        };
+        let default_value = |idx: usize| {
+            Field::try_from(fields[idx].clone())
+                .unwrap()
+                .default_value()
+                .to_string()
+                .replace(' ', "")
+        };
+
+        assert_eq!(default_value(0), "u16::MAX");
+        assert_eq!(default_value(1), "[f32::NAN;4]");
+        assert_eq!(
+            default_value(2),
+            "Variants::try_from((3u8)asu8).unwrap_or_default()"
+        );
+        assert_eq!(default_value(3), "<Quantity<i16,CentiAmperes>>::new(-1i16)");
+        assert_eq!(default_value(4), "core::default::Default::default()");
+        assert!(matches!(
+            Field::try_from(fields[5].clone()),
+            Err(Error::Field(FieldError::DefaultValueParseError(_)))
+        ));
+    }
+
+    #[test]
+    fn string_fields() {
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            struct Struct {
+                text: MavString<50>,
+
+                #[default_value(0u8)]
+                with_default: mavspec::rust::spec::MavString<16>,
+            }
+        })
+        .unwrap();
+
+        let fields = match input.data {
+            Data::Struct(struct_) => struct_.fields.into_iter().collect::<Vec<_>>(),
+            _ => panic!("not a struct: {}", input.to_token_stream()),
+        };
+
+        let text = Field::try_from(fields[0].clone()).unwrap();
+        assert!(text.is_string());
+        assert!(text.custom_type().is_none());
+        assert!(matches!(
+            text.field_type(),
+            &FieldType::Array(ScalarType::Char, _)
+        ));
+        assert_eq!(
+            text.default_value().to_string().replace(' ', ""),
+            "core::default::Default::default()"
+        );
+
+        assert!(matches!(
+            Field::try_from(fields[1].clone()),
+            Err(Error::Field(FieldError::UnsupportedStringAttribute(
+                _,
+                "default_value"
+            )))
+        ));
+    }
+
+    #[test]
+    fn instance_fields() {
+        let input: syn::DeriveInput = syn::parse2(quote! {
+            struct Struct {
+                #[instance]
+                scalar_u8: u8,
+
+                #[instance]
+                #[base_type(u8)]
+                scalar_enum: Variants,
+
+                #[instance]
+                name: MavString<10>,
+
+                not_an_instance: u16,
+
+                #[instance]
+                scalar_f32: f32,
+
+                #[instance]
+                scalar_u64: u64,
+
+                #[instance]
+                scalar_i8: i8,
+
+                #[instance]
+                array_u8_4: [u8; 4],
+            }
+        })
+        .unwrap();
+
+        let fields = match input.data {
+            Data::Struct(struct_) => struct_.fields.into_iter().collect::<Vec<_>>(),
+            _ => panic!("not a struct: {}", input.to_token_stream()),
+        };
+
+        for field in &fields[0..3] {
+            assert!(Field::try_from(field.clone()).unwrap().is_instance());
+        }
+        assert!(!Field::try_from(fields[3].clone()).unwrap().is_instance());
+        for field in &fields[4..8] {
+            assert!(matches!(
+                Field::try_from(field.clone()),
+                Err(Error::Field(FieldError::InvalidInstanceType(_)))
+            ));
+        }
+    }
+
     #[test]
     fn fields_larger_than_custom_types() {
         for field in create_fields() {
@@ -1,17 +1,21 @@
 use std::collections::HashSet;
+use std::env::{current_dir, var_os};
 use std::ffi::OsStr;
-use std::fs::remove_dir_all;
-use std::path::{Path, PathBuf};
+use std::fs::read_dir;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 
 extern crate cargo_manifest;
-use crate::error::RustGenResult;
+use crate::error::{RustGenError, RustGenResult};
 use cargo_manifest::{Manifest, Value};
 use mavinspect::parser::InspectorBuilder;
 use mavinspect::protocol::{Filter, Microservices, Protocol};
 use mavinspect::Inspector;
 
-use crate::generator::{Generator, GeneratorParams};
+use crate::generator::{Generator, GeneratorParams, OutputLayout, SerdeEnumRepr, WipPolicy};
+use crate::multipliers::load_field_multipliers;
+use crate::test_vectors::{sample_test_vectors, SampleTestVector, TestVector};
+use crate::type_attributes::TypeAttributes;
 
 /// Code builder for Rust generator.
 ///
@@ -59,7 +63,18 @@ pub struct BuildHelper {
     protocol: Option<Arc<Protocol>>,
     microservices: Option<Microservices>,
     serde: bool,
+    arbitrary: bool,
+    async_handlers: bool,
     generate_tests: Option<bool>,
+    generate_builders: Option<bool>,
+    typed_units: Option<bool>,
+    wip: Option<WipPolicy>,
+    serde_enums: Option<SerdeEnumRepr>,
+    type_attributes: Option<Vec<TypeAttributes>>,
+    test_vectors: Option<Vec<PathBuf>>,
+    output_layout: Option<OutputLayout>,
+    mavspec_path: Option<PathBuf>,
+    emit_rerun_if_changed: bool,
 }
 
 /// Configuration builder for [`BuildHelper`].
@@ -78,24 +93,70 @@ impl BuildHelper {
     }
 
     /// Scans for dialects and generates MAVLink dialects.
+    ///
+    /// Only files with changed content are written. Generated files are listed in `.generated` file within
+    /// [`Self::out_path`], files listed by the previous generation which are no longer generated are removed. Other files
+    /// (i.e. `README.md`, `Cargo.lock`, or `target` of generated workspace) are kept.
+    ///
+    /// If [`Self::emit_rerun_if_changed`] is enabled, prints `cargo:rerun-if-changed` directives for generation inputs.
     pub fn generate(&self) -> RustGenResult<()> {
-        if let Err(err) = remove_dir_all(&self.out_path) {
-            log::debug!("Error while cleaning output directory: {err:?}");
+        self.make_generator(self.output_layout())?.generate()?;
+        if self.emit_rerun_if_changed {
+            self.print_rerun_if_changed()?;
         }
+        Ok(())
+    }
 
-        let protocol = self.load_filtered_protocol()?;
+    /// Generates MAVLink dialects in memory and compares them with files in [`Self::out_path`].
+    ///
+    /// Nothing is written. Returns [`RustGenError::Verification`] with a report on missing, extra, and differing files
+    /// (including unified diff) if existing files are outdated. Useful for checking generated code committed to a
+    /// repository. Files which are not listed as generated in `.generated` file (i.e. `README.md`) are ignored.
+    pub fn verify(&self) -> RustGenResult<()> {
+        self.make_generator(self.output_layout())?.verify()
+    }
 
-        Generator::new(
-            protocol,
-            &self.out_path,
-            GeneratorParams {
-                serde: self.serde,
-                generate_tests: self.generate_tests.unwrap_or(false),
-            },
-        )
-        .generate()?;
+    /// Scans for dialects and generates MAVLink dialects as a source code of a single module.
+    ///
+    /// Nothing is written to [`Self::out_path`]. The result is the same as the root module generated with
+    /// [`OutputLayout::SingleFile`] regardless of [`Self::output_layout`].
+    pub fn generate_code(&self) -> RustGenResult<String> {
+        Ok(self
+            .make_generator(OutputLayout::SingleFile)?
+            .generate_code())
+    }
 
-        Ok(())
+    /// Generates deterministic sample test vectors for a `dialect` according to configuration.
+    ///
+    /// Dialect can be specified either by its name or by its canonical name (i.e. `common` or `Common`). Only
+    /// entities retained by filters and [`WipPolicy`] are sampled. See [`sample_test_vectors`] for details.
+    pub fn generate_test_vectors(
+        &self,
+        dialect: &str,
+        seed: u64,
+    ) -> RustGenResult<Vec<SampleTestVector>> {
+        let protocol = self.load_filtered_protocol()?;
+        let dialect = protocol
+            .dialects()
+            .into_iter()
+            .find(|d| d.name() == dialect || d.canonical_name() == dialect)
+            .ok_or_else(|| {
+                RustGenError::InvalidTestVectors(format!("unknown dialect `{dialect}`"))
+            })?;
+
+        sample_test_vectors(dialect, seed, self.wip())
+    }
+
+    /// Files which generated code depends on.
+    ///
+    /// These are MAVLink XML definitions found in [`Self::sources`], [`Self::test_vectors`], and Cargo manifest
+    /// specified by [`Self::manifest_path`]. Useful for tracking changes in build scripts and macros.
+    pub fn source_files(&self) -> RustGenResult<Vec<PathBuf>> {
+        let mut files = self.definition_files()?;
+        files.extend(self.test_vectors.iter().flatten().cloned());
+        files.extend(self.manifest_path.iter().cloned());
+
+        Ok(files)
     }
 
     /// Output path for autogenerated files.
@@ -121,6 +182,19 @@ impl BuildHelper {
     /// enums = ["STORAGE_STATUS", "GIMBAL_*"]
     /// commands = ["MAV_CMD_DO_CHANGE_SPEED", "MAV_CMD_DO_SET_ROI*"]
     /// generate_tests = false
+    /// generate_builders = false
+    /// typed_units = false
+    /// wip = "include"
+    /// serde_enums = "derive"
+    /// test_vectors = ["test_vectors/common.json"]
+    /// output_layout = "modules"
+    /// mavspec_path = "../mavspec"
+    ///
+    /// [[package.metadata.mavspec.type_attributes]]
+    /// kinds = ["enums", "bitmasks"]
+    /// names = ["MAV_*"]
+    /// derives = ["PartialEq", "Eq", "Hash"]
+    /// attributes = ["#[allow(clippy::upper_case_acronyms)]"]
     /// ```
     ///
     /// If [`Self::manifest_path`] is set, then the following parameters will be populated from keys in `Cargo.toml`:
@@ -130,6 +204,15 @@ impl BuildHelper {
     /// * [`Self::enums`] from `enums` key.
     /// * [`Self::commands`] from `commands` key.
     /// * [`Self::generate_tests`] from `generate_tests` key.
+    /// * [`Self::generate_builders`] from `generate_builders` key.
+    /// * [`Self::typed_units`] from `typed_units` key.
+    /// * [`Self::wip`] from `wip` key (`"include"`, `"exclude"`, or `"warn"`).
+    /// * [`Self::serde_enums`] from `serde_enums` key (`"derive"`, `"numeric"`, `"name"`, or `"flags"`).
+    /// * [`Self::type_attributes`] from `type_attributes` array of tables.
+    /// * [`Self::test_vectors`] from `test_vectors` key (paths are relative to manifest directory).
+    /// * [`Self::output_layout`] from `output_layout` key (`"modules"`, `"single_file"`, `"file_per_dialect"`, or
+    ///   `"workspace"`).
+    /// * [`Self::mavspec_path`] from `mavspec_path` key (path is relative to manifest directory).
     ///
     /// Note that if set explicitly, these parameters has precedence over keys from manifest.
     pub fn manifest_path(&self) -> Option<&Path> {
@@ -201,11 +284,28 @@ impl BuildHelper {
         }
     }
 
+    /// Whether [`Self::generate`] prints `cargo:rerun-if-changed` directives for generation inputs.
+    ///
+    /// Disabled by default.
+    pub fn emit_rerun_if_changed(&self) -> bool {
+        self.emit_rerun_if_changed
+    }
+
     /// [Serde](https://serde.rs/) support flag for generated entities.
     pub fn serde(&self) -> bool {
         self.serde
     }
 
+    /// [Arbitrary](https://docs.rs/arbitrary) support flag for generated entities.
+    pub fn arbitrary(&self) -> bool {
+        self.arbitrary
+    }
+
+    /// Async message handlers support flag.
+    pub fn async_handlers(&self) -> bool {
+        self.async_handlers
+    }
+
     /// Tests generation flag.
     ///
     /// If set to `true`, then tests will be generated.
@@ -213,6 +313,143 @@ impl BuildHelper {
         self.generate_tests.unwrap_or(false)
     }
 
+    /// Constructors and builders generation flag.
+    ///
+    /// If set to `true`, then messages will have `new(..)` constructors and `with_<field>(..)` setters.
+    pub fn generate_builders(&self) -> bool {
+        self.generate_builders.unwrap_or(false)
+    }
+
+    /// Typed units flag.
+    ///
+    /// If set to `true`, then message fields with recognised MAVLink units will be represented as
+    /// `mavspec::rust::spec::units::Quantity` instead of raw numeric types.
+    pub fn typed_units(&self) -> bool {
+        self.typed_units.unwrap_or(false)
+    }
+
+    /// Policy for work-in-progress MAVLink entities.
+    ///
+    /// Default is [`WipPolicy::Include`].
+    pub fn wip(&self) -> WipPolicy {
+        self.wip.unwrap_or_default()
+    }
+
+    /// Representation of enums and bitmasks in human-readable `serde` formats.
+    ///
+    /// Default is [`SerdeEnumRepr::Derive`]. Has no effect unless [`Self::serde`] is enabled.
+    pub fn serde_enums(&self) -> SerdeEnumRepr {
+        self.serde_enums.unwrap_or_default()
+    }
+
+    /// Extra derives and attributes for generated types.
+    pub fn type_attributes(&self) -> &[TypeAttributes] {
+        self.type_attributes.as_deref().unwrap_or_default()
+    }
+
+    /// Files with test vectors for known-answer tests of generated messages.
+    ///
+    /// See [`BuildHelperBuilder::set_test_vectors`] for details.
+    pub fn test_vectors(&self) -> Option<Vec<&Path>> {
+        self.test_vectors
+            .as_ref()
+            .map(|paths| paths.iter().map(|path| path.as_path()).collect())
+    }
+
+    /// Layout of generated files.
+    ///
+    /// Default is [`OutputLayout::Modules`].
+    pub fn output_layout(&self) -> OutputLayout {
+        self.output_layout.unwrap_or_default()
+    }
+
+    /// Path to `mavspec` crate used by crates generated with [`OutputLayout::Workspace`].
+    ///
+    /// If not set, generated crates depend on `mavspec` from crates.io of the same version as generator.
+    pub fn mavspec_path(&self) -> Option<&Path> {
+        self.mavspec_path.as_deref()
+    }
+
+    fn print_rerun_if_changed(&self) -> RustGenResult<()> {
+        // Files within `OUT_DIR` are written by the build script itself and would keep it always outdated
+        let out_dir = var_os("OUT_DIR").map(absolute_path).transpose()?;
+
+        let sources = self.sources.iter().flatten().cloned();
+        for path in sources.chain(self.source_files()?) {
+            let within_out_dir = match &out_dir {
+                Some(out_dir) => absolute_path(&path)?.starts_with(out_dir),
+                None => false,
+            };
+            if !within_out_dir {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn definition_files(&self) -> RustGenResult<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for source in self.sources.iter().flatten() {
+            for entry in read_dir(source)? {
+                let path = entry?.path();
+                if path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"))
+                {
+                    files.push(path);
+                }
+            }
+        }
+        files.sort();
+
+        Ok(files)
+    }
+
+    fn make_generator(&self, output_layout: OutputLayout) -> RustGenResult<Generator> {
+        let protocol = self.load_filtered_protocol()?;
+        let test_vectors = match &self.test_vectors {
+            Some(paths) => TestVector::load_all(paths, &protocol, self.wip())?,
+            None => Vec::new(),
+        };
+        if !test_vectors.is_empty() && !self.generate_tests() {
+            log::warn!("Test vectors are ignored since tests generation is disabled.");
+        }
+        if self.protocol.is_some() {
+            log::warn!("Scaled accessors are disabled since protocol is set without MAVLink XML definitions.");
+        }
+
+        Ok(Generator::new(
+            protocol,
+            &self.out_path,
+            GeneratorParams {
+                serde: self.serde,
+                arbitrary: self.arbitrary,
+                async_handlers: self.async_handlers,
+                generate_tests: self.generate_tests.unwrap_or(false),
+                generate_builders: self.generate_builders(),
+                typed_units: self.typed_units.unwrap_or(false),
+                wip: self.wip(),
+                serde_enums: self.serde_enums(),
+                type_attributes: self.type_attributes().to_vec(),
+                test_vectors,
+                output_layout,
+                mavspec_path: match self.mavspec_path() {
+                    // Crates are generated directly within output path, so the first step is their parent
+                    Some(path) => Some(
+                        Path::new("..")
+                            .join(relative_path(&absolute_path(path)?, &absolute_path(self.out_path())?)),
+                    ),
+                    None => None,
+                },
+                // Protocols set directly have no XML definitions to read multipliers from
+                scaled_accessors: self.protocol.is_none(),
+                field_multipliers: load_field_multipliers(&self.definition_files()?)?,
+            },
+        ))
+    }
+
     fn load_filtered_protocol(&self) -> RustGenResult<Arc<Protocol>> {
         Ok(if let Some(protocol) = &self.protocol {
             protocol.clone()
@@ -276,7 +513,7 @@ impl BuildHelper {
                 if let Some(metadata) = package.metadata {
                     if let Some(spec) = metadata.get("mavspec") {
                         // Spaghetti mode: OFF
-                        self.apply_manifest_config_spec(spec);
+                        self.apply_manifest_config_spec(spec)?;
                     }
                 }
             }
@@ -284,17 +521,120 @@ impl BuildHelper {
         Ok(())
     }
 
-    fn apply_manifest_config_spec(&mut self, spec: &Value) {
+    fn apply_manifest_config_spec(&mut self, spec: &Value) -> RustGenResult<()> {
         self.apply_manifest_config_messages(spec);
         self.apply_manifest_config_enums(spec);
         self.apply_manifest_config_commands(spec);
         self.apply_manifest_config_microservices(spec);
 
         if let Some(Value::Boolean(generate_tests)) = spec.get("generate_tests") {
-            self.generate_tests = Some(*generate_tests);
+            if self.generate_tests.is_none() {
+                self.generate_tests = Some(*generate_tests);
+            }
+        }
+        if let Some(Value::Boolean(generate_builders)) = spec.get("generate_builders") {
+            if self.generate_builders.is_none() {
+                self.generate_builders = Some(*generate_builders);
+            }
+        }
+        if let Some(Value::Boolean(typed_units)) = spec.get("typed_units") {
+            if self.typed_units.is_none() {
+                self.typed_units = Some(*typed_units);
+            }
+        }
+        if let Some(Value::String(wip)) = spec.get("wip") {
+            if self.wip.is_none() {
+                self.wip = Some(wip.parse()?);
+            }
+        }
+        if let Some(Value::String(serde_enums)) = spec.get("serde_enums") {
+            if self.serde_enums.is_none() {
+                self.serde_enums = Some(serde_enums.parse()?);
+            }
+        }
+        if let Some(Value::String(output_layout)) = spec.get("output_layout") {
+            if self.output_layout.is_none() {
+                self.output_layout = Some(output_layout.parse()?);
+            }
+        }
+        self.apply_manifest_config_type_attributes(spec)?;
+        self.apply_manifest_config_test_vectors(spec);
+        self.apply_manifest_config_mavspec_path(spec);
+
+        Ok(())
+    }
+
+    fn apply_manifest_config_test_vectors(&mut self, spec: &Value) {
+        if let Some(Value::Array(paths)) = spec.get("test_vectors") {
+            if self.test_vectors.is_none() {
+                let manifest_dir = self.manifest_dir();
+                self.test_vectors = Some(Vec::from_iter(
+                    paths
+                        .iter()
+                        .map(|v| manifest_dir.join(v.to_string().replace('"', ""))),
+                ));
+            }
+        }
+    }
+
+    fn apply_manifest_config_mavspec_path(&mut self, spec: &Value) {
+        if let Some(Value::String(path)) = spec.get("mavspec_path") {
+            if self.mavspec_path.is_none() {
+                self.mavspec_path = Some(self.manifest_dir().join(path));
+            }
         }
     }
 
+    fn manifest_dir(&self) -> &Path {
+        self.manifest_path
+            .as_deref()
+            .and_then(Path::parent)
+            .unwrap_or(Path::new(""))
+    }
+
+    fn apply_manifest_config_type_attributes(&mut self, spec: &Value) -> RustGenResult<()> {
+        let Some(Value::Array(tables)) = spec.get("type_attributes") else {
+            return Ok(());
+        };
+        if self.type_attributes.is_some() {
+            return Ok(());
+        }
+
+        let strings = |table: &Value, key: &str| -> RustGenResult<Vec<String>> {
+            match table.get(key) {
+                None => Ok(Vec::new()),
+                Some(Value::Array(values)) => values
+                    .iter()
+                    .map(|value| match value {
+                        Value::String(value) => Ok(value.clone()),
+                        _ => Err(RustGenError::InvalidTypeAttributes(format!(
+                            "`{key}` should contain only strings"
+                        ))),
+                    })
+                    .collect(),
+                Some(_) => Err(RustGenError::InvalidTypeAttributes(format!(
+                    "`{key}` should be an array of strings"
+                ))),
+            }
+        };
+
+        let mut type_attributes = Vec::new();
+        for table in tables {
+            type_attributes.push(TypeAttributes {
+                kinds: strings(table, "kinds")?
+                    .iter()
+                    .map(|kind| kind.parse())
+                    .collect::<RustGenResult<_>>()?,
+                names: strings(table, "names")?,
+                derives: strings(table, "derives")?,
+                attributes: strings(table, "attributes")?,
+            });
+        }
+        self.type_attributes = Some(type_attributes);
+
+        Ok(())
+    }
+
     fn apply_manifest_config_messages(&mut self, spec: &Value) {
         if let Some(Value::Array(msgs)) = spec.get("messages") {
             if self.messages.is_none() {
@@ -355,6 +695,9 @@ impl BuildHelperBuilder {
         if helper.manifest_path.is_some() {
             helper.apply_manifest_config()?;
         }
+        for type_attributes in helper.type_attributes() {
+            type_attributes.validate()?;
+        }
 
         Ok(helper)
     }
@@ -380,7 +723,7 @@ impl BuildHelperBuilder {
     /// * [`Self::set_manifest_path`].
     pub fn set_sources<T>(&mut self, sources: &[T]) -> &mut Self
     where
-        T: ?Sized + Into<PathBuf> + Clone,
+        T: Into<PathBuf> + Clone,
     {
         self.0.sources = Some(sources.iter().cloned().map(|src| src.into()).collect());
         self.0.manifest_path = None;
@@ -398,6 +741,19 @@ impl BuildHelperBuilder {
     /// enums = ["STORAGE_STATUS", "GIMBAL_*"]
     /// commands = ["MAV_CMD_DO_CHANGE_SPEED", "MAV_CMD_DO_SET_ROI*"]
     /// generate_tests = false
+    /// generate_builders = false
+    /// typed_units = false
+    /// wip = "include"
+    /// serde_enums = "derive"
+    /// test_vectors = ["test_vectors/common.json"]
+    /// output_layout = "modules"
+    /// mavspec_path = "../mavspec"
+    ///
+    /// [[package.metadata.mavspec.type_attributes]]
+    /// kinds = ["enums", "bitmasks"]
+    /// names = ["MAV_*"]
+    /// derives = ["PartialEq", "Eq", "Hash"]
+    /// attributes = ["#[allow(clippy::upper_case_acronyms)]"]
     /// ```
     ///
     /// The following parameters have precedence over configuration defined in Cargo manifest:
@@ -407,6 +763,14 @@ impl BuildHelperBuilder {
     /// * [`Self::set_enums`] replaces `enums` key.
     /// * [`Self::set_commands`] replaces `commands` key.
     /// * [`Self::set_generate_tests`] replaces `generate_tests` key.
+    /// * [`Self::set_generate_builders`] replaces `generate_builders` key.
+    /// * [`Self::set_typed_units`] replaces `typed_units` key.
+    /// * [`Self::set_wip`] replaces `wip` key.
+    /// * [`Self::set_serde_enums`] replaces `serde_enums` key.
+    /// * [`Self::set_type_attributes`] replaces `type_attributes` tables.
+    /// * [`Self::set_test_vectors`] replaces `test_vectors` key.
+    /// * [`Self::set_output_layout`] replaces `output_layout` key.
+    /// * [`Self::set_mavspec_path`] replaces `mavspec_path` key.
     pub fn set_manifest_path<T: ?Sized + AsRef<OsStr>>(&mut self, manifest_path: &T) -> &mut Self {
         self.0.manifest_path = Some(PathBuf::from(manifest_path));
         self
@@ -494,6 +858,28 @@ impl BuildHelperBuilder {
         self
     }
 
+    /// Enables/disables [Arbitrary](https://docs.rs/arbitrary) support for generated entities.
+    ///
+    /// If enabled, messages, enums, bitmasks, and dialect enums implement `arbitrary::Arbitrary` when `arbitrary`
+    /// feature of the crate with generated code is enabled. Such crate should depend on `arbitrary` with `derive`
+    /// feature and enable `arbitrary` feature of `mavspec`.
+    ///
+    /// Generated tests (see [`Self::set_generate_tests`]) will include randomized encoding/decoding round-trips.
+    pub fn set_arbitrary(&mut self, arbitrary: bool) -> &mut Self {
+        self.0.arbitrary = arbitrary;
+        self
+    }
+
+    /// Enables/disables async message handlers for generated dialects.
+    ///
+    /// Each dialect has a `<Dialect>Handler` trait with a method for every message. If enabled, an async counterpart
+    /// `<Dialect>AsyncHandler` is generated as well. Async handlers are available only when `async` feature of the
+    /// crate with generated code is enabled.
+    pub fn set_async_handlers(&mut self, async_handlers: bool) -> &mut Self {
+        self.0.async_handlers = async_handlers;
+        self
+    }
+
     /// Manually define MAVInspect [`Protocol`].
     ///
     /// If set, then [`Self::set_sources`] will be discarded and all parameters controlling MAVLink XML definitions will
@@ -507,6 +893,9 @@ impl BuildHelperBuilder {
     /// * [`Self::set_enums`],
     /// * [`Self::set_commands`],
     /// * [`Self::set_manifest_path`].
+    ///
+    /// Scaled accessors are not generated for a protocol set this way, since XML `multiplier` attributes of fields
+    /// can't be read without definitions.
     pub fn set_protocol(&mut self, protocol: Protocol) -> &mut Self {
         self.0.protocol = Some(Arc::new(protocol));
         self.0.sources = None;
@@ -522,12 +911,198 @@ impl BuildHelperBuilder {
         self.0.generate_tests = Some(generate_tests);
         self
     }
+
+    /// Enables/disables generation of constructors and builders for messages.
+    ///
+    /// If set to `true`, then each message gets a `new(..)` constructor which takes all non-extension fields in the
+    /// order of their definition in MAVLink XML, and a `with_<field>(..)` setter for each field. Extension fields are
+    /// set to their default values by constructor. Disabled by default.
+    ///
+    /// Overrides `generate_builders` configuration flag set by [`Self::set_manifest_path`].
+    pub fn set_generate_builders(&mut self, generate_builders: bool) -> &mut Self {
+        self.0.generate_builders = Some(generate_builders);
+        self
+    }
+
+    /// Enables/disables typed units for message fields.
+    ///
+    /// If set to `true`, then message fields with recognised MAVLink
+    /// [units](https://mavlink.io/en/guide/xml_schema.html#units) will be represented as
+    /// `mavspec::rust::spec::units::Quantity` tagged with corresponding unit. Raw values are available through
+    /// `Quantity::raw` and wire representation remains the same. Disabled by default.
+    ///
+    /// Overrides `typed_units` configuration flag set by [`Self::set_manifest_path`].
+    pub fn set_typed_units(&mut self, typed_units: bool) -> &mut Self {
+        self.0.typed_units = Some(typed_units);
+        self
+    }
+
+    /// Sets policy for work-in-progress MAVLink entities.
+    ///
+    /// Messages and enum entries marked as `<wip/>` can be generated as regular entities
+    /// ([`WipPolicy::Include`], default), skipped ([`WipPolicy::Exclude`]), or generated with `#[deprecated]`
+    /// attribute ([`WipPolicy::Warn`]), so the compiler warns when unstable parts of the protocol are used.
+    ///
+    /// [`WipPolicy::Exclude`] skips enums which consist of work-in-progress entries only, fields which refer to such
+    /// enums are generated as plain numeric fields.
+    ///
+    /// Overrides `wip` configuration value set by [`Self::set_manifest_path`].
+    pub fn set_wip(&mut self, wip: WipPolicy) -> &mut Self {
+        self.0.wip = Some(wip);
+        self
+    }
+
+    /// Sets representation of enums and bitmasks in human-readable `serde` formats (i.e. JSON).
+    ///
+    /// By default ([`SerdeEnumRepr::Derive`]) enums are represented by Rust variant names and bitmasks by numeric
+    /// values. Other options use numeric values ([`SerdeEnumRepr::Numeric`]), canonical MAVLink names
+    /// ([`SerdeEnumRepr::Name`]), or lists of flag names for bitmasks ([`SerdeEnumRepr::Flags`]). In these modes
+    /// deserialization accepts all of the representations. Has no effect unless [`Self::set_serde`] is enabled.
+    ///
+    /// Overrides `serde_enums` configuration value set by [`Self::set_manifest_path`].
+    pub fn set_serde_enums(&mut self, serde_enums: SerdeEnumRepr) -> &mut Self {
+        self.0.serde_enums = Some(serde_enums);
+        self
+    }
+
+    /// Sets extra derives and attributes for generated types.
+    ///
+    /// Each [`TypeAttributes`] entry selects messages, enums, bitmasks, or dialect enums by kind and MAVLink name
+    /// pattern. Derives and attributes are validated by [`Self::build`].
+    ///
+    /// Overrides `type_attributes` configuration set by [`Self::set_manifest_path`].
+    pub fn set_type_attributes(&mut self, type_attributes: &[TypeAttributes]) -> &mut Self {
+        self.0.type_attributes = Some(type_attributes.to_vec());
+        self
+    }
+
+    /// Sets files with test vectors for known-answer tests of generated messages.
+    ///
+    /// Each test vector defines a message, field values, MAVLink version, and the expected payload (for example,
+    /// produced by reference C or `pymavlink` implementations). JSON files contain an array of objects:
+    ///
+    /// ```json
+    /// [
+    ///     {
+    ///         "message": "HEARTBEAT",
+    ///         "version": 2,
+    ///         "fields": { "type": "MAV_TYPE_QUADROTOR", "base_mode": "MAV_MODE_FLAG_SAFETY_ARMED", "custom_mode": 3 },
+    ///         "payload": "03000000020080"
+    ///     }
+    /// ]
+    /// ```
+    ///
+    /// CSV files have a header with `message`, `version`, `fields`, and `payload` columns, where `fields` contains a
+    /// JSON object. Fields which are not specified have default values. Enums and bitmasks accept entry names
+    /// (bitmask flags are separated by `|`), `char[N]` fields accept strings. `MAVLink 2` payloads should have
+    /// trailing zero bytes truncated.
+    ///
+    /// Vectors are validated against generated dialects, unknown messages, fields, or enum entries result in
+    /// [`RustGenError::InvalidTestVectors`]. Each message with test vectors gets a `known_answers` test, which
+    /// requires tests generation (see [`Self::set_generate_tests`]).
+    ///
+    /// Overrides `test_vectors` configuration key set by [`Self::set_manifest_path`].
+    pub fn set_test_vectors<T>(&mut self, test_vectors: &[T]) -> &mut Self
+    where
+        T: Into<PathBuf> + Clone,
+    {
+        self.0.test_vectors = Some(
+            test_vectors
+                .iter()
+                .cloned()
+                .map(|path| path.into())
+                .collect(),
+        );
+        self
+    }
+
+    /// Sets layout of generated files.
+    ///
+    /// By default ([`OutputLayout::Modules`]) each dialect, enum, and message has its own file. Generated code can be
+    /// written to a single self-contained `mod.rs` ([`OutputLayout::SingleFile`]) or to one file per dialect
+    /// ([`OutputLayout::FilePerDialect`]). Public paths of generated entities are the same for all layouts, and root
+    /// module is always `mod.rs` in output directory.
+    ///
+    /// Overrides `output_layout` configuration value set by [`Self::set_manifest_path`].
+    pub fn set_output_layout(&mut self, output_layout: OutputLayout) -> &mut Self {
+        self.0.output_layout = Some(output_layout);
+        self
+    }
+
+    /// Sets path to local `mavspec` crate used by crates generated with [`OutputLayout::Workspace`].
+    ///
+    /// By default, generated crates depend on `mavspec` from crates.io of the same version as generator. Relative
+    /// paths are resolved against the current directory. Manifests of generated crates refer to this path relative to
+    /// their directories, so the generated workspace can be moved together with `mavspec`.
+    ///
+    /// Overrides `mavspec_path` configuration value set by [`Self::set_manifest_path`].
+    pub fn set_mavspec_path<T: Into<PathBuf>>(&mut self, mavspec_path: T) -> &mut Self {
+        self.0.mavspec_path = Some(mavspec_path.into());
+        self
+    }
+
+    /// Enables/disables printing of `cargo:rerun-if-changed` directives by [`BuildHelper::generate`].
+    ///
+    /// Directives are printed for [`BuildHelper::sources`] and [`BuildHelper::source_files`], except files within
+    /// `OUT_DIR` which are written by the build script itself. Once a build script prints any of these directives, Cargo
+    /// re-runs it only when listed files change, so build scripts which depend on other inputs should print directives
+    /// for them as well. Disabled by default.
+    pub fn set_emit_rerun_if_changed(&mut self, emit_rerun_if_changed: bool) -> &mut Self {
+        self.0.emit_rerun_if_changed = emit_rerun_if_changed;
+        self
+    }
+}
+
+/// Returns `path` joined with current directory unless it is already absolute.
+fn absolute_path<P: AsRef<Path>>(path: P) -> std::io::Result<PathBuf> {
+    let path = path.as_ref();
+    Ok(if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        current_dir()?.join(path)
+    })
+}
+
+/// Returns `path` relative to `base`, both paths are expected to be absolute.
+///
+/// Paths are normalized lexically. If paths have different roots (i.e. Windows drives), returns `path` as is.
+fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    fn normalize(path: &Path) -> Vec<Component<'_>> {
+        let mut components = Vec::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir if matches!(components.last(), Some(Component::Normal(_))) => {
+                    components.pop();
+                }
+                component => components.push(component),
+            }
+        }
+        components
+    }
+    let (path_components, base_components) = (normalize(path), normalize(base));
+
+    let common = path_components
+        .iter()
+        .zip(&base_components)
+        .take_while(|(left, right)| left == right)
+        .count();
+    if common == 0 {
+        return path.to_path_buf();
+    }
+
+    base_components[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .chain(path_components[common..].iter().copied())
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::remove_dir_all;
+    use crate::TypeKind;
+    use std::fs::{read_to_string, remove_dir_all, remove_file, write};
     use std::path::Path;
 
     #[test]
@@ -629,4 +1204,569 @@ mod tests {
         assert!(!mav_cmd.has_entry_with_name("MAV_CMD_PREFLIGHT_CALIBRATION"));
         /* and others */
     }
+
+    #[test]
+    fn build_helper_wip_policy() {
+        let helper = BuildHelper::builder("../tmp/mavlink").build().unwrap();
+        assert_eq!(helper.wip(), WipPolicy::Include);
+
+        let helper = BuildHelper::builder("../tmp/mavlink")
+            .set_wip(WipPolicy::Warn)
+            .build()
+            .unwrap();
+        assert_eq!(helper.wip(), WipPolicy::Warn);
+
+        assert_eq!("exclude".parse::<WipPolicy>().unwrap(), WipPolicy::Exclude);
+        assert!(matches!(
+            "ignore".parse::<WipPolicy>(),
+            Err(RustGenError::InvalidWipPolicy(_))
+        ));
+    }
+
+    #[test]
+    fn build_helper_excludes_wip_only_enums() {
+        let root = Path::new("../tmp/mavlink/wip_only_enums");
+        let definitions = root.join("definitions");
+        std::fs::create_dir_all(&definitions).unwrap();
+        write(
+            definitions.join("wip_only.xml"),
+            r#"<?xml version="1.0"?>
+<mavlink>
+    <version>3</version>
+    <dialect>0</dialect>
+    <enums>
+        <enum name="WIP_ONLY">
+            <description>Enum with work-in-progress entries only.</description>
+            <entry value="0" name="WIP_ONLY_FIRST">
+                <wip/>
+                <description>First entry.</description>
+            </entry>
+            <entry value="1" name="WIP_ONLY_SECOND">
+                <wip/>
+                <description>Second entry.</description>
+            </entry>
+        </enum>
+    </enums>
+    <messages>
+        <message id="1" name="WIP_ONLY_MESSAGE">
+            <description>Message with a field which refers to work-in-progress enum.</description>
+            <field type="uint8_t" name="mode" enum="WIP_ONLY">Mode.</field>
+        </message>
+    </messages>
+</mavlink>
+"#,
+        )
+        .unwrap();
+
+        let generate = |wip: WipPolicy| {
+            let out_path = root.join(format!("{wip:?}").to_lowercase());
+            BuildHelper::builder(&out_path)
+                .set_sources(&[&definitions])
+                .set_wip(wip)
+                .generate()
+                .unwrap();
+            out_path.join("dialects/wip_only")
+        };
+
+        let included = generate(WipPolicy::Include);
+        assert!(included.join("enums/wip_only.rs").exists());
+        let message = read_to_string(included.join("messages/wip_only_message.rs")).unwrap();
+        assert!(message.contains("pub mode: super::super::enums::WipOnly,"));
+
+        let excluded = generate(WipPolicy::Exclude);
+        assert!(!excluded.join("enums/wip_only.rs").exists());
+        let message = read_to_string(excluded.join("messages/wip_only_message.rs")).unwrap();
+        assert!(message.contains("pub mode: u8,"));
+
+        let vectors = BuildHelper::builder(root.join("exclude"))
+            .set_sources(&[&definitions])
+            .set_wip(WipPolicy::Exclude)
+            .build()
+            .unwrap()
+            .generate_test_vectors("wip_only", 0)
+            .unwrap();
+        assert_eq!(vectors.len(), 2);
+
+        remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn build_helper_scaled_accessors_with_multipliers() {
+        let root = Path::new("../tmp/mavlink/multiplier_scales");
+        let definitions = root.join("definitions");
+        std::fs::create_dir_all(&definitions).unwrap();
+        write(
+            definitions.join("scaled.xml"),
+            r#"<?xml version="1.0"?>
+<mavlink>
+    <version>3</version>
+    <dialect>0</dialect>
+    <messages>
+        <message id="1" name="SCALED">
+            <description>Message with scaled fields.</description>
+            <field type="int16_t" name="plain" units="cm">Field without multiplier.</field>
+            <field type="int16_t" name="folded" units="cm" multiplier="1E-2">Field with multiplier.</field>
+            <field type="uint8_t" name="distance" units="m" multiplier="1E-1">Field in target units.</field>
+        </message>
+    </messages>
+</mavlink>
+"#,
+        )
+        .unwrap();
+        write(
+            definitions.join("derived.xml"),
+            r#"<?xml version="1.0"?>
+<mavlink>
+    <include>scaled.xml</include>
+    <version>3</version>
+    <dialect>1</dialect>
+    <messages>
+        <message id="2" name="DERIVED">
+            <description>Message without multipliers.</description>
+            <field type="int16_t" name="folded" units="cm">Field without multiplier.</field>
+        </message>
+    </messages>
+</mavlink>
+"#,
+        )
+        .unwrap();
+
+        let out_path = root.join("generated");
+        BuildHelper::builder(&out_path)
+            .set_sources(&[&definitions])
+            .generate()
+            .unwrap();
+
+        let message = read_to_string(out_path.join("dialects/scaled/messages/scaled.rs")).unwrap();
+        assert!(message.contains("self.plain as f64 * 1.0 / 100.0"));
+        assert!(message.contains("self.folded as f64 * 1.0 / 10000.0"));
+        assert!(message.contains("self.distance as f64 * 1.0 / 10.0"));
+        // Multipliers are bound to the dialect where message is defined
+        let message = read_to_string(out_path.join("dialects/derived/messages/derived.rs")).unwrap();
+        assert!(message.contains("self.folded as f64 * 1.0 / 100.0"));
+
+        // Multipliers are unknown without definitions
+        let protocol = Inspector::builder()
+            .set_sources(&[&definitions])
+            .build()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let out_path = root.join("protocol");
+        BuildHelper::builder(&out_path)
+            .set_protocol(protocol)
+            .generate()
+            .unwrap();
+        let message = read_to_string(out_path.join("dialects/scaled/messages/scaled.rs")).unwrap();
+        assert!(!message.contains("as f64 *"));
+
+        remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn build_helper_serde_enums() {
+        let helper = BuildHelper::builder("../tmp/mavlink").build().unwrap();
+        assert_eq!(helper.serde_enums(), SerdeEnumRepr::Derive);
+
+        let helper = BuildHelper::builder("../tmp/mavlink")
+            .set_serde_enums(SerdeEnumRepr::Flags)
+            .build()
+            .unwrap();
+        assert_eq!(helper.serde_enums(), SerdeEnumRepr::Flags);
+
+        assert_eq!(
+            "numeric".parse::<SerdeEnumRepr>().unwrap(),
+            SerdeEnumRepr::Numeric
+        );
+        assert!(matches!(
+            "names".parse::<SerdeEnumRepr>(),
+            Err(RustGenError::InvalidSerdeEnumRepr(_))
+        ));
+    }
+
+    #[test]
+    fn build_helper_output_layout() {
+        let out_path = Path::new("../tmp/mavlink/helper_output_layout");
+        let generate = |layout: OutputLayout| {
+            BuildHelper::builder(out_path)
+                .set_sources(&["../message_definitions/standard"])
+                .set_include_dialects(&["minimal"])
+                .set_output_layout(layout)
+                .generate()
+                .unwrap();
+        };
+
+        generate(OutputLayout::Modules);
+        assert!(out_path
+            .join("dialects/minimal/messages/heartbeat.rs")
+            .exists());
+
+        generate(OutputLayout::SingleFile);
+        assert!(out_path.join("mod.rs").exists());
+        assert!(!out_path.join("dialects").exists());
+
+        generate(OutputLayout::FilePerDialect);
+        assert!(out_path.join("mod.rs").exists());
+        assert!(out_path.join("dialects/mod.rs").exists());
+        assert!(out_path.join("dialects/minimal.rs").exists());
+        assert!(!out_path.join("dialects/minimal").exists());
+
+        remove_dir_all(out_path).unwrap();
+
+        assert_eq!(
+            "single_file".parse::<OutputLayout>().unwrap(),
+            OutputLayout::SingleFile
+        );
+        assert!(matches!(
+            "single".parse::<OutputLayout>(),
+            Err(RustGenError::InvalidOutputLayout(_))
+        ));
+    }
+
+    #[test]
+    fn build_helper_generate_code() {
+        let out_path = Path::new("../tmp/mavlink/helper_generate_code");
+        let mut builder = BuildHelper::builder(out_path);
+        builder
+            .set_sources(&["../message_definitions/standard"])
+            .set_include_dialects(&["minimal"]);
+
+        let code = builder.build().unwrap().generate_code().unwrap();
+        assert!(!out_path.exists());
+
+        builder
+            .set_output_layout(OutputLayout::SingleFile)
+            .generate()
+            .unwrap();
+        assert_eq!(code, read_to_string(out_path.join("mod.rs")).unwrap());
+        remove_dir_all(out_path).unwrap();
+
+        let source_files = builder.build().unwrap().source_files().unwrap();
+        assert!(source_files.contains(&PathBuf::from(
+            "../message_definitions/standard/minimal.xml"
+        )));
+        assert!(source_files
+            .iter()
+            .all(|path| path.extension().unwrap() == "xml"));
+    }
+
+    #[test]
+    fn build_helper_writes_changed_files_only() {
+        let out_path = Path::new("../tmp/mavlink/helper_changed_files_only");
+        let mut builder = BuildHelper::builder(out_path);
+        builder
+            .set_sources(&["../message_definitions/standard"])
+            .set_include_dialects(&["minimal"]);
+        builder.generate().unwrap();
+
+        let heartbeat = out_path.join("dialects/minimal/messages/heartbeat.rs");
+        let protocol_version = out_path.join("dialects/minimal/messages/protocol_version.rs");
+        let modified = |path: &Path| path.metadata().unwrap().modified().unwrap();
+        let heartbeat_modified = modified(&heartbeat);
+        std::thread::sleep(std::time::Duration::from_millis(50));
+
+        // Unchanged files are kept, changed files are restored unless dialect is skipped due to fingerprints
+        write(&protocol_version, "// Changed").unwrap();
+        builder.generate().unwrap();
+        assert_eq!(modified(&heartbeat), heartbeat_modified);
+        #[cfg(not(feature = "fingerprints"))]
+        assert_ne!(read_to_string(&protocol_version).unwrap(), "// Changed");
+
+        // Files which are no longer generated are removed, files which were never generated are kept
+        write(out_path.join("README.md"), "# Bindings").unwrap();
+        write(out_path.join("dialects/notes.txt"), "Notes").unwrap();
+        builder.set_messages(&["HEARTBEAT"]).generate().unwrap();
+        assert!(!protocol_version.exists());
+        assert_eq!(modified(&heartbeat), heartbeat_modified);
+        assert_eq!(
+            read_to_string(out_path.join("README.md")).unwrap(),
+            "# Bindings"
+        );
+        assert!(out_path.join("dialects/notes.txt").exists());
+
+        // Files of a different layout are replaced, directories with foreign files are kept
+        builder
+            .set_output_layout(OutputLayout::SingleFile)
+            .generate()
+            .unwrap();
+        assert!(!out_path.join("dialects/minimal").exists());
+        assert!(out_path.join("dialects/notes.txt").exists());
+        assert!(out_path.join("README.md").exists());
+        assert!(out_path.join("mod.rs").exists());
+        assert_eq!(
+            read_to_string(out_path.join(".generated")).unwrap(),
+            "mod.rs\n"
+        );
+
+        remove_dir_all(out_path).unwrap();
+    }
+
+    #[test]
+    fn build_helper_verify() {
+        let out_path = Path::new("../tmp/mavlink/helper_verify");
+        let mut builder = BuildHelper::builder(out_path);
+        builder
+            .set_sources(&["../message_definitions/standard"])
+            .set_include_dialects(&["minimal"]);
+
+        let Err(RustGenError::Verification(report)) = builder.build().unwrap().verify() else {
+            panic!("verification of missing files should fail");
+        };
+        assert!(report
+            .missing
+            .contains(&PathBuf::from("dialects/minimal/messages/heartbeat.rs")));
+        assert!(!out_path.exists());
+
+        builder.generate().unwrap();
+        builder.build().unwrap().verify().unwrap();
+
+        let heartbeat = out_path.join("dialects/minimal/messages/heartbeat.rs");
+        let content = read_to_string(&heartbeat).unwrap();
+        write(
+            &heartbeat,
+            content.replacen("pub struct", "pub(crate) struct", 1),
+        )
+        .unwrap();
+        write(out_path.join("dialects/stale.rs"), "// Stale").unwrap();
+        let listing = read_to_string(out_path.join(".generated")).unwrap();
+        write(
+            out_path.join(".generated"),
+            format!("{listing}dialects/stale.rs\n"),
+        )
+        .unwrap();
+        remove_file(out_path.join("dialects/minimal/messages/protocol_version.rs")).unwrap();
+        // Files which were never generated are not reported
+        write(out_path.join("README.md"), "# Bindings").unwrap();
+        std::fs::create_dir_all(out_path.join("target")).unwrap();
+        write(out_path.join("target/Cargo.lock"), "").unwrap();
+
+        let Err(RustGenError::Verification(report)) = builder.build().unwrap().verify() else {
+            panic!("verification of changed files should fail");
+        };
+        assert_eq!(
+            report.missing,
+            vec![PathBuf::from(
+                "dialects/minimal/messages/protocol_version.rs"
+            )]
+        );
+        assert_eq!(report.extra, vec![PathBuf::from("dialects/stale.rs")]);
+        assert_eq!(
+            report.differing,
+            vec![PathBuf::from("dialects/minimal/messages/heartbeat.rs")]
+        );
+        assert!(report
+            .diff
+            .starts_with("--- a/dialects/minimal/messages/heartbeat.rs\n"));
+        assert!(report.diff.contains("\n-pub(crate) struct Heartbeat {\n"));
+        assert!(report.diff.contains("\n+pub struct Heartbeat {\n"));
+        // Nothing is written
+        assert_eq!(
+            read_to_string(out_path.join("dialects/stale.rs")).unwrap(),
+            "// Stale"
+        );
+
+        // Files which differ only by a newline at the end have non-empty diff
+        builder.generate().unwrap();
+        write(&heartbeat, content.trim_end()).unwrap();
+        let Err(RustGenError::Verification(report)) = builder.build().unwrap().verify() else {
+            panic!("verification of files without trailing newline should fail");
+        };
+        assert_eq!(report.differing.len(), 1);
+        assert!(report.diff.contains("\\ No newline at end of file\n"));
+
+        remove_dir_all(out_path).unwrap();
+    }
+
+    #[cfg(feature = "fingerprints")]
+    #[test]
+    fn build_helper_dialect_fingerprints() {
+        let out_path = Path::new("../tmp/mavlink/helper_dialect_fingerprints");
+        let mut builder = BuildHelper::builder(out_path);
+        builder
+            .set_sources(&["../message_definitions/standard"])
+            .set_include_dialects(&["minimal", "standard"]);
+        builder.generate().unwrap();
+        assert!(out_path.join(".fingerprint").exists());
+
+        // Dialects with unchanged fingerprints are not re-generated
+        let heartbeat = out_path.join("dialects/minimal/messages/heartbeat.rs");
+        write(&heartbeat, "// Changed").unwrap();
+        builder.generate().unwrap();
+        assert_eq!(read_to_string(&heartbeat).unwrap(), "// Changed");
+
+        // Changed generator parameters affect fingerprints
+        builder.set_generate_builders(true).generate().unwrap();
+        assert_ne!(read_to_string(&heartbeat).unwrap(), "// Changed");
+
+        remove_dir_all(out_path).unwrap();
+    }
+
+    #[test]
+    fn build_helper_workspace_layout() {
+        let out_path = Path::new("../tmp/mavlink/helper_workspace_layout");
+        BuildHelper::builder(out_path)
+            .set_sources(&[
+                "../message_definitions/standard",
+                "../message_definitions/extra",
+            ])
+            .set_include_dialects(&["MAVInspect_test"])
+            .set_serde(true)
+            .set_output_layout(OutputLayout::Workspace)
+            .set_mavspec_path("../mavspec")
+            .generate()
+            .unwrap();
+
+        let read = |path: &str| std::fs::read_to_string(out_path.join(path)).unwrap();
+
+        let workspace = read("Cargo.toml");
+        assert!(workspace.contains("\"mav_inspect_test\""));
+        assert!(workspace.contains("\"minimal\""));
+        assert!(!out_path.join("mod.rs").exists());
+
+        let manifest = read("mav_inspect_test/Cargo.toml");
+        assert!(manifest.contains("name = \"mavlink_mav_inspect_test\""));
+        assert!(manifest.contains("mavlink_minimal = { path = \"../minimal\""));
+        assert!(manifest.contains("\"mavspec/serde\", \"mavlink_minimal/serde\""));
+        assert!(manifest.contains("alloc = [\"mavspec/alloc\", \"mavlink_minimal/alloc\"]"));
+        assert!(!manifest.contains("arbitrary"));
+        assert!(manifest.contains("mavspec = { path = \"../../../../mavspec\""));
+
+        let manifest = read("minimal/Cargo.toml");
+        assert!(manifest.contains("alloc = [\"mavspec/alloc\"]"));
+
+        assert!(read("mav_inspect_test/src/lib.rs").contains("no_std"));
+        assert!(read("mav_inspect_test/src/messages/heartbeat.rs")
+            .contains("pub use mavlink_minimal::messages::heartbeat::{"));
+        assert!(read("mav_inspect_test/src/enums/mav_type.rs")
+            .contains("pub use mavlink_minimal::enums::mav_type::MavType;"));
+        assert!(out_path.join("minimal/src/messages/heartbeat.rs").exists());
+
+        // Files created by Cargo in generated workspace survive regeneration
+        write(out_path.join("Cargo.lock"), "# Lock").unwrap();
+        std::fs::create_dir_all(out_path.join("target/debug")).unwrap();
+        write(out_path.join("target/debug/build.log"), "").unwrap();
+        BuildHelper::builder(out_path)
+            .set_sources(&["../message_definitions/standard"])
+            .set_include_dialects(&["minimal"])
+            .set_output_layout(OutputLayout::Workspace)
+            .generate()
+            .unwrap();
+        assert!(out_path.join("Cargo.lock").exists());
+        assert!(out_path.join("target/debug/build.log").exists());
+        assert!(!out_path.join("mav_inspect_test").exists());
+
+        remove_dir_all(out_path).unwrap();
+    }
+
+    #[test]
+    fn relative_paths() {
+        assert_eq!(
+            relative_path(Path::new("/crate/mavspec"), Path::new("/crate/tmp/mavlink")),
+            Path::new("../../mavspec")
+        );
+        assert_eq!(
+            relative_path(
+                Path::new("/crate/a/../mavspec"),
+                Path::new("/crate/./b/../tmp")
+            ),
+            Path::new("../mavspec")
+        );
+        assert_eq!(
+            relative_path(Path::new("/crate/tmp/mavspec"), Path::new("/crate")),
+            Path::new("tmp/mavspec")
+        );
+    }
+
+    #[test]
+    fn build_helper_emit_rerun_if_changed_is_opt_in() {
+        let mut builder = BuildHelper::builder("../tmp/mavlink");
+        assert!(!builder.build().unwrap().emit_rerun_if_changed());
+        assert!(builder
+            .set_emit_rerun_if_changed(true)
+            .build()
+            .unwrap()
+            .emit_rerun_if_changed());
+    }
+
+    #[test]
+    fn build_helper_setters_override_manifest() {
+        let helper = BuildHelper::builder("../tmp/mavlink")
+            .set_manifest_path("../tests/rust/Cargo.toml")
+            .build()
+            .unwrap();
+        assert!(helper.generate_tests());
+        assert!(helper.generate_builders());
+
+        let helper = BuildHelper::builder("../tmp/mavlink")
+            .set_generate_tests(false)
+            .set_generate_builders(false)
+            .set_manifest_path("../tests/rust/Cargo.toml")
+            .build()
+            .unwrap();
+        assert!(!helper.generate_tests());
+        assert!(!helper.generate_builders());
+    }
+
+    #[test]
+    fn build_helper_type_attributes() {
+        let helper = BuildHelper::builder("../tmp/mavlink")
+            .set_manifest_path("tests/manifests/type_attributes.toml")
+            .build()
+            .unwrap();
+        let type_attributes = helper.type_attributes();
+        assert_eq!(type_attributes.len(), 2);
+        assert!(type_attributes[0].kinds.is_empty());
+        assert_eq!(type_attributes[0].derives, ["PartialEq", "Eq", "Hash"]);
+        assert_eq!(type_attributes[1].kinds, [TypeKind::Message]);
+
+        let helper = BuildHelper::builder("../tmp/mavlink")
+            .set_manifest_path("tests/manifests/type_attributes.toml")
+            .set_type_attributes(&[])
+            .build()
+            .unwrap();
+        assert!(helper.type_attributes().is_empty());
+
+        assert!(matches!(
+            BuildHelper::builder("../tmp/mavlink")
+                .set_type_attributes(&[TypeAttributes {
+                    attributes: vec!["#[repr(C".to_string()],
+                    ..Default::default()
+                }])
+                .build(),
+            Err(RustGenError::InvalidTypeAttributes(_))
+        ));
+    }
+
+    #[test]
+    fn build_helper_test_vectors() {
+        let helper = BuildHelper::builder("../tmp/mavlink")
+            .set_manifest_path("../tests/rust/Cargo.toml")
+            .build()
+            .unwrap();
+        assert_eq!(
+            helper.test_vectors().unwrap(),
+            [
+                Path::new("../tests/rust/test_vectors/minimal.json"),
+                Path::new("../tests/rust/test_vectors/mav_inspect_test.csv"),
+            ]
+        );
+
+        let helper = BuildHelper::builder("../tmp/mavlink")
+            .set_manifest_path("../tests/rust/Cargo.toml")
+            .set_test_vectors(&["vectors.json"])
+            .build()
+            .unwrap();
+        assert_eq!(helper.test_vectors().unwrap(), [Path::new("vectors.json")]);
+
+        let out_path = "../tmp/mavlink/helper_test_vectors";
+        assert!(matches!(
+            BuildHelper::builder(out_path)
+                .set_sources(&["../message_definitions/standard"])
+                .set_include_dialects(&["minimal"])
+                .set_test_vectors(&["../tests/rust/test_vectors/common.json"])
+                .generate(),
+            Err(RustGenError::InvalidTestVectors(_))
+        ));
+        remove_dir_all(out_path).unwrap_or_default();
+    }
 }
@@ -1,3 +1,5 @@
+use mavinspect::protocol::Units;
+
 // See: https://doc.rust-lang.org/reference/keywords.html
 const RUST_RESERVED_KEYWORDS: [&str; 50] = [
     "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
@@ -17,6 +19,14 @@ pub fn dialect_mod_name(dialect_name: String) -> String {
     heck::AsSnakeCase(dialect_name).to_string()
 }
 
+pub fn dialect_crate_name(dialect_name: &str) -> String {
+    format!("mavlink_{}", dialect_mod_name(dialect_name.to_string()))
+}
+
+pub fn dialect_crate_dir_name(dialect_name: &str) -> String {
+    dialect_mod_name(dialect_name.to_string())
+}
+
 pub fn dialect_enum_name(dialect_name: &str) -> String {
     heck::AsUpperCamelCase(dialect_name).to_string()
 }
@@ -92,6 +102,25 @@ pub fn message_struct_name(message_name: &str) -> String {
     valid_rust_name(heck::AsUpperCamelCase(message_name).to_string().as_str())
 }
 
+pub fn message_accessor_name(message_name: &str) -> String {
+    format!("as_{}", heck::AsSnakeCase(message_name))
+}
+
+pub fn message_handler_name(message_name: &str) -> String {
+    format!("on_{}", heck::AsSnakeCase(message_name))
+}
+
 pub fn rust_var_name(var_name: &str) -> String {
     valid_rust_name(heck::AsSnakeCase(var_name).to_string().as_str())
 }
+
+/// Name of the marker type from `mavspec::rust::spec::units` which corresponds to MAVLink units.
+///
+/// Returns [`None`] for units without a marker type.
+pub fn units_type_name(units: &Units) -> Option<&'static str> {
+    mavspec_rust_spec::units::type_name(units.to_str())
+}
+
+pub fn scaled_accessor_name(field_name: &str, suffix: &str) -> String {
+    valid_rust_name(format!("{}_{suffix}", heck::AsSnakeCase(field_name)).as_str())
+}
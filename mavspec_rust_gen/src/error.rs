@@ -5,6 +5,8 @@ use std::sync::Arc;
 use cargo_manifest::Error as ManifestError;
 use mavinspect::errors::Error as InspectError;
 
+use crate::verify::VerificationReport;
+
 /// Result returned by all user-facing functions in MAVSpec Rust generation module.
 pub type RustGenResult<T> = Result<T, RustGenError>;
 
@@ -20,6 +22,27 @@ pub enum RustGenError {
     /// Cargo manifest parsing error.
     #[error("Cargo manifest error: {0:?}")]
     Manifest(Arc<ManifestError>),
+    /// Invalid policy for work-in-progress entities.
+    #[error("invalid WIP policy: {0:?}, expected `include`, `exclude`, or `warn`")]
+    InvalidWipPolicy(String),
+    /// Invalid `serde` representation of enums.
+    #[error("invalid serde enum representation: {0:?}, expected `derive`, `numeric`, `name`, or `flags`")]
+    InvalidSerdeEnumRepr(String),
+    /// Invalid layout of generated files.
+    #[error("invalid output layout: {0:?}, expected `modules`, `single_file`, `file_per_dialect`, or `workspace`")]
+    InvalidOutputLayout(String),
+    /// Invalid extra derives or attributes for generated types.
+    #[error("invalid type attributes: {0}")]
+    InvalidTypeAttributes(String),
+    /// Invalid test vectors for known-answer tests.
+    #[error("invalid test vectors: {0}")]
+    InvalidTestVectors(String),
+    /// Serialization error.
+    #[error("serialization error: {0:?}")]
+    Serialization(Arc<serde_json::Error>),
+    /// Existing files differ from generated code.
+    #[error("{0}")]
+    Verification(Arc<VerificationReport>),
 }
 
 impl From<std::io::Error> for RustGenError {
@@ -34,6 +57,12 @@ impl From<InspectError> for RustGenError {
     }
 }
 
+impl From<serde_json::Error> for RustGenError {
+    fn from(value: serde_json::Error) -> Self {
+        RustGenError::Serialization(Arc::new(value))
+    }
+}
+
 impl From<ManifestError> for RustGenError {
     fn from(value: ManifestError) -> Self {
         RustGenError::Manifest(Arc::new(value))
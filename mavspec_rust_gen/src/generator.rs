@@ -1,19 +1,23 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsStr;
-#[cfg(feature = "fingerprints")]
-use std::fs::read_to_string;
-use std::fs::{create_dir_all, File};
+use std::fs::{create_dir_all, read, read_dir, read_to_string, remove_dir, remove_file, File};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
 
 #[cfg(feature = "fingerprints")]
 use base64::{engine::general_purpose, Engine as _};
+#[cfg(feature = "fingerprints")]
+use crc_any::CRCu64;
 use serde::Serialize;
+use syn::ext::IdentExt;
 
 use mavinspect::protocol::{Dialect, Enum, Protocol};
 
 use crate::conventions;
-use crate::error::RustGenResult;
+use crate::error::{RustGenError, RustGenResult};
+use crate::multipliers::FieldMultiplier;
 use crate::specs::dialects::dialect::enums::{
     EnumImplModuleSpec, EnumInheritedModuleSpec, EnumsRootModuleSpec,
 };
@@ -22,15 +26,148 @@ use crate::specs::dialects::dialect::messages::{
 };
 use crate::specs::dialects::dialect::DialectModuleSpec;
 use crate::specs::dialects::DialectsRootModuleSpec;
+use crate::specs::workspace::{DialectCrateSpec, WorkspaceSpec};
 use crate::templates;
+use crate::test_vectors::TestVector;
+use crate::type_attributes::TypeAttributes;
+use crate::verify::{self, VerificationReport};
 
 /// [`Generator`] parameters.
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct GeneratorParams {
     pub serde: bool,
+    pub arbitrary: bool,
+    pub async_handlers: bool,
     pub generate_tests: bool,
+    pub generate_builders: bool,
+    pub typed_units: bool,
+    pub wip: WipPolicy,
+    pub serde_enums: SerdeEnumRepr,
+    pub type_attributes: Vec<TypeAttributes>,
+    pub test_vectors: Vec<TestVector>,
+    pub output_layout: OutputLayout,
+    pub mavspec_path: Option<PathBuf>,
+    pub scaled_accessors: bool,
+    pub field_multipliers: Vec<FieldMultiplier>,
+}
+
+/// Defines how work-in-progress MAVLink entities (marked with `<wip/>`) are generated.
+///
+/// Messages and enum entries (including commands) may be marked as work in progress. Such entities are unstable and
+/// may change in the future.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub enum WipPolicy {
+    /// Generate work-in-progress entities as regular ones.
+    #[default]
+    Include,
+    /// Do not generate work-in-progress entities.
+    ///
+    /// Enums which have only work-in-progress entries are skipped entirely, fields which refer to them are generated
+    /// as plain numeric fields.
+    Exclude,
+    /// Generate work-in-progress entities marked as `#[deprecated]`, so compiler warns when they are used.
+    Warn,
+}
+
+impl WipPolicy {
+    /// Whether MAVLink enum is skipped because all its entries are work in progress.
+    pub(crate) fn excludes_enum(&self, mav_enum: &Enum) -> bool {
+        *self == WipPolicy::Exclude
+            && !mav_enum.entries().is_empty()
+            && mav_enum.entries().iter().all(|entry| entry.wip())
+    }
 }
 
+impl FromStr for WipPolicy {
+    type Err = RustGenError;
+
+    /// Parses policy from `include`, `exclude`, or `warn`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "include" => Ok(WipPolicy::Include),
+            "exclude" => Ok(WipPolicy::Exclude),
+            "warn" => Ok(WipPolicy::Warn),
+            _ => Err(RustGenError::InvalidWipPolicy(s.to_string())),
+        }
+    }
+}
+
+/// Defines how MAVLink enums and bitmasks are represented by `serde` in human-readable formats (i.e. JSON).
+///
+/// Deserialization accepts any of the representations regardless of the chosen one, [`SerdeEnumRepr::Derive`] also
+/// accepts Rust variant names of enums. Formats which are not human-readable always use numeric values, except for
+/// enums with [`SerdeEnumRepr::Derive`] which keep variant indices of derived `serde` implementation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub enum SerdeEnumRepr {
+    /// Use `serde` derive macros: enums are represented by Rust variant names and bitmasks by numeric values.
+    #[default]
+    Derive,
+    /// Numeric values of enum entries and bitmasks.
+    Numeric,
+    /// Canonical MAVLink names of enum entries, bitmasks are represented as `|`-separated flag names.
+    Name,
+    /// Canonical MAVLink names of enum entries, bitmasks are represented as lists of flag names.
+    Flags,
+}
+
+impl FromStr for SerdeEnumRepr {
+    type Err = RustGenError;
+
+    /// Parses representation from `derive`, `numeric`, `name`, or `flags`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "derive" => Ok(SerdeEnumRepr::Derive),
+            "numeric" => Ok(SerdeEnumRepr::Numeric),
+            "name" => Ok(SerdeEnumRepr::Name),
+            "flags" => Ok(SerdeEnumRepr::Flags),
+            _ => Err(RustGenError::InvalidSerdeEnumRepr(s.to_string())),
+        }
+    }
+}
+
+/// Defines how generated modules are laid out in files.
+///
+/// Public paths of generated entities (i.e. `dialects::minimal::messages::Heartbeat`) are the same for all layouts
+/// except [`OutputLayout::Workspace`], and the root module is always written to `mod.rs` in the output directory, so it
+/// can be included with `include!`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub enum OutputLayout {
+    /// Each dialect, enum, and message has its own module file.
+    #[default]
+    Modules,
+    /// All dialects are written to a single self-contained `mod.rs` file.
+    SingleFile,
+    /// Each dialect is written to a single file in `dialects` directory.
+    FilePerDialect,
+    /// Cargo workspace with a crate for each dialect (i.e. `mavlink_common` in `common` directory).
+    ///
+    /// Crate root corresponds to the dialect module of other layouts (i.e. `mavlink_common::messages::Heartbeat`).
+    /// Dialect crates depend on crates of dialects they inherit messages and enums from and re-export inherited
+    /// entities.
+    Workspace,
+}
+
+impl FromStr for OutputLayout {
+    type Err = RustGenError;
+
+    /// Parses layout from `modules`, `single_file`, `file_per_dialect`, or `workspace`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "modules" => Ok(OutputLayout::Modules),
+            "single_file" => Ok(OutputLayout::SingleFile),
+            "file_per_dialect" => Ok(OutputLayout::FilePerDialect),
+            "workspace" => Ok(OutputLayout::Workspace),
+            _ => Err(RustGenError::InvalidOutputLayout(s.to_string())),
+        }
+    }
+}
+
+/// Generated modules by their file paths.
+type Modules = BTreeMap<PathBuf, syn::File>;
+
+/// Contents of generated files by their paths.
+type Files = BTreeMap<PathBuf, String>;
+
 /// Rust code generator.
 pub struct Generator {
     protocol: Arc<Protocol>,
@@ -57,16 +194,19 @@ impl Generator {
         log::info!("Generating Rust code from MAVLink protocol.");
 
         #[cfg(feature = "fingerprints")]
-        if !self.fingerprint_has_updated()? {
-            log::info!("Fingerprint hasn't changed. Skipping.");
-            return Ok(());
-        }
+        let unchanged = self.unchanged_dialects()?;
+        #[cfg(not(feature = "fingerprints"))]
+        let unchanged = BTreeSet::new();
 
-        self.generate_root_module()?;
-        self.generate_dialects()?;
+        let files = self.layout_modules(self.generate_modules(&unchanged), &unchanged);
+        let preserved: Vec<PathBuf> = unchanged
+            .iter()
+            .filter_map(|dialect_name| self.dialect_output_path(dialect_name))
+            .collect();
+        self.write_files(files, &preserved)?;
 
         #[cfg(feature = "fingerprints")]
-        self.generate_fingerprint()?;
+        self.write_fingerprints()?;
 
         log::info!(
             "Generation results: {}",
@@ -76,155 +216,251 @@ impl Generator {
         Ok(())
     }
 
+    /// Generate Rust bindings in memory and compare them with existing files without writing anything.
+    ///
+    /// Returns [`RustGenError::Verification`] if some files are missing, extra, or differ from generated ones. Extra files
+    /// are files listed as generated by the previous generation which are no longer generated, other files within
+    /// output path are ignored.
+    pub fn verify(&self) -> RustGenResult<()> {
+        let files = self.layout_modules(self.generate_modules(&BTreeSet::new()), &BTreeSet::new());
+        let relative = |path: &Path| path.strip_prefix(&self.path).unwrap().to_path_buf();
+
+        let mut report = VerificationReport::default();
+        for (path, content) in &files {
+            if !path.is_file() {
+                report.missing.push(relative(path));
+                continue;
+            }
+
+            let existing_content = read(path)?;
+            if existing_content != content.as_bytes() {
+                let path = relative(path);
+                report.diff.push_str(&verify::unified_diff(
+                    &path.to_string_lossy(),
+                    &String::from_utf8_lossy(&existing_content),
+                    content,
+                ));
+                report.differing.push(path);
+            }
+        }
+        // Only files listed as generated are checked, so files added by users or Cargo are not reported
+        report.extra = self
+            .previously_generated_files()
+            .iter()
+            .filter(|path| !files.contains_key(*path) && path.is_file())
+            .map(|path| relative(path))
+            .collect();
+
+        if report.is_empty() {
+            log::info!("Generated code is up to date: {:?}", self.path);
+            Ok(())
+        } else {
+            Err(RustGenError::Verification(Arc::new(report)))
+        }
+    }
+
+    /// Generate Rust bindings as a source code of a single root module without writing files.
+    ///
+    /// All submodules are inlined just like for [`OutputLayout::SingleFile`]. The result is expected to be used in
+    /// code-generating macros, therefore [`OutputLayout::Workspace`] is not supported.
+    pub fn generate_code(&self) -> String {
+        let mut modules = self.generate_modules(&BTreeSet::new());
+        let root = inline_modules(&self.root_module_file_path("mod.rs"), &mut modules);
+        prettyplease::unparse(&root)
+    }
+
+    /// Generates modules of all dialects except `skipped` ones.
+    fn generate_modules(&self, skipped: &BTreeSet<String>) -> Modules {
+        let mut modules = Modules::new();
+        self.generate_root_module(&mut modules);
+        self.generate_dialects(&mut modules, skipped);
+        modules
+    }
+
+    /// Fingerprints of dialects by their names.
+    ///
+    /// Dialect fingerprint takes into account its definition, definitions of dialects it depends on, generator
+    /// version and parameters. Dialects with unchanged fingerprints are not re-generated.
     #[cfg(feature = "fingerprints")]
-    fn fingerprint(&self) -> String {
-        general_purpose::STANDARD_NO_PAD.encode(self.protocol.fingerprint().to_le_bytes())
+    fn dialect_fingerprints(&self) -> RustGenResult<BTreeMap<String, String>> {
+        // CRC is stable across Rust versions and platforms unlike `std` hashers
+        let mut params_crc = CRCu64::crc64();
+        params_crc.digest(env!("CARGO_PKG_VERSION").as_bytes());
+        params_crc.digest(serde_json::to_string(&self.params)?.as_bytes());
+        let params_fingerprint = params_crc.get_crc();
+
+        Ok(self
+            .protocol
+            .dialects()
+            .map(|dialect| {
+                let mut crc = CRCu64::crc64();
+                crc.digest(&params_fingerprint.to_le_bytes());
+                crc.digest(&dialect.fingerprint().to_le_bytes());
+
+                let dialect_spec = DialectModuleSpec::new(dialect, &self.params);
+                for dependency in self.dialect_dependencies(&dialect_spec) {
+                    crc.digest(format!("{dependency:?} ").as_bytes());
+                    if let Some(dependency) = self.protocol.get_dialect_by_name(dependency) {
+                        crc.digest(&dependency.fingerprint().to_le_bytes());
+                    }
+                }
+
+                let fingerprint =
+                    general_purpose::STANDARD_NO_PAD.encode(crc.get_crc().to_le_bytes());
+                (dialect.name().to_string(), fingerprint)
+            })
+            .collect())
     }
 
+    /// Dialects with the same fingerprints as during the previous generation whose output still exists.
     #[cfg(feature = "fingerprints")]
-    fn fingerprint_has_updated(&self) -> RustGenResult<bool> {
-        if self.fingerprint_path().exists() {
-            let existing_fingerprint = read_to_string(self.fingerprint_path())?;
-            return Ok(existing_fingerprint != self.fingerprint());
+    fn unchanged_dialects(&self) -> RustGenResult<BTreeSet<String>> {
+        let previous: BTreeMap<String, String> = read_to_string(self.fingerprint_path())
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(name, fingerprint)| (name.to_string(), fingerprint.to_string()))
+            .collect();
+
+        let unchanged: BTreeSet<String> = self
+            .dialect_fingerprints()?
+            .into_iter()
+            .filter(|(name, fingerprint)| {
+                previous.get(name) == Some(fingerprint)
+                    && self
+                        .dialect_output_path(name)
+                        .is_some_and(|path| path.exists())
+            })
+            .map(|(name, _)| name)
+            .collect();
+        for dialect_name in &unchanged {
+            log::info!("Fingerprint of '{dialect_name}' dialect hasn't changed. Skipping.");
         }
 
-        Ok(true)
+        Ok(unchanged)
     }
 
     #[cfg(feature = "fingerprints")]
-    fn generate_fingerprint(&self) -> RustGenResult<()> {
-        let mut file = File::create(self.fingerprint_path())?;
-        file.write_all(self.fingerprint().as_bytes())?;
+    fn write_fingerprints(&self) -> RustGenResult<()> {
+        let fingerprints: String = self
+            .dialect_fingerprints()?
+            .into_iter()
+            .map(|(name, fingerprint)| format!("{name} {fingerprint}\n"))
+            .collect();
+        write_if_changed(&self.fingerprint_path(), &fingerprints)?;
         Ok(())
     }
 
-    fn generate_root_module(&self) -> RustGenResult<()> {
-        create_dir_all(self.path.as_path())?;
-
-        let mut file = File::create(self.root_module_file_path("mod.rs"))?;
-        let content = prettyplease::unparse(&templates::root_module());
-
-        file.write_all(content.as_bytes())?;
+    fn generate_root_module(&self, modules: &mut Modules) {
+        modules.insert(
+            self.root_module_file_path("mod.rs"),
+            templates::root_module(),
+        );
         log::debug!("Generated: root module.");
-
-        Ok(())
     }
 
-    fn generate_dialects(&self) -> RustGenResult<()> {
-        create_dir_all(self.dialects_dir())?;
-
-        let mut file = File::create(self.dialects_mod_rs())?;
-        let content = prettyplease::unparse(&templates::dialects::dialects_root_module(
-            &DialectsRootModuleSpec::new(self.protocol.as_ref(), &self.params),
-        ));
-
-        file.write_all(content.as_bytes())?;
+    fn generate_dialects(&self, modules: &mut Modules, skipped: &BTreeSet<String>) {
+        modules.insert(
+            self.dialects_mod_rs(),
+            templates::dialects::dialects_root_module(&DialectsRootModuleSpec::new(
+                self.protocol.as_ref(),
+                &self.params,
+            )),
+        );
         log::debug!("Generated: 'dialects' root module.");
 
         for dialect in self.protocol.dialects() {
+            if skipped.contains(dialect.name()) {
+                continue;
+            }
             let dialect_spec = DialectModuleSpec::new(dialect, &self.params);
-            self.generate_dialect(&dialect_spec)?;
+            self.generate_dialect(&dialect_spec, modules);
         }
-
-        Ok(())
     }
 
-    fn generate_dialect(&self, dialect_spec: &DialectModuleSpec) -> RustGenResult<()> {
-        create_dir_all(self.dialect_dir(dialect_spec.name()))?;
-
-        let mut file = File::create(self.dialect_mod_rs(dialect_spec.name()))?;
-        let content =
-            prettyplease::unparse(&templates::dialects::dialect::dialect_module(dialect_spec));
-
-        file.write_all(content.as_bytes())?;
+    fn generate_dialect(&self, dialect_spec: &DialectModuleSpec, modules: &mut Modules) {
+        modules.insert(
+            self.dialect_mod_rs(dialect_spec.name()),
+            templates::dialects::dialect::dialect_module(dialect_spec),
+        );
         log::debug!(
             "Generated: 'dialects::{}' root module.",
             dialect_spec.name()
         );
 
-        self.generate_enums(dialect_spec)?;
-        self.generate_messages(dialect_spec)?;
-
-        Ok(())
+        self.generate_enums(dialect_spec, modules);
+        self.generate_messages(dialect_spec, modules);
     }
 
-    fn generate_enums(&self, dialect_spec: &DialectModuleSpec) -> RustGenResult<()> {
-        create_dir_all(self.enums_dir(dialect_spec.name()))?;
-
-        let mut file = File::create(self.enums_mod_rs(dialect_spec.name()))?;
-        let content =
-            prettyplease::unparse(&templates::dialects::dialect::enums::enums_root_module(
-                &EnumsRootModuleSpec::new(dialect_spec, &self.params),
-            ));
-
-        file.write_all(content.as_bytes())?;
+    fn generate_enums(&self, dialect_spec: &DialectModuleSpec, modules: &mut Modules) {
+        modules.insert(
+            self.enums_mod_rs(dialect_spec.name()),
+            templates::dialects::dialect::enums::enums_root_module(&EnumsRootModuleSpec::new(
+                dialect_spec,
+                &self.params,
+            )),
+        );
         log::debug!(
             "Generated: 'dialects::{}::enums' root module.",
             dialect_spec.name()
         );
 
         for mav_enum in dialect_spec.enums() {
-            let mut file = File::create(self.enum_file(dialect_spec.name(), mav_enum.name()))?;
-
             let content = if let Some(inherited_from_dialect) =
                 self.enum_inherited_from(mav_enum, dialect_spec.name())
             {
-                prettyplease::unparse(&templates::dialects::dialect::enums::enum_inherited_module(
+                templates::dialects::dialect::enums::enum_inherited_module(
                     &EnumInheritedModuleSpec::new(
                         mav_enum,
                         inherited_from_dialect.name(),
                         &self.params,
                     ),
-                ))
+                )
             } else {
-                prettyplease::unparse(&templates::dialects::dialect::enums::enum_module(
-                    &EnumImplModuleSpec::new(mav_enum, &self.params),
+                templates::dialects::dialect::enums::enum_module(&EnumImplModuleSpec::new(
+                    mav_enum,
+                    &self.params,
                 ))
             };
 
-            file.write_all(content.as_bytes())?;
+            modules.insert(
+                self.enum_file(dialect_spec.name(), mav_enum.name()),
+                content,
+            );
             log::trace!(
                 "Generated: enum '{}' for dialect '{}'.",
                 mav_enum.name(),
                 dialect_spec.name(),
             );
         }
-
-        Ok(())
     }
 
-    fn generate_messages(&self, dialect_spec: &DialectModuleSpec) -> RustGenResult<()> {
-        create_dir_all(self.messages_dir(dialect_spec.name()))?;
-
-        let mut file = File::create(self.messages_mod_rs(dialect_spec.name()))?;
-        let content = prettyplease::unparse(
-            &templates::dialects::dialect::messages::messages_root_module(
+    fn generate_messages(&self, dialect_spec: &DialectModuleSpec, modules: &mut Modules) {
+        modules.insert(
+            self.messages_mod_rs(dialect_spec.name()),
+            templates::dialects::dialect::messages::messages_root_module(
                 &MessagesRootModuleSpec::new(dialect_spec, &self.params),
             ),
         );
-
-        file.write_all(content.as_bytes())?;
         log::debug!(
             "Generated: 'dialects::{}::messages' root module.",
             dialect_spec.name()
         );
 
         for message in dialect_spec.messages() {
-            let mut file = File::create(self.message_file(dialect_spec.name(), message.name()))?;
+            let path = self.message_file(dialect_spec.name(), message.name());
 
             match message.defined_in() {
                 Some(dialect_name) if dialect_name != dialect_spec.name() => {
-                    let content = prettyplease::unparse(
-                        &templates::dialects::dialect::messages::inherited_message_module(
-                            &MessageInheritedModuleSpec::new(
-                                dialect_name.as_str(),
-                                message,
-                                &self.params,
-                            ),
+                    let content = templates::dialects::dialect::messages::inherited_message_module(
+                        &MessageInheritedModuleSpec::new(
+                            dialect_name.as_str(),
+                            message,
+                            &self.params,
                         ),
                     );
 
-                    file.write_all(content.as_bytes())?;
+                    modules.insert(path, content);
                     log::trace!(
                         "Message '{}' in dialect '{}' is inherited from dialect '{}'.",
                         message.name(),
@@ -233,13 +469,11 @@ impl Generator {
                     );
                 }
                 _ => {
-                    let content = prettyplease::unparse(
-                        &templates::dialects::dialect::messages::message_module(
-                            &MessageImplModuleSpec::new(message, dialect_spec),
-                        ),
+                    let content = templates::dialects::dialect::messages::message_module(
+                        &MessageImplModuleSpec::new(message, dialect_spec),
                     );
 
-                    file.write_all(content.as_bytes())?;
+                    modules.insert(path, content);
                     log::trace!(
                         "Generated: message '{}' for dialect '{}'.",
                         message.name(),
@@ -249,19 +483,229 @@ impl Generator {
             }
         }
         log::debug!("Generated: all '{}' dialect messages.", dialect_spec.name());
+    }
+
+    /// Merges generated modules into files according to [`OutputLayout`].
+    fn layout_modules(&self, mut modules: Modules, skipped: &BTreeSet<String>) -> Files {
+        match self.params.output_layout {
+            OutputLayout::Modules => {}
+            OutputLayout::SingleFile => {
+                let root_path = self.root_module_file_path("mod.rs");
+                let root = inline_modules(&root_path, &mut modules);
+                modules = Modules::from([(root_path, root)]);
+            }
+            OutputLayout::FilePerDialect => {
+                for dialect in self.protocol.dialects() {
+                    if skipped.contains(dialect.name()) {
+                        continue;
+                    }
+                    let dialect_mod_rs = self.dialect_mod_rs(dialect.name());
+                    let dialect_file = self.dialect_dir(dialect.name()).with_extension("rs");
+                    let content = inline_modules(&dialect_mod_rs, &mut modules);
+                    modules.insert(dialect_file, content);
+                }
+            }
+            OutputLayout::Workspace => return self.layout_workspace(modules),
+        }
+
+        modules
+            .into_iter()
+            .map(|(path, content)| (path, prettyplease::unparse(&content)))
+            .collect()
+    }
+
+    /// Moves module tree of each dialect to a separate crate and adds Cargo manifests.
+    fn layout_workspace(&self, modules: Modules) -> Files {
+        let mut files = Files::new();
+        let dialects: Vec<&Dialect> = self.protocol.dialects().collect();
+        let dialect_names: Vec<&str> = dialects.iter().map(|dialect| dialect.name()).collect();
+
+        files.insert(
+            self.root_module_file_path("Cargo.toml"),
+            templates::workspace::workspace_manifest(&WorkspaceSpec::new(
+                &dialect_names,
+                &self.params,
+            )),
+        );
+
+        for dialect in dialects {
+            let dialect_spec = DialectModuleSpec::new(dialect, &self.params);
+            let dependencies = self.dialect_dependencies(&dialect_spec);
+            let crate_dir = self.crate_dir(dialect.name());
+            files.insert(
+                crate_dir.join("Cargo.toml"),
+                templates::workspace::dialect_crate_manifest(&DialectCrateSpec::new(
+                    dialect.name(),
+                    &dependencies,
+                    &self.params,
+                )),
+            );
+        }
+
+        for (path, content) in modules {
+            let Some((dialect_name, module_path)) = dialect_names.iter().find_map(|&name| {
+                path.strip_prefix(self.dialect_dir(name))
+                    .ok()
+                    .map(|module_path| (name, module_path.to_path_buf()))
+            }) else {
+                continue;
+            };
+
+            let src_dir = self.crate_dir(dialect_name).join("src");
+            let (path, content) = if module_path.as_path() == Path::new("mod.rs") {
+                (
+                    src_dir.join("lib.rs"),
+                    templates::workspace::dialect_crate_root(content),
+                )
+            } else {
+                (src_dir.join(module_path), content)
+            };
+            files.insert(path, prettyplease::unparse(&content));
+        }
+
+        files
+    }
+
+    /// Dialects which define messages and enums inherited by dialect.
+    fn dialect_dependencies<'a>(&'a self, dialect_spec: &'a DialectModuleSpec) -> Vec<&'a str> {
+        let mut dependencies = BTreeSet::new();
+
+        for message in dialect_spec.messages() {
+            match message.defined_in() {
+                Some(dialect_name) if dialect_name != dialect_spec.name() => {
+                    dependencies.insert(dialect_name.as_str());
+                }
+                _ => {}
+            }
+        }
+        for mav_enum in dialect_spec.enums() {
+            if let Some(dialect) = self.enum_inherited_from(mav_enum, dialect_spec.name()) {
+                dependencies.insert(dialect.name());
+            }
+        }
+
+        dependencies.into_iter().collect()
+    }
+
+    /// Writes files which content has changed and removes previously generated files which are no longer produced.
+    ///
+    /// Files within `preserved` paths are kept. Only files listed in [`Self::generated_files_path`] during previous
+    /// generation are removed, other files within output path are never touched.
+    fn write_files(&self, files: Files, preserved: &[PathBuf]) -> RustGenResult<()> {
+        // Output of versions which didn't list generated files can't be told apart from foreign files
+        if !self.generated_files_path().exists()
+            && self.path.is_dir()
+            && read_dir(&self.path)?.next().is_some()
+        {
+            log::warn!(
+                "Output path has no listing of generated files, stale files will not be removed: {:?}",
+                self.path
+            );
+        }
+
+        for (path, content) in &files {
+            if write_if_changed(path, content)? {
+                log::trace!("Updated: {path:?}");
+            }
+        }
+
+        let previous = self.previously_generated_files();
+        let mut generated: BTreeSet<PathBuf> = files.into_keys().collect();
+        generated.extend(
+            previous
+                .iter()
+                .filter(|path| preserved.iter().any(|p| path.starts_with(p)))
+                .cloned(),
+        );
+        for path in previous.difference(&generated) {
+            self.remove_stale_file(path)?;
+        }
+
+        let listing: String = generated
+            .iter()
+            .filter_map(|path| path.strip_prefix(&self.path).ok())
+            .map(|path| {
+                let components: Vec<_> = path
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect();
+                format!("{}\n", components.join("/"))
+            })
+            .collect();
+        write_if_changed(&self.generated_files_path(), &listing)?;
+
+        Ok(())
+    }
+
+    /// Files listed in [`Self::generated_files_path`] by the previous generation.
+    fn previously_generated_files(&self) -> BTreeSet<PathBuf> {
+        read_to_string(self.generated_files_path())
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| !line.is_empty())
+            // Listing may be edited by hand, it should never point outside output path
+            .map(Path::new)
+            .filter(|path| {
+                path.components()
+                    .all(|component| matches!(component, Component::Normal(_)))
+            })
+            .map(|path| self.path.join(path))
+            .collect()
+    }
+
+    /// Removes a stale generated file and parent directories left empty.
+    fn remove_stale_file(&self, path: &Path) -> RustGenResult<()> {
+        if path.is_file() {
+            log::trace!("Removed stale file: {path:?}");
+            remove_file(path)?;
+        }
+
+        let mut dir = path.parent();
+        while let Some(current) = dir.filter(|dir| *dir != self.path && dir.starts_with(&self.path))
+        {
+            if !current.is_dir() || read_dir(current)?.next().is_some() {
+                break;
+            }
+            remove_dir(current)?;
+            dir = current.parent();
+        }
 
         Ok(())
     }
 
+    /// File which lists generated files relative to output path, one per line.
+    fn generated_files_path(&self) -> PathBuf {
+        self.path.join(".generated")
+    }
+
     #[cfg(feature = "fingerprints")]
     fn fingerprint_path(&self) -> PathBuf {
         self.path.join(".fingerprint")
     }
 
+    /// Path to generated files of a dialect which can be kept when dialect is not re-generated.
+    ///
+    /// Returns [`None`] for [`OutputLayout::SingleFile`] since all dialects share the same file.
+    fn dialect_output_path(&self, dialect_name: &str) -> Option<PathBuf> {
+        match self.params.output_layout {
+            OutputLayout::Modules => Some(self.dialect_dir(dialect_name)),
+            OutputLayout::SingleFile => None,
+            OutputLayout::FilePerDialect => {
+                Some(self.dialect_dir(dialect_name).with_extension("rs"))
+            }
+            OutputLayout::Workspace => Some(self.crate_dir(dialect_name).join("src")),
+        }
+    }
+
     fn root_module_file_path(&self, filename: &str) -> PathBuf {
         self.path.join(filename)
     }
 
+    fn crate_dir(&self, dialect_name: &str) -> PathBuf {
+        self.path
+            .join(conventions::dialect_crate_dir_name(dialect_name))
+    }
+
     fn dialects_dir(&self) -> PathBuf {
         self.path.join("dialects")
     }
@@ -329,3 +773,60 @@ impl Generator {
         None
     }
 }
+
+/// Writes `content` to a file at `path` unless it already has the same content.
+///
+/// Keeps modification time of unchanged files, so Cargo does not rebuild crates depending on them. Returns `true` if
+/// file was written.
+fn write_if_changed(path: &Path, content: &str) -> RustGenResult<bool> {
+    if read(path).is_ok_and(|existing| existing == content.as_bytes()) {
+        return Ok(false);
+    }
+
+    if let Some(dir) = path.parent() {
+        create_dir_all(dir)?;
+    }
+    let mut file = File::create(path)?;
+    file.write_all(content.as_bytes())?;
+
+    Ok(true)
+}
+
+/// Removes module at `path` and its file-backed submodules from `modules` and merges them into a single module.
+///
+/// Submodules declared as `mod name;` are replaced by `mod name { .. }` with the contents of `name.rs` or
+/// `name/mod.rs`, so paths of all inner items remain the same.
+fn inline_modules(path: &Path, modules: &mut Modules) -> syn::File {
+    let mut module = modules.remove(path).unwrap();
+    let dir = match path.file_name() {
+        Some(file_name) if file_name == "mod.rs" => path.parent().unwrap().to_path_buf(),
+        _ => path.with_extension(""),
+    };
+
+    for item in module.items.iter_mut() {
+        let syn::Item::Mod(item_mod) = item else {
+            continue;
+        };
+        if item_mod.content.is_some() {
+            continue;
+        }
+
+        let name = item_mod.ident.unraw().to_string();
+        let submodule_path = [
+            dir.join(format!("{name}.rs")),
+            dir.join(&name).join("mod.rs"),
+        ]
+        .into_iter()
+        .find(|path| modules.contains_key(path));
+        let Some(submodule_path) = submodule_path else {
+            continue;
+        };
+
+        let submodule = inline_modules(&submodule_path, modules);
+        item_mod.attrs.extend(submodule.attrs);
+        item_mod.content = Some((Default::default(), submodule.items));
+        item_mod.semi = None;
+    }
+
+    module
+}
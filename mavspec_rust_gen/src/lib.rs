@@ -93,10 +93,24 @@
 //! The last two cases of handling inconvenient names are not something of high aesthetic value but in our defence we
 //! must say that all approaches we've considered looked equally ugly.
 //!
+//! # Incremental generation
+//!
+//! Generator writes only files with changed content, so Cargo rebuilds only what depends on changed code. Generated
+//! files are listed in `.generated` file within output directory. Files listed by the previous generation that are no
+//! longer generated are removed, other files are never touched. Output of versions prior to `.generated` listing should
+//! be cleaned once after upgrade, since its stale files can't be told apart from foreign ones.
+//!
+//! # Verification
+//!
+//! [`BuildHelper::verify`] generates code in memory and compares it with existing files in output directory. Missing,
+//! extra, and differing files are reported as [`error::RustGenError::Verification`] with [`VerificationReport`] that
+//! contains a unified diff.
+//!
 //! # Fingerprints
 //!
-//! MAVInspect may skip code re-generation if dialects haven't changed. It uses 64-bit CRC fingerprint to monitor
-//! changes. Set `fingerprints` feature flag to enable this behavior.
+//! MAVInspect may skip code re-generation if dialects haven't changed. It uses 64-bit CRC fingerprint of each
+//! dialect to monitor changes, which also takes into account dialects it depends on and generator parameters.
+//! Fingerprints are stable across platforms and Rust versions. Set `fingerprints` feature flag to enable this behavior.
 //!
 //! This feature is useful for reducing build time during development and CI runs. Make sure that your releases are
 //! clean and do not depend on fingerprints.
@@ -115,11 +129,18 @@
 
 mod build_helper;
 pub use build_helper::{BuildHelper, BuildHelperBuilder};
+pub use generator::{OutputLayout, SerdeEnumRepr, WipPolicy};
+pub use type_attributes::{TypeAttributes, TypeKind};
+pub use verify::VerificationReport;
 
 pub mod utils;
 
 pub(crate) mod conventions;
 pub mod error;
 pub(crate) mod generator;
+pub(crate) mod multipliers;
 pub(crate) mod specs;
 pub(crate) mod templates;
+pub mod test_vectors;
+pub(crate) mod type_attributes;
+pub(crate) mod verify;
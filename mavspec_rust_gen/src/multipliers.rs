@@ -0,0 +1,243 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::path::Path;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::Serialize;
+
+use crate::error::RustGenResult;
+
+/// Positive decimal multiplier (i.e. `1E-2`) represented as a fraction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Multiplier {
+    numerator: u64,
+    denominator: u64,
+}
+
+/// XML `multiplier` attribute of a message field.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FieldMultiplier {
+    /// Name of the dialect where message is defined.
+    pub dialect: String,
+    /// Canonical message name.
+    pub message: String,
+    /// Field name.
+    pub field: String,
+    /// Raw value multiplied by this value gives value in field units.
+    pub multiplier: Multiplier,
+}
+
+impl Multiplier {
+    /// Parses decimal number in plain (`0.01`) or scientific (`1E-2`) notation.
+    ///
+    /// Returns `None` for zero, negative values, and values which do not fit into `u64` fraction.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let value = value.strip_prefix('+').unwrap_or(value);
+        let (mantissa, exponent) = match value.find(['e', 'E']) {
+            Some(idx) => (&value[..idx], value[idx + 1..].parse::<i32>().ok()?),
+            None => (value, 0),
+        };
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if integer.is_empty() && fraction.is_empty()
+            || !integer
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let digits = format!("{integer}{fraction}");
+        let digits = digits.trim_start_matches('0');
+        let mantissa: u64 = if digits.is_empty() {
+            0
+        } else {
+            digits.parse().ok()?
+        };
+        if mantissa == 0 {
+            return None;
+        }
+
+        let exponent = exponent.checked_sub(i32::try_from(fraction.len()).ok()?)?;
+        let power = 10u64.checked_pow(exponent.unsigned_abs())?;
+        let (numerator, denominator) = if exponent >= 0 {
+            (mantissa.checked_mul(power)?, 1)
+        } else {
+            (mantissa, power)
+        };
+
+        let gcd = gcd(numerator, denominator);
+        Some(Self {
+            numerator: numerator / gcd,
+            denominator: denominator / gcd,
+        })
+    }
+
+    /// Numerator of multiplier fraction.
+    pub(crate) fn numerator(&self) -> u64 {
+        self.numerator
+    }
+
+    /// Denominator of multiplier fraction.
+    pub(crate) fn denominator(&self) -> u64 {
+        self.denominator
+    }
+}
+
+/// Finds multiplier of a message field defined in dialect `dialect`.
+pub(crate) fn find_multiplier<'a>(
+    multipliers: &'a [FieldMultiplier],
+    dialect: &str,
+    message: &str,
+    field: &str,
+) -> Option<&'a Multiplier> {
+    multipliers
+        .iter()
+        .find(|m| m.dialect == dialect && m.message == message && m.field == field)
+        .map(|m| &m.multiplier)
+}
+
+/// Loads XML `multiplier` attributes of message fields from MAVLink XML definitions.
+///
+/// MAVInspect does not expose `multiplier`, so definitions are scanned directly. Dialect names are taken from file
+/// names, the same way as MAVInspect does.
+pub(crate) fn load_field_multipliers<P: AsRef<Path>>(
+    files: &[P],
+) -> RustGenResult<Vec<FieldMultiplier>> {
+    let mut multipliers = Vec::new();
+
+    for file in files {
+        let file = file.as_ref();
+        let dialect = file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut reader = Reader::from_file(file).map_err(invalid_data)?;
+        let mut buf = Vec::new();
+        let mut message_name: Option<String> = None;
+
+        loop {
+            match reader.read_event_into(&mut buf).map_err(invalid_data)? {
+                Event::Start(tag) if tag.name().as_ref() == b"message" => {
+                    message_name = attribute(&tag, "name")?;
+                }
+                Event::End(tag) if tag.name().as_ref() == b"message" => {
+                    message_name = None;
+                }
+                Event::Start(tag) | Event::Empty(tag) if tag.name().as_ref() == b"field" => {
+                    if let (Some(message_name), Some(field_name), Some(value)) = (
+                        &message_name,
+                        attribute(&tag, "name")?,
+                        attribute(&tag, "multiplier")?,
+                    ) {
+                        let multiplier = Multiplier::parse(&value).ok_or_else(|| {
+                            IoError::new(
+                                ErrorKind::InvalidData,
+                                format!(
+                                    "invalid multiplier `{value}` of `{message_name}.{field_name}` in {}",
+                                    file.display()
+                                ),
+                            )
+                        })?;
+                        multipliers.push(FieldMultiplier {
+                            dialect: dialect.clone(),
+                            message: message_name.clone(),
+                            field: field_name,
+                            multiplier,
+                        });
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+    }
+
+    Ok(multipliers)
+}
+
+/// Greatest common divisor.
+pub(crate) fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn attribute(tag: &BytesStart, name: &str) -> RustGenResult<Option<String>> {
+    match tag.try_get_attribute(name).map_err(invalid_data)? {
+        Some(attr) => Ok(Some(
+            attr.unescape_value().map_err(invalid_data)?.into_owned(),
+        )),
+        None => Ok(None),
+    }
+}
+
+fn invalid_data(err: impl Into<quick_xml::Error>) -> IoError {
+    IoError::new(ErrorKind::InvalidData, err.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+
+    #[test]
+    fn multipliers_are_parsed() {
+        let fraction =
+            |value: &str| Multiplier::parse(value).map(|m| (m.numerator(), m.denominator()));
+
+        assert_eq!(fraction("1E-2"), Some((1, 100)));
+        assert_eq!(fraction("1e-7"), Some((1, 10_000_000)));
+        assert_eq!(fraction("2.5E1"), Some((25, 1)));
+        assert_eq!(fraction("0.125"), Some((1, 8)));
+        assert_eq!(fraction("+100"), Some((100, 1)));
+        assert_eq!(fraction("1E3"), Some((1_000, 1)));
+
+        assert_eq!(fraction("0"), None);
+        assert_eq!(fraction("-1E-2"), None);
+        assert_eq!(fraction("E2"), None);
+        assert_eq!(fraction("1E40"), None);
+        assert_eq!(fraction("abc"), None);
+    }
+
+    #[test]
+    fn multipliers_are_loaded() {
+        let root = Path::new("../tmp/mavlink/multipliers");
+        create_dir_all(root).unwrap();
+        let path = root.join("scaled.xml");
+        write(
+            &path,
+            r#"<?xml version="1.0"?>
+<mavlink>
+    <messages>
+        <message id="1" name="SCALED">
+            <description>Message with multiplier.</description>
+            <field type="int16_t" name="plain" units="cm">Plain.</field>
+            <field type="int16_t" name="scaled" units="cm" multiplier="1E-2">Scaled.</field>
+        </message>
+    </messages>
+</mavlink>
+"#,
+        )
+        .unwrap();
+
+        let multipliers = load_field_multipliers(&[&path]).unwrap();
+        assert_eq!(
+            multipliers,
+            vec![FieldMultiplier {
+                dialect: "scaled".to_string(),
+                message: "SCALED".to_string(),
+                field: "scaled".to_string(),
+                multiplier: Multiplier::parse("0.01").unwrap(),
+            }]
+        );
+        assert!(find_multiplier(&multipliers, "scaled", "SCALED", "scaled").is_some());
+        assert!(find_multiplier(&multipliers, "other", "SCALED", "scaled").is_none());
+        assert!(find_multiplier(&multipliers, "scaled", "SCALED", "plain").is_none());
+
+        remove_dir_all(root).unwrap();
+    }
+}
@@ -1,10 +1,15 @@
-use mavinspect::protocol::{Enum, EnumEntry, MavType};
+use mavinspect::protocol::{
+    Enum, EnumEntry, EnumEntryMavCmdFlags, EnumEntryMavCmdParam, MavType,
+};
 use serde::Serialize;
 
 use crate::conventions::split_description;
-use crate::generator::GeneratorParams;
+use crate::generator::{GeneratorParams, WipPolicy};
 use crate::specs::dialects::dialect::DialectModuleSpec;
-use crate::specs::Spec;
+use crate::specs::{deprecation_note, Spec};
+
+/// Name of the MAVLink enum which contains [commands](https://mavlink.io/en/messages/common.html#mav_commands).
+pub(crate) const MAV_CMD_ENUM_NAME: &str = "MAV_CMD";
 
 /// Input for enums root module template.
 pub(crate) struct EnumsRootModuleSpec<'a> {
@@ -47,6 +52,8 @@ pub(crate) struct EnumImplModuleSpec<'a> {
     inferred_type: MavType,
     entries: Vec<EnumEntrySpec<'a>>,
     is_bitmask: bool,
+    is_command: bool,
+    deprecated: Option<String>,
     params: &'a GeneratorParams,
 }
 
@@ -58,10 +65,14 @@ impl<'a> Spec for EnumImplModuleSpec<'a> {
 
 impl<'a> EnumImplModuleSpec<'a> {
     pub(crate) fn new(mav_enum: &'a Enum, params: &'a GeneratorParams) -> Self {
-        let mut entries: Vec<EnumEntrySpec> = mav_enum
-            .entries()
-            .iter()
-            .map(EnumEntrySpec::from_enum_entry)
+        let mut entries: Vec<&EnumEntry> = mav_enum.entries().iter().collect();
+        // Enums with work-in-progress entries only are not generated at all under this policy
+        if params.wip == WipPolicy::Exclude {
+            entries.retain(|entry| !entry.wip());
+        }
+        let mut entries: Vec<EnumEntrySpec> = entries
+            .into_iter()
+            .map(|entry| EnumEntrySpec::from_enum_entry(entry, params))
             .collect();
         entries.sort_by_key(|entry| entry.value);
 
@@ -71,6 +82,8 @@ impl<'a> EnumImplModuleSpec<'a> {
             inferred_type: mav_enum.inferred_type(),
             entries,
             is_bitmask: mav_enum.bitmask(),
+            is_command: mav_enum.name() == MAV_CMD_ENUM_NAME,
+            deprecated: deprecation_note(mav_enum.deprecated(), false, params),
             params,
         }
     }
@@ -83,17 +96,27 @@ impl<'a> EnumImplModuleSpec<'a> {
         self.is_bitmask
     }
 
+    /// Whether this enum is `MAV_CMD` and its entries are commands.
+    pub(crate) fn is_command(&self) -> bool {
+        self.is_command
+    }
+
     pub(crate) fn description(&self) -> &[String] {
         self.description.as_slice()
     }
 
-    pub(crate) fn entries(&self) -> &[EnumEntrySpec] {
+    pub(crate) fn entries(&self) -> &[EnumEntrySpec<'_>] {
         self.entries.as_slice()
     }
 
     pub(crate) fn inferred_type(&self) -> &MavType {
         &self.inferred_type
     }
+
+    /// Note for `#[deprecated]` attribute (if enum is deprecated).
+    pub(crate) fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
 }
 
 /// Enum entry representation for template.
@@ -105,15 +128,27 @@ pub(crate) struct EnumEntrySpec<'a> {
     name: &'a str,
     name_stripped: String,
     description: Vec<String>,
+    cmd_flags: Option<EnumEntryMavCmdFlags>,
+    params: Vec<EnumEntryMavCmdParam>,
+    deprecated: Option<String>,
 }
 
 impl<'a> EnumEntrySpec<'a> {
-    pub(crate) fn from_enum_entry(entry: &'a EnumEntry) -> Self {
+    pub(crate) fn from_enum_entry(
+        entry: &'a EnumEntry,
+        generator_params: &GeneratorParams,
+    ) -> Self {
+        let mut params = entry.params().to_vec();
+        params.sort_by_key(|param| param.index());
+
         Self {
             value: entry.value(),
             name: entry.name(),
             name_stripped: entry.name_stripped().to_string(),
             description: split_description(entry.description()),
+            cmd_flags: entry.cmd_flags().cloned(),
+            params,
+            deprecated: deprecation_note(entry.deprecated(), entry.wip(), generator_params),
         }
     }
 
@@ -136,6 +171,21 @@ impl<'a> EnumEntrySpec<'a> {
     pub(crate) fn description(&self) -> &[String] {
         self.description.as_slice()
     }
+
+    /// `MAV_CMD` flags (only for commands).
+    pub(crate) fn cmd_flags(&self) -> Option<&EnumEntryMavCmdFlags> {
+        self.cmd_flags.as_ref()
+    }
+
+    /// `MAV_CMD` params sorted by index (only for commands).
+    pub(crate) fn params(&self) -> &[EnumEntryMavCmdParam] {
+        self.params.as_slice()
+    }
+
+    /// Note for `#[deprecated]` attribute (if entry is deprecated or work in progress).
+    pub(crate) fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
 }
 
 pub(crate) struct EnumInheritedModuleSpec<'a> {
@@ -1,11 +1,15 @@
-use mavinspect::protocol::{MavType, Message, MessageField};
+use mavinspect::protocol::{
+    MavType, Message, MessageField, MessageFieldInvalidValue, Units, Value,
+};
 use quote::format_ident;
 use serde::Serialize;
 
-use crate::conventions::{message_struct_name, split_description};
+use crate::conventions::{message_struct_name, split_description, units_type_name};
 use crate::generator::GeneratorParams;
+use crate::multipliers::find_multiplier;
+use crate::specs::dialects::dialect::scales::FieldScale;
 use crate::specs::dialects::dialect::DialectModuleSpec;
-use crate::specs::Spec;
+use crate::specs::{deprecation_note, Spec};
 
 /// Input for `messages` root module template.
 #[derive(Clone, Debug, Serialize)]
@@ -57,6 +61,7 @@ pub struct MessageImplModuleSpec<'a> {
     extension_fields: Vec<FieldSpec>,
     has_extension_fields: bool,
     crc_extra: u8,
+    deprecated: Option<String>,
     params: &'a GeneratorParams,
 }
 
@@ -76,28 +81,38 @@ impl<'a> MessageImplModuleSpec<'a> {
             description: split_description(message.description()),
             fields: FieldSpec::from_mavinspect_fields(
                 message.fields().iter().collect::<Vec<_>>().as_slice(),
+                message,
                 dialect_spec,
             ),
             // `MAVLink 1`
             is_v1_compatible: message.is_v1_compatible(),
             fields_v1: FieldSpec::from_mavinspect_fields(
                 message.fields_v1().as_slice(),
+                message,
                 dialect_spec,
             ),
             payload_v1_size: message.size_v1(),
             // `MAVLink 2`
             fields_v2: FieldSpec::from_mavinspect_fields(
                 message.fields_v2().as_slice(),
+                message,
                 dialect_spec,
             ),
             payload_v2_size: message.size_v2(),
             extension_fields: FieldSpec::from_mavinspect_fields(
                 message.extension_fields().as_slice(),
+                message,
                 dialect_spec,
             ),
             has_extension_fields: message.has_extension_fields(),
             // CRC
             crc_extra: message.crc_extra(),
+            // Deprecation and work-in-progress status
+            deprecated: deprecation_note(
+                message.deprecated(),
+                message.wip(),
+                dialect_spec.params(),
+            ),
             // Generator params
             params: dialect_spec.params(),
         }
@@ -131,9 +146,29 @@ impl<'a> MessageImplModuleSpec<'a> {
         self.is_v1_compatible
     }
 
+    pub(crate) fn payload_v1_size(&self) -> usize {
+        self.payload_v1_size
+    }
+
+    pub(crate) fn payload_v2_size(&self) -> usize {
+        self.payload_v2_size
+    }
+
+    /// Whether message contains floating point fields (which makes `Eq` and `Hash` impossible).
+    pub(crate) fn has_floats(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|field| matches!(field.r#type().base_type(), MavType::Float | MavType::Double))
+    }
+
     pub(crate) fn crc_extra(&self) -> u8 {
         self.crc_extra
     }
+
+    /// Note for `#[deprecated]` attribute (if message is deprecated or work in progress).
+    pub(crate) fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
@@ -150,29 +185,64 @@ pub struct FieldSpec {
     requires_enum_casting: bool,
     requires_serde_arrays: bool,
     is_extension: bool,
+    units: Option<Units>,
+    is_quantity: bool,
+    is_instance: bool,
+    scale: Option<FieldScale>,
+    invalid: Option<MessageFieldInvalidValue>,
+    default: Option<Value>,
 }
 
 impl FieldSpec {
-    fn from_mavinspect_field(value: &MessageField, dialect_spec: &DialectModuleSpec) -> FieldSpec {
+    fn from_mavinspect_field(
+        value: &MessageField,
+        message: &Message,
+        dialect_spec: &DialectModuleSpec,
+    ) -> FieldSpec {
         let mut spec = FieldSpec {
             name: value.name().into(),
             description: split_description(value.description()),
             r#type: value.r#type().clone(),
             is_array: value.r#type().is_array(),
             is_extension: value.extension(),
+            // Only unsigned integers up to 32 bits and strings can be represented as stream instances
+            is_instance: value.instance()
+                && match value.r#type() {
+                    MavType::UInt8 | MavType::UInt16 | MavType::UInt32 => true,
+                    MavType::Array(_, _) => matches!(value.r#type().base_type(), MavType::Char),
+                    _ => false,
+                },
+            units: value.units().cloned(),
+            invalid: value.invalid().cloned(),
             ..Default::default()
         };
 
         if let MavType::Array(_, len) = value.r#type() {
             spec.array_length = *len;
 
-            if *len > 32 && dialect_spec.params().serde {
+            // Strings are serialized by `MavString` itself
+            if *len > 32 && dialect_spec.params().serde && !spec.is_string() {
                 spec.requires_serde_arrays = true;
             }
         }
 
+        // Explicit XML default has priority over invalid sentinel
+        spec.default = value.default().cloned();
+
         if let Some(enum_name) = value.r#enum() {
             if let Some(field_enum) = dialect_spec.get_enum_by_name(enum_name) {
+                if let (None, Some(MessageFieldInvalidValue::EnumEntryValue(entry_name))) =
+                    (&spec.default, value.invalid())
+                {
+                    spec.default = field_enum
+                        .entries()
+                        .iter()
+                        .find(|entry| entry.name() == entry_name)
+                        .and_then(|entry| {
+                            Value::parse(entry.value().to_string().as_str(), value.r#type()).ok()
+                        });
+                }
+
                 spec.is_enum = true;
                 spec.enum_name = field_enum.name().into();
                 spec.enum_type = field_enum.inferred_type();
@@ -183,16 +253,44 @@ impl FieldSpec {
             }
         }
 
+        if let (
+            None,
+            Some(
+                MessageFieldInvalidValue::Value(sentinel)
+                | MessageFieldInvalidValue::AllItems(sentinel)
+                | MessageFieldInvalidValue::FirstItem(sentinel),
+            ),
+        ) = (&spec.default, value.invalid())
+        {
+            spec.default = Some(sentinel.clone());
+        }
+
+        if !spec.is_enum && dialect_spec.params().scaled_accessors {
+            let multiplier = find_multiplier(
+                &dialect_spec.params().field_multipliers,
+                message.defined_in().map_or(dialect_spec.name(), |name| name.as_str()),
+                message.name(),
+                value.name(),
+            );
+            spec.scale = FieldScale::new(value.r#type(), value.units(), multiplier);
+        }
+
+        spec.is_quantity = dialect_spec.params().typed_units
+            && spec.units.as_ref().and_then(units_type_name).is_some()
+            && !spec.is_enum
+            && !matches!(value.r#type().base_type(), MavType::Char);
+
         spec
     }
 
     fn from_mavinspect_fields(
         fields: &[&MessageField],
+        message: &Message,
         dialect_spec: &DialectModuleSpec,
     ) -> Vec<FieldSpec> {
         fields
             .iter()
-            .map(|fld| FieldSpec::from_mavinspect_field(fld, dialect_spec))
+            .map(|fld| FieldSpec::from_mavinspect_field(fld, message, dialect_spec))
             .collect()
     }
 
@@ -232,6 +330,11 @@ impl FieldSpec {
         self.array_length
     }
 
+    /// Whether field is a `char[N]` string represented by `MavString<N>`.
+    pub(crate) fn is_string(&self) -> bool {
+        self.is_array && !self.is_enum && matches!(self.r#type.base_type(), MavType::Char)
+    }
+
     pub(crate) fn requires_enum_casting(&self) -> bool {
         self.requires_enum_casting
     }
@@ -243,6 +346,55 @@ impl FieldSpec {
     pub(crate) fn is_extension(&self) -> bool {
         self.is_extension
     }
+
+    /// Whether field is marked with `instance="true"` and identifies a message stream.
+    pub(crate) fn is_instance(&self) -> bool {
+        self.is_instance
+    }
+
+    pub(crate) fn units(&self) -> Option<&Units> {
+        self.units.as_ref()
+    }
+
+    /// Whether field should be represented as a `Quantity` tagged with units.
+    pub(crate) fn is_quantity(&self) -> bool {
+        self.is_quantity
+    }
+
+    /// Scale for fixed-point fields which require scaled accessors.
+    pub(crate) fn scale(&self) -> Option<&FieldScale> {
+        self.scale.as_ref()
+    }
+
+    /// Sentinel which marks field value as invalid.
+    pub(crate) fn invalid(&self) -> Option<&MessageFieldInvalidValue> {
+        self.invalid.as_ref()
+    }
+
+    /// Default value of a field.
+    ///
+    /// Defined by `default` attribute of a field in XML definition. If it is absent, invalid sentinel is used, so
+    /// fields are "not provided" by default.
+    pub(crate) fn default(&self) -> Option<&Value> {
+        self.default.as_ref()
+    }
+
+    /// Whether field requires `Option` accessors which encode `None` as invalid sentinel.
+    ///
+    /// Only fields represented by raw numeric types are supported, sentinels of enum and bitmask fields are exposed
+    /// only as metadata.
+    pub(crate) fn has_invalid_accessors(&self) -> bool {
+        !self.is_enum
+            && !matches!(self.r#type.base_type(), MavType::Char)
+            && matches!(
+                self.invalid,
+                Some(
+                    MessageFieldInvalidValue::Value(_)
+                        | MessageFieldInvalidValue::AllItems(_)
+                        | MessageFieldInvalidValue::FirstItem(_)
+                )
+            )
+    }
 }
 
 /// Input for modules containing references to already implemented messages.
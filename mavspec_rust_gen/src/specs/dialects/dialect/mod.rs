@@ -3,3 +3,4 @@ pub(crate) use root::DialectModuleSpec;
 
 pub(crate) mod enums;
 pub(crate) mod messages;
+pub(crate) mod scales;
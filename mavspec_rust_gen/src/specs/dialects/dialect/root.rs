@@ -1,7 +1,7 @@
 use mavinspect::protocol::{Dialect, DialectId, DialectVersion, Enum, Message};
 use serde::Serialize;
 
-use crate::generator::GeneratorParams;
+use crate::generator::{GeneratorParams, WipPolicy};
 use crate::specs::Spec;
 
 /// Specification for dialect module template.
@@ -23,12 +23,26 @@ impl<'a> Spec for DialectModuleSpec<'a> {
 
 impl<'a> DialectModuleSpec<'a> {
     pub(crate) fn new(dialect: &'a Dialect, params: &'a GeneratorParams) -> Self {
+        // Entities are sorted to make generated code independent of parsing order
+        let mut messages: Vec<&Message> = dialect
+            .messages()
+            .into_iter()
+            .filter(|msg| params.wip != WipPolicy::Exclude || !msg.wip())
+            .collect();
+        messages.sort_by_key(|msg| msg.id());
+        let mut enums: Vec<&Enum> = dialect
+            .enums()
+            .into_iter()
+            .filter(|mav_enum| !params.wip.excludes_enum(mav_enum))
+            .collect();
+        enums.sort_by_key(|mav_enum| mav_enum.name());
+
         Self {
             name: dialect.name(),
             version: dialect.version(),
             dialect_id: dialect.dialect(),
-            messages: Vec::from_iter(dialect.messages()),
-            enums: Vec::from_iter(dialect.enums()),
+            messages,
+            enums,
             params,
         }
     }
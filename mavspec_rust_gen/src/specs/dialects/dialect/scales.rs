@@ -0,0 +1,497 @@
+use mavinspect::protocol::{MavType, Units};
+use serde::Serialize;
+
+use crate::multipliers::{gcd, Multiplier};
+
+/// Conversion of a fixed-point MAVLink field into human-friendly units.
+///
+/// Defines scaled accessors generated for message fields with known units.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) enum FieldScale {
+    /// Raw value multiplied by `multiplier` and divided by `divisor` gives value in `units`.
+    Fixed {
+        suffix: &'static str,
+        units: &'static str,
+        multiplier: u64,
+        divisor: u64,
+    },
+    /// Raw value represents time interval which can be converted to `Duration`.
+    Duration { units: DurationUnits },
+}
+
+/// Time units which can be represented as `core::time::Duration`.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub(crate) enum DurationUnits {
+    Seconds,
+    DeciSeconds,
+    CentiSeconds,
+    MilliSeconds,
+    MicroSeconds,
+    NanoSeconds,
+}
+
+impl FieldScale {
+    /// Defines scale for a field of specified type, units, and XML `multiplier`.
+    ///
+    /// Scaled accessors are generated only for non-array integer fields with units. Fields which are already
+    /// represented in target units (i.e. `m`) get them only if they have a multiplier. Fields with units that do not
+    /// have a natural target units (i.e. `bytes`) are ignored.
+    ///
+    /// Multiplier is applied on top of units, so `cm` with multiplier `1E-2` gives `raw * 1e-4` metres. Time
+    /// intervals with multiplier get accessors in seconds instead of `Duration`.
+    pub(crate) fn new(
+        r#type: &MavType,
+        units: Option<&Units>,
+        multiplier: Option<&Multiplier>,
+    ) -> Option<Self> {
+        let units = units?;
+
+        if !Self::is_fixed_point(r#type) {
+            return None;
+        }
+
+        let (suffix, target, unit_multiplier, unit_divisor) = match multiplier {
+            None => {
+                if let Some(units) = DurationUnits::new(units) {
+                    return if Self::is_unsigned(r#type) {
+                        Some(Self::Duration { units })
+                    } else {
+                        None
+                    };
+                }
+                Self::fixed_units(units)?
+            }
+            Some(_) => Self::fixed_units(units).or_else(|| Self::target_units(units))?,
+        };
+
+        let (multiplier, divisor) = match multiplier {
+            Some(multiplier) => reduce(
+                unit_multiplier.checked_mul(multiplier.numerator())?,
+                unit_divisor.checked_mul(multiplier.denominator())?,
+            ),
+            None => (unit_multiplier, unit_divisor),
+        };
+
+        Some(Self::Fixed {
+            suffix,
+            units: target,
+            multiplier,
+            divisor,
+        })
+    }
+
+    /// Accessor suffix, target units, multiplier, and divisor for fixed-point units.
+    fn fixed_units(units: &Units) -> Option<(&'static str, &'static str, u64, u64)> {
+        Some(match units {
+            // Angles
+            Units::DegreesE7 => ("deg", "deg", 1, 10_000_000),
+            Units::DegreesE5 => ("deg", "deg", 1, 100_000),
+            Units::CentiDegrees => ("deg", "deg", 1, 100),
+            Units::HalfDegrees => ("deg", "deg", 2, 1),
+            Units::CentiDegreesPerSecond => ("deg_s", "deg/s", 1, 100),
+            Units::MilliRadiansPerSecond => ("rad_s", "rad/s", 1, 1_000),
+            // Distance
+            Units::KiloMetres => ("m", "m", 1_000, 1),
+            Units::DecaMetres => ("m", "m", 10, 1),
+            Units::DeciMetres => ("m", "m", 1, 10),
+            Units::CentiMetres => ("m", "m", 1, 100),
+            Units::MilliMetres => ("m", "m", 1, 1_000),
+            Units::SquareCentiMetres => ("m2", "m^2", 1, 10_000),
+            // Speed
+            Units::FiveMetresPerSecond => ("m_s", "m/s", 1, 5),
+            Units::DeciMetresPerSecond => ("m_s", "m/s", 1, 10),
+            Units::CentiMetresPerSecond => ("m_s", "m/s", 1, 100),
+            Units::MilliMetresPerSecond => ("m_s", "m/s", 1, 1_000),
+            // Temperature
+            Units::CentiDegreeCelsius => ("deg_c", "degC", 1, 100),
+            // Electricity
+            Units::CentiVolt => ("v", "V", 1, 100),
+            Units::MilliVolt => ("v", "V", 1, 1_000),
+            Units::CentiAmpere => ("a", "A", 1, 100),
+            Units::MilliAmpere => ("a", "A", 1, 1_000),
+            Units::MilliAmpereHour => ("ah", "Ah", 1, 1_000),
+            // Magnetism
+            Units::MilliTesla => ("t", "T", 1, 1_000),
+            Units::MilliGauss => ("gauss", "gauss", 1, 1_000),
+            // Energy
+            Units::HectoJoule => ("j", "J", 100, 1),
+            // Pressure
+            Units::HectoPascal => ("pa", "Pa", 100, 1),
+            Units::KiloPascal => ("pa", "Pa", 1_000, 1),
+            Units::MilliBar => ("pa", "Pa", 100, 1),
+            // Ratio
+            Units::DeciPercent => ("percent", "%", 1, 10),
+            Units::CentiPercent => ("percent", "%", 1, 100),
+            // Digital
+            Units::DeciPixels => ("pix", "pix", 1, 10),
+            _ => return None,
+        })
+    }
+
+    /// Accessor suffix, target units, multiplier, and divisor for units which are scaled only by XML multiplier.
+    fn target_units(units: &Units) -> Option<(&'static str, &'static str, u64, u64)> {
+        Some(match units {
+            // Time
+            Units::Seconds => ("s", "s", 1, 1),
+            Units::DeciSeconds => ("s", "s", 1, 10),
+            Units::CentiSeconds => ("s", "s", 1, 100),
+            Units::MilliSeconds => ("s", "s", 1, 1_000),
+            Units::MicroSeconds => ("s", "s", 1, 1_000_000),
+            Units::NanoSeconds => ("s", "s", 1, 1_000_000_000),
+            // Angles
+            Units::Degrees => ("deg", "deg", 1, 1),
+            Units::DegreesPerSecond => ("deg_s", "deg/s", 1, 1),
+            Units::Radians => ("rad", "rad", 1, 1),
+            Units::RadiansPerSecond => ("rad_s", "rad/s", 1, 1),
+            // Distance and speed
+            Units::Metres => ("m", "m", 1, 1),
+            Units::MetresPerSecond => ("m_s", "m/s", 1, 1),
+            Units::MetresPerSecondSquared => ("m_s2", "m/s/s", 1, 1),
+            // Temperature
+            Units::DegreesCelsius => ("deg_c", "degC", 1, 1),
+            // Electricity
+            Units::Volt => ("v", "V", 1, 1),
+            Units::Ampere => ("a", "A", 1, 1),
+            Units::AmpereHour => ("ah", "Ah", 1, 1),
+            // Pressure
+            Units::Pascal => ("pa", "Pa", 1, 1),
+            // Ratio and frequency
+            Units::Percent => ("percent", "%", 1, 1),
+            Units::Hertz => ("hz", "Hz", 1, 1),
+            _ => return None,
+        })
+    }
+
+    fn is_fixed_point(r#type: &MavType) -> bool {
+        matches!(
+            r#type,
+            MavType::Int8
+                | MavType::Int16
+                | MavType::Int32
+                | MavType::Int64
+                | MavType::UInt8
+                | MavType::UInt16
+                | MavType::UInt32
+                | MavType::UInt64
+        )
+    }
+
+    fn is_unsigned(r#type: &MavType) -> bool {
+        matches!(
+            r#type,
+            MavType::UInt8 | MavType::UInt16 | MavType::UInt32 | MavType::UInt64
+        )
+    }
+}
+
+/// Reduces fraction by the greatest common divisor.
+fn reduce(numerator: u64, denominator: u64) -> (u64, u64) {
+    match gcd(numerator, denominator) {
+        0 => (numerator, denominator),
+        gcd => (numerator / gcd, denominator / gcd),
+    }
+}
+
+impl DurationUnits {
+    fn new(units: &Units) -> Option<Self> {
+        Some(match units {
+            Units::Seconds => Self::Seconds,
+            Units::DeciSeconds => Self::DeciSeconds,
+            Units::CentiSeconds => Self::CentiSeconds,
+            Units::MilliSeconds => Self::MilliSeconds,
+            Units::MicroSeconds => Self::MicroSeconds,
+            Units::NanoSeconds => Self::NanoSeconds,
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mavinspect::Inspector;
+    use mavspec_rust_spec::units::round_half_away_from_zero;
+
+    fn fixed(r#type: &MavType, units: Units, multiplier: Option<&str>) -> (u64, u64) {
+        let multiplier = multiplier.map(|value| Multiplier::parse(value).unwrap());
+        match FieldScale::new(r#type, Some(&units), multiplier.as_ref()) {
+            Some(FieldScale::Fixed {
+                multiplier,
+                divisor,
+                ..
+            }) => (multiplier, divisor),
+            scale => panic!("unexpected scale for `{}`: {scale:?}", units.to_str()),
+        }
+    }
+
+    // Same conversion as in generated getters
+    fn get(scale: (u64, u64), raw: f64) -> f64 {
+        raw * scale.0 as f64 / scale.1 as f64
+    }
+
+    // Same conversion as in generated setters before casting to the field type
+    fn set(scale: (u64, u64), value: f64) -> f64 {
+        round_half_away_from_zero(value * scale.1 as f64 / scale.0 as f64)
+    }
+
+    #[test]
+    fn fixed_scale_values() {
+        let entries = [
+            (Units::DegreesE7, 123_456_789, 12.345_678_9),
+            (Units::DegreesE5, 12_345_678, 123.456_78),
+            (Units::CentiDegrees, 18_000, 180.0),
+            (Units::HalfDegrees, 90, 180.0),
+            (Units::CentiDegreesPerSecond, 250, 2.5),
+            (Units::MilliRadiansPerSecond, 1_500, 1.5),
+            (Units::KiloMetres, 3, 3_000.0),
+            (Units::DecaMetres, 12, 120.0),
+            (Units::DeciMetres, 15, 1.5),
+            (Units::CentiMetres, 150, 1.5),
+            (Units::MilliMetres, 1_500, 1.5),
+            (Units::SquareCentiMetres, 25_000, 2.5),
+            (Units::FiveMetresPerSecond, 50, 10.0),
+            (Units::DeciMetresPerSecond, 15, 1.5),
+            (Units::CentiMetresPerSecond, 150, 1.5),
+            (Units::MilliMetresPerSecond, 1_500, 1.5),
+            (Units::CentiDegreeCelsius, 2_150, 21.5),
+            (Units::CentiVolt, 1_250, 12.5),
+            (Units::MilliVolt, 12_500, 12.5),
+            (Units::CentiAmpere, 150, 1.5),
+            (Units::MilliAmpere, 1_500, 1.5),
+            (Units::MilliAmpereHour, 2_500, 2.5),
+            (Units::MilliTesla, 500, 0.5),
+            (Units::MilliGauss, 500, 0.5),
+            (Units::HectoJoule, 5, 500.0),
+            (Units::HectoPascal, 1_013, 101_300.0),
+            (Units::KiloPascal, 101, 101_000.0),
+            (Units::MilliBar, 1_013, 101_300.0),
+            (Units::DeciPercent, 505, 50.5),
+            (Units::CentiPercent, 5_050, 50.5),
+            (Units::DeciPixels, 15, 1.5),
+        ];
+
+        for (units, raw, value) in entries {
+            let scale = fixed(&MavType::Int32, units, None);
+            assert_eq!(
+                get(scale, raw as f64),
+                value,
+                "getter of `{}`",
+                units.to_str()
+            );
+            assert_eq!(
+                set(scale, value),
+                raw as f64,
+                "setter of `{}`",
+                units.to_str()
+            );
+            assert_eq!(
+                set(scale, -value),
+                -raw as f64,
+                "setter of `{}`",
+                units.to_str()
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_scale_rounding() {
+        let half_degrees = fixed(&MavType::UInt8, Units::HalfDegrees, None);
+        assert_eq!(set(half_degrees, 3.0), 2.0);
+        assert_eq!(set(half_degrees, 2.9), 1.0);
+        assert_eq!(set(half_degrees, -3.0), -2.0);
+
+        let five_metres_per_second = fixed(&MavType::UInt8, Units::FiveMetresPerSecond, None);
+        assert_eq!(set(five_metres_per_second, 0.5), 3.0);
+        assert_eq!(set(five_metres_per_second, 0.49), 2.0);
+        assert_eq!(set(five_metres_per_second, -0.5), -3.0);
+
+        let centimetres = fixed(&MavType::Int16, Units::CentiMetres, None);
+        assert_eq!(set(centimetres, 0.125), 13.0);
+        assert_eq!(set(centimetres, -0.125), -13.0);
+    }
+
+    #[test]
+    fn fixed_scale_saturation() {
+        let centimetres = fixed(&MavType::Int16, Units::CentiMetres, None);
+        assert_eq!(set(centimetres, f64::MAX) as i16, i16::MAX);
+        assert_eq!(set(centimetres, f64::MIN) as i16, i16::MIN);
+        assert_eq!(set(centimetres, f64::INFINITY) as i16, i16::MAX);
+        assert_eq!(set(centimetres, 400.0) as i16, i16::MAX);
+        assert_eq!(set(centimetres, f64::NAN) as i16, 0);
+
+        let half_degrees = fixed(&MavType::UInt8, Units::HalfDegrees, None);
+        assert_eq!(set(half_degrees, 1_000.0) as u8, u8::MAX);
+        assert_eq!(set(half_degrees, -10.0) as u8, 0);
+        assert_eq!(set(half_degrees, f64::NAN) as u8, 0);
+
+        let hectopascal = fixed(&MavType::UInt64, Units::HectoPascal, None);
+        assert_eq!(set(hectopascal, f64::MAX) as u64, u64::MAX);
+        assert_eq!(set(hectopascal, f64::MIN) as u64, 0);
+    }
+
+    #[test]
+    fn multipliers_are_folded() {
+        // Multiplier is applied on top of units
+        assert_eq!(
+            fixed(&MavType::Int16, Units::CentiMetres, Some("1E-2")),
+            (1, 10_000)
+        );
+        assert_eq!(
+            fixed(&MavType::Int16, Units::HalfDegrees, Some("0.5")),
+            (1, 1)
+        );
+        assert_eq!(
+            fixed(&MavType::Int16, Units::HectoPascal, Some("1E-3")),
+            (1, 10)
+        );
+        assert_eq!(
+            fixed(&MavType::Int16, Units::FiveMetresPerSecond, Some("2.5")),
+            (1, 2)
+        );
+
+        // Units already in target units are scaled only by multiplier
+        assert_eq!(
+            fixed(&MavType::Int16, Units::Metres, Some("1E-2")),
+            (1, 100)
+        );
+        assert_eq!(fixed(&MavType::UInt8, Units::Degrees, Some("2")), (2, 1));
+
+        // Time intervals with multiplier are converted to seconds regardless of signedness
+        assert_eq!(
+            fixed(&MavType::UInt32, Units::MicroSeconds, Some("1E-1")),
+            (1, 10_000_000)
+        );
+        assert_eq!(
+            fixed(&MavType::Int32, Units::MilliSeconds, Some("10")),
+            (1, 100)
+        );
+
+        // Units without target units are still ignored
+        let multiplier = Multiplier::parse("1E-2").unwrap();
+        assert!(FieldScale::new(&MavType::Int16, Some(&Units::Bytes), Some(&multiplier)).is_none());
+        assert!(
+            FieldScale::new(&MavType::Float, Some(&Units::Metres), Some(&multiplier)).is_none()
+        );
+        assert!(FieldScale::new(&MavType::Int16, None, Some(&multiplier)).is_none());
+    }
+
+    #[test]
+    fn fixed_point_only() {
+        assert!(FieldScale::new(&MavType::Float, Some(&Units::CentiMetres), None).is_none());
+        assert!(FieldScale::new(&MavType::Int32, None, None).is_none());
+        assert!(FieldScale::new(
+            &MavType::Array(Box::new(MavType::Int32), 4),
+            Some(&Units::MilliMetres),
+            None
+        )
+        .is_none());
+        assert!(FieldScale::new(&MavType::Int32, Some(&Units::MilliMetres), None).is_some());
+    }
+
+    #[test]
+    fn signed_durations_are_ignored() {
+        assert!(matches!(
+            FieldScale::new(&MavType::UInt64, Some(&Units::MicroSeconds), None),
+            Some(FieldScale::Duration { .. })
+        ));
+        assert!(FieldScale::new(&MavType::Int64, Some(&Units::NanoSeconds), None).is_none());
+    }
+
+    #[test]
+    fn high_latency2_scales() {
+        let protocol = Inspector::builder()
+            .set_sources(&["../message_definitions/standard"])
+            .set_include(&["common"])
+            .build()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let message = protocol
+            .get_dialect_by_name("common")
+            .unwrap()
+            .get_message_by_name("HIGH_LATENCY2")
+            .unwrap();
+
+        // Same conversion as in generated getters
+        let scaled = |field_name: &str, raw: f64| {
+            let field = message
+                .fields()
+                .iter()
+                .find(|field| field.name() == field_name)
+                .unwrap();
+            match FieldScale::new(field.r#type(), field.units(), None) {
+                Some(FieldScale::Fixed {
+                    multiplier,
+                    divisor,
+                    ..
+                }) => raw * multiplier as f64 / divisor as f64,
+                scale => panic!("unexpected scale of `{field_name}`: {scale:?}"),
+            }
+        };
+
+        // `deg/2`
+        assert_eq!(scaled("heading", 90.0), 180.0);
+        assert_eq!(scaled("heading", 180.0), 360.0);
+        // `m/s*5`
+        assert_eq!(scaled("airspeed", 50.0), 10.0);
+        assert_eq!(scaled("groundspeed", 255.0), 51.0);
+    }
+
+    #[test]
+    fn bundled_definitions_are_covered() {
+        let protocol = Inspector::builder()
+            .set_sources(&[
+                "../message_definitions/standard",
+                "../message_definitions/extra",
+            ])
+            .build()
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        // Units of integer fields which intentionally have no scaled accessors
+        let unscaled = [
+            Units::Metres,
+            Units::MetresPerSecond,
+            Units::Degrees,
+            Units::DegreesCelsius,
+            Units::Radians,
+            Units::RotationsPerMinute,
+            Units::Hertz,
+            Units::Percent,
+            Units::DeciBel,
+            Units::Pixels,
+            Units::Grams,
+            Units::MilliG,
+            Units::Bytes,
+            Units::BytesPerSecond,
+            Units::BitsPerSecond,
+            Units::KibiBytePerSecond,
+            Units::MebiByte,
+            Units::MebiBytePerSecond,
+        ];
+
+        for dialect in protocol.dialects() {
+            for message in dialect.messages() {
+                for field in message.fields() {
+                    let (Some(units), true) =
+                        (field.units(), FieldScale::is_fixed_point(field.r#type()))
+                    else {
+                        continue;
+                    };
+
+                    assert!(
+                        FieldScale::new(field.r#type(), Some(units), None).is_some()
+                            || unscaled.contains(units)
+                            || (DurationUnits::new(units).is_some()
+                                && !FieldScale::is_unsigned(field.r#type())),
+                        "no scale defined for `{}.{}` with units `{}`",
+                        message.name(),
+                        field.name(),
+                        units.to_str()
+                    );
+                }
+            }
+        }
+    }
+}
@@ -17,11 +17,14 @@ impl<'a> Spec for DialectsRootModuleSpec<'a> {
 
 impl<'a> DialectsRootModuleSpec<'a> {
     pub(crate) fn new(protocol: &Protocol, params: &'a GeneratorParams) -> Self {
+        let mut module_names: Vec<String> = protocol
+            .dialects()
+            .map(|dialect| dialect_mod_name(dialect.name().into()))
+            .collect();
+        module_names.sort();
+
         Self {
-            module_names: protocol
-                .dialects()
-                .map(|dialect| dialect_mod_name(dialect.name().into()))
-                .collect(),
+            module_names,
             params,
         }
     }
@@ -1,7 +1,39 @@
 pub(crate) mod dialects;
+pub(crate) mod workspace;
 
-use crate::generator::GeneratorParams;
+use mavinspect::protocol::Deprecated;
+
+use crate::generator::{GeneratorParams, WipPolicy};
 
 pub(crate) trait Spec {
     fn params(&self) -> &GeneratorParams;
 }
+
+/// Composes a note for `#[deprecated]` attribute of a generated entity.
+///
+/// Entities deprecated by MAVLink XML definitions always get a note. Work-in-progress entities get a note only if
+/// [`WipPolicy::Warn`] is set. Since Rust allows only one `#[deprecated]` attribute, actual deprecation takes priority.
+pub(crate) fn deprecation_note(
+    deprecated: Option<&Deprecated>,
+    wip: bool,
+    params: &GeneratorParams,
+) -> Option<String> {
+    if let Some(deprecated) = deprecated {
+        let since = deprecated.since();
+        let mut note = format!("Deprecated since {}-{:02}", since.year(), since.month());
+        if !deprecated.replaced_by().is_empty() {
+            note.push_str(&format!(", replaced by `{}`", deprecated.replaced_by()));
+        }
+        note.push('.');
+        return Some(note);
+    }
+
+    if wip && params.wip == WipPolicy::Warn {
+        return Some(
+            "Work in progress: this part of MAVLink protocol is unstable and may change."
+                .to_string(),
+        );
+    }
+
+    None
+}
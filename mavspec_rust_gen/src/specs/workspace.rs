@@ -0,0 +1,88 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::conventions::{dialect_crate_dir_name, dialect_crate_name};
+use crate::generator::GeneratorParams;
+use crate::specs::Spec;
+
+/// Specification for Cargo workspace manifest template.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct WorkspaceSpec<'a> {
+    members: Vec<String>,
+    params: &'a GeneratorParams,
+}
+
+impl<'a> Spec for WorkspaceSpec<'a> {
+    fn params(&self) -> &GeneratorParams {
+        self.params
+    }
+}
+
+impl<'a> WorkspaceSpec<'a> {
+    pub(crate) fn new(dialect_names: &[&str], params: &'a GeneratorParams) -> Self {
+        let mut members: Vec<String> = dialect_names
+            .iter()
+            .map(|name| dialect_crate_dir_name(name))
+            .collect();
+        members.sort();
+
+        Self { members, params }
+    }
+
+    pub(crate) fn members(&self) -> &[String] {
+        self.members.as_slice()
+    }
+}
+
+/// Specification for dialect crate manifest template.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct DialectCrateSpec<'a> {
+    dialect_name: &'a str,
+    crate_name: String,
+    dependencies: Vec<(String, String)>,
+    params: &'a GeneratorParams,
+}
+
+impl<'a> Spec for DialectCrateSpec<'a> {
+    fn params(&self) -> &GeneratorParams {
+        self.params
+    }
+}
+
+impl<'a> DialectCrateSpec<'a> {
+    /// Creates specification for dialect crate which depends on crates of `dependencies` dialects.
+    pub(crate) fn new(
+        dialect_name: &'a str,
+        dependencies: &[&str],
+        params: &'a GeneratorParams,
+    ) -> Self {
+        Self {
+            dialect_name,
+            crate_name: dialect_crate_name(dialect_name),
+            dependencies: dependencies
+                .iter()
+                .map(|name| (dialect_crate_name(name), dialect_crate_dir_name(name)))
+                .collect(),
+            params,
+        }
+    }
+
+    pub(crate) fn dialect_name(&self) -> &str {
+        self.dialect_name
+    }
+
+    pub(crate) fn crate_name(&self) -> &str {
+        self.crate_name.as_str()
+    }
+
+    /// Crate names and directories of dialects this dialect inherits entities from.
+    pub(crate) fn dependencies(&self) -> &[(String, String)] {
+        self.dependencies.as_slice()
+    }
+
+    /// Path to local `mavspec` crate, if any.
+    pub(crate) fn mavspec_path(&self) -> Option<&Path> {
+        self.params.mavspec_path.as_deref()
+    }
+}
@@ -1,13 +1,18 @@
 use crate::conventions::{
-    dialect_mod_name, enum_bitmask_entry_name, enum_entry_name, enum_mod_name, enum_rust_name,
+    dialect_crate_name, dialect_mod_name, enum_bitmask_entry_name, enum_entry_name, enum_mod_name,
+    enum_rust_name,
 };
 use quote::{format_ident, quote};
 
+use crate::generator::{OutputLayout, SerdeEnumRepr};
 use crate::specs::dialects::dialect::enums::{
     EnumImplModuleSpec, EnumInheritedModuleSpec, EnumsRootModuleSpec,
 };
 use crate::specs::Spec;
-use crate::templates::helpers::make_serde_derive_annotation;
+use crate::templates::helpers::{
+    make_arbitrary_derive_annotation, make_deprecated_annotation, make_f32_value_expr,
+};
+use crate::type_attributes::{make_type_attributes, TypeKind};
 
 pub(crate) fn enums_root_module(spec: &EnumsRootModuleSpec) -> syn::File {
     let module_doc_comment = format!(" MAVLink enums of `{}` dialect.", spec.dialect_name());
@@ -34,12 +39,17 @@ pub(crate) fn enum_module(spec: &EnumImplModuleSpec) -> syn::File {
 
     let bitmask_impl = make_bitmask_enum(spec);
     let enum_impl = make_enum(spec);
+    let command_info_impl = make_command_info_impl(spec);
+    let tests = generate_tests(spec);
 
     syn::parse2(quote! {
         #![doc = #module_doc_comment]
 
         #bitmask_impl
         #enum_impl
+        #command_info_impl
+
+        #tests
     })
     .unwrap()
 }
@@ -49,7 +59,16 @@ fn make_bitmask_enum(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
     let description_doc_comments = spec.description().iter().map(|line| {
         quote! { #[doc = #line] }
     });
-    let derive_serde = make_serde_derive_annotation(spec.params().serde);
+    let derive_serde = make_enum_serde_derive_annotation(spec);
+    let serde_impl = make_bitmask_serde_impl(spec);
+    let arbitrary_impl = make_bitmask_arbitrary_impl(spec);
+    let type_attributes = make_type_attributes(
+        &spec.params().type_attributes,
+        TypeKind::Bitmask,
+        spec.name(),
+        false,
+    );
+    let deprecated = make_deprecated_annotation(spec.deprecated());
     let enum_ident = format_ident!("{}", enum_rust_name(spec.name()));
     let enum_inferred_type = format_ident!("{}", spec.inferred_type().rust_type());
 
@@ -60,15 +79,29 @@ fn make_bitmask_enum(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
         });
         let flag_ident = format_ident!("{}", enum_bitmask_entry_name(entry.name_stripped()));
         let flag_value = entry.value_expr();
+        let deprecated = make_deprecated_annotation(entry.deprecated());
 
         quote! {
             #[doc = #name_doc_comment]
             ///
             #(#description_doc_comments)*
+            #deprecated
             const #flag_ident = #flag_value;
         }
     });
 
+    let enum_name = spec.name();
+    let entry_infos = spec.entries().iter().map(|entry| {
+        let flag_ident = format_ident!("{}", enum_bitmask_entry_name(entry.name_stripped()));
+        let name = entry.name();
+        let name_stripped = entry.name_stripped();
+        let description = entry.description().join(" ");
+
+        quote! {
+            mavspec::rust::spec::EnumEntryInfo::new(Self::#flag_ident, #name, #name_stripped, #description)
+        }
+    });
+
     if spec.is_bitmask() {
         quote! {
             use mavspec::rust::spec::bitflags::bitflags;
@@ -81,6 +114,8 @@ fn make_bitmask_enum(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
             #(#description_doc_comments)*
             #[derive(core::marker::Copy, core::clone::Clone, core::fmt::Debug, core::default::Default)]
             #derive_serde
+            #type_attributes
+            #deprecated
             pub struct #enum_ident(#enum_inferred_type);
 
             bitflags! {
@@ -88,6 +123,61 @@ fn make_bitmask_enum(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
                     #(#entry_consts)*
                 }
             }
+
+            impl mavspec::rust::spec::EnumSpec for #enum_ident {
+                const NAME: &'static str = #enum_name;
+                const ENTRIES: &'static [mavspec::rust::spec::EnumEntryInfo<Self>] = &[
+                    #(#entry_infos,)*
+                ];
+
+                fn entry_info(&self) -> Option<&'static mavspec::rust::spec::EnumEntryInfo<Self>> {
+                    Self::ENTRIES
+                        .iter()
+                        .find(|entry| entry.value().bits() == self.bits())
+                }
+            }
+
+            impl #enum_ident {
+                /// Canonical MAVLink name of this flag.
+                ///
+                /// Returns [`None`] if value does not correspond to exactly one flag.
+                pub fn name(&self) -> Option<&'static str> {
+                    mavspec::rust::spec::EnumSpec::entry_info(self).map(|entry| entry.name())
+                }
+
+                /// Name of this flag without enum name prefix.
+                ///
+                /// Returns [`None`] if value does not correspond to exactly one flag.
+                pub fn name_stripped(&self) -> Option<&'static str> {
+                    mavspec::rust::spec::EnumSpec::entry_info(self).map(|entry| entry.name_stripped())
+                }
+
+                /// Description of this flag.
+                ///
+                /// Returns [`None`] if value does not correspond to exactly one flag.
+                pub fn description(&self) -> Option<&'static str> {
+                    mavspec::rust::spec::EnumSpec::entry_info(self).map(|entry| entry.description())
+                }
+            }
+
+            impl core::fmt::Display for #enum_ident {
+                /// Formats bitmask as `|`-separated canonical flag names.
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    mavspec::rust::spec::enums::fmt_bitmask(self, f)
+                }
+            }
+
+            impl core::str::FromStr for #enum_ident {
+                type Err = mavspec::rust::spec::SpecError;
+
+                /// Parses bitmask from `|`-separated flag names (canonical or prefix-stripped, case-insensitive).
+                fn from_str(s: &str) -> Result<Self, mavspec::rust::spec::SpecError> {
+                    mavspec::rust::spec::enums::parse_bitmask(s)
+                }
+            }
+
+            #serde_impl
+            #arbitrary_impl
         }
     } else {
         quote!()
@@ -102,7 +192,17 @@ fn make_enum(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
         let description_doc_comments = spec.description().iter().map(|line| {
             quote! { #[doc = #line] }
         });
-        let derive_serde = make_serde_derive_annotation(spec.params().serde);
+        let derive_serde = make_enum_serde_derive_annotation(spec);
+        let derive_arbitrary = make_arbitrary_derive_annotation(spec.params().arbitrary);
+        let serde_impl = make_enum_serde_impl(spec);
+        let type_attributes = make_type_attributes(
+            &spec.params().type_attributes,
+            TypeKind::Enum,
+            spec.name(),
+            false,
+        );
+        let deprecated = make_deprecated_annotation(spec.deprecated());
+        let enum_name = spec.name();
         let enum_ident = format_ident!("{}", enum_rust_name(spec.name()));
         let enum_inferred_type = format_ident!("{}", spec.inferred_type().rust_type());
 
@@ -113,11 +213,19 @@ fn make_enum(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
             });
             let entry_ident = format_ident!("{}", enum_entry_name(entry.name_stripped()));
             let entry_value = entry.value_expr();
+            let deprecated = make_deprecated_annotation(entry.deprecated());
+            let name = entry.name();
+            let name_stripped = entry.name_stripped();
+            let description = entry.description().join(" ");
 
             quote! {
                 #[doc = #name_doc_comment]
                 ///
                 #(#description_doc_comments)*
+                #deprecated
+                #[canonical_name(#name)]
+                #[stripped_name(#name_stripped)]
+                #[description(#description)]
                 #entry_ident = #entry_value,
             }
         });
@@ -133,11 +241,325 @@ fn make_enum(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
             #[derive(mavspec::rust::derive::Enum)]
             #[derive(core::marker::Copy, core::clone::Clone, core::fmt::Debug, core::default::Default)]
             #[repr(#enum_inferred_type)]
+            #[canonical_name(#enum_name)]
             #derive_serde
+            #derive_arbitrary
+            #type_attributes
+            #deprecated
             pub enum #enum_ident {
                 #[default]
                 #(#enum_variants)*
             }
+
+            #serde_impl
+        }
+    }
+}
+
+/// Derives `serde::Serialize` for [`SerdeEnumRepr::Derive`], deserialization is always implemented explicitly.
+fn make_enum_serde_derive_annotation(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
+    if spec.params().serde && spec.params().serde_enums == SerdeEnumRepr::Derive {
+        quote! {
+            #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+        }
+    } else {
+        quote!()
+    }
+}
+
+/// Conversion from `u64` to the numeric representation of enum.
+fn make_from_u64_expr(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
+    let enum_inferred_type = format_ident!("{}", spec.inferred_type().rust_type());
+
+    if spec.inferred_type().rust_type() == "u64" {
+        quote! { Some(value) }
+    } else {
+        quote! { #enum_inferred_type::try_from(value).ok() }
+    }
+}
+
+fn make_enum_serde_impl(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
+    if !spec.params().serde {
+        return quote!();
+    }
+
+    let enum_ident = format_ident!("{}", enum_rust_name(spec.name()));
+    let enum_inferred_type = format_ident!("{}", spec.inferred_type().rust_type());
+    let from_u64 = make_from_u64_expr(spec);
+
+    let serialize = match spec.params().serde_enums {
+        SerdeEnumRepr::Derive => None,
+        SerdeEnumRepr::Numeric => Some(quote! {
+            serde::Serialize::serialize(&(*self as #enum_inferred_type), serializer)
+        }),
+        _ => Some(quote! {
+            if serializer.is_human_readable() {
+                serializer.serialize_str(self.name())
+            } else {
+                serde::Serialize::serialize(&(*self as #enum_inferred_type), serializer)
+            }
+        }),
+    };
+    let serialize_impl = serialize.map(|serialize| {
+        quote! {
+            #[cfg(feature = "serde")]
+            impl serde::Serialize for #enum_ident {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    #serialize
+                }
+            }
+        }
+    });
+
+    // Derived representation also accepts Rust variant names and, for compact formats, variant indices
+    let deserialize = if spec.params().serde_enums == SerdeEnumRepr::Derive {
+        let variants = spec
+            .entries()
+            .iter()
+            .map(|entry| enum_entry_name(entry.name_stripped()));
+        quote! {
+            mavspec::rust::spec::enums::deserialize_derived_enum::<Self, #enum_inferred_type, D>(
+                deserializer,
+                |value| #from_u64.and_then(|value| Self::try_from(value).ok()),
+                &[#(#variants,)*],
+            )
+        }
+    } else {
+        quote! {
+            mavspec::rust::spec::enums::deserialize_enum::<Self, #enum_inferred_type, D>(
+                deserializer,
+                |value| #from_u64.and_then(|value| Self::try_from(value).ok()),
+            )
+        }
+    };
+
+    quote! {
+        #serialize_impl
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for #enum_ident {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                #deserialize
+            }
+        }
+    }
+}
+
+fn make_bitmask_serde_impl(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
+    if !spec.params().serde {
+        return quote!();
+    }
+
+    let enum_ident = format_ident!("{}", enum_rust_name(spec.name()));
+    let enum_inferred_type = format_ident!("{}", spec.inferred_type().rust_type());
+    let from_u64 = make_from_u64_expr(spec);
+
+    let serialize_human_readable = match spec.params().serde_enums {
+        SerdeEnumRepr::Name => quote! { serializer.collect_str(self) },
+        _ => quote! { mavspec::rust::spec::enums::serialize_bitmask_flags(self, serializer) },
+    };
+    let serialize = match spec.params().serde_enums {
+        SerdeEnumRepr::Derive => None,
+        SerdeEnumRepr::Numeric => Some(quote! {
+            serde::Serialize::serialize(&self.bits(), serializer)
+        }),
+        _ => Some(quote! {
+            if serializer.is_human_readable() {
+                #serialize_human_readable
+            } else {
+                serde::Serialize::serialize(&self.bits(), serializer)
+            }
+        }),
+    };
+    let serialize_impl = serialize.map(|serialize| {
+        quote! {
+            #[cfg(feature = "serde")]
+            impl serde::Serialize for #enum_ident {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    #serialize
+                }
+            }
+        }
+    });
+
+    quote! {
+        #serialize_impl
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for #enum_ident {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                mavspec::rust::spec::enums::deserialize_bitmask::<Self, #enum_inferred_type, D>(
+                    deserializer,
+                    |value| #from_u64.map(Self::from_bits_retain),
+                )
+            }
+        }
+    }
+}
+
+/// Implements `arbitrary::Arbitrary` for bitmask.
+///
+/// Unknown bits are truncated, so generated values survive encoding/decoding round-trips.
+fn make_bitmask_arbitrary_impl(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
+    if !spec.params().arbitrary {
+        return quote!();
+    }
+
+    let enum_ident = format_ident!("{}", enum_rust_name(spec.name()));
+    let enum_inferred_type = format_ident!("{}", spec.inferred_type().rust_type());
+
+    quote! {
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for #enum_ident {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                Ok(Self::from_bits_truncate(<#enum_inferred_type as arbitrary::Arbitrary>::arbitrary(u)?))
+            }
+
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                <#enum_inferred_type as arbitrary::Arbitrary>::size_hint(depth)
+            }
+        }
+    }
+}
+
+/// Generates tests which check all enum entries or bitmask flags.
+fn generate_tests(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
+    if !spec.params().generate_tests {
+        return quote!();
+    }
+
+    let enum_ident = format_ident!("{}", enum_rust_name(spec.name()));
+    let enum_inferred_type = format_ident!("{}", spec.inferred_type().rust_type());
+
+    let checks = if spec.is_bitmask() {
+        quote! {
+            let mut all_flags = #enum_ident::empty();
+            for entry in #enum_ident::ENTRIES {
+                let flag = entry.value();
+                assert_eq!(#enum_ident::from_bits(flag.bits()).unwrap().bits(), flag.bits());
+                all_flags |= flag;
+            }
+            assert_eq!(#enum_ident::all().bits(), all_flags.bits());
+            assert_eq!(#enum_ident::from_bits_truncate(#enum_inferred_type::MAX).bits(), all_flags.bits());
+        }
+    } else {
+        quote! {
+            for entry in #enum_ident::ENTRIES {
+                let value = entry.value() as #enum_inferred_type;
+                assert_eq!(#enum_ident::try_from(value).unwrap() as #enum_inferred_type, value);
+                assert_eq!(entry.value().name(), entry.name());
+            }
+        }
+    };
+
+    quote! {
+        #[cfg(test)]
+        mod tests {
+            use mavspec::rust::spec::EnumSpec;
+
+            use super::*;
+
+            #[test]
+            #[allow(deprecated)]
+            fn entries() {
+                #checks
+            }
+        }
+    }
+}
+
+fn make_command_info_impl(spec: &EnumImplModuleSpec) -> proc_macro2::TokenStream {
+    if spec.is_bitmask() || !spec.is_command() {
+        return quote!();
+    }
+
+    let enum_ident = format_ident!("{}", enum_rust_name(spec.name()));
+
+    let command_infos = spec.entries().iter().map(|entry| {
+        let value = entry.value_expr();
+        let name = entry.name();
+        let description = entry.description().join(" ");
+
+        let params = entry.params().iter().map(|param| {
+            let index = param.index();
+            let param_description = param.description();
+
+            let label = param.label().map(|label| quote! { .with_label(#label) });
+            let units = param.units().map(|units| {
+                let units = units.to_str();
+                quote! { .with_units(#units) }
+            });
+            let enum_name = param
+                .r#enum()
+                .map(|enum_name| quote! { .with_enum_name(#enum_name) });
+            let decimal_places = param
+                .decimal_places()
+                .map(|decimal_places| quote! { .with_decimal_places(#decimal_places) });
+            let increment = param.increment().map(|value| {
+                let value = make_f32_value_expr(value);
+                quote! { .with_increment(#value) }
+            });
+            let min_value = param.min_value().map(|value| {
+                let value = make_f32_value_expr(value);
+                quote! { .with_min_value(#value) }
+            });
+            let max_value = param.max_value().map(|value| {
+                let value = make_f32_value_expr(value);
+                quote! { .with_max_value(#value) }
+            });
+            let reserved = param.reserved().then(|| quote! { .with_reserved(true) });
+            let default = param.default().map(|value| {
+                let value = make_f32_value_expr(value);
+                quote! { .with_default(#value) }
+            });
+
+            quote! {
+                mavspec::rust::spec::CommandParamInfo::new(#index, #param_description)
+                    #label #units #enum_name #decimal_places #increment #min_value #max_value
+                    #reserved #default
+            }
+        });
+
+        let flags = entry.cmd_flags().map(|flags| {
+            let has_location = flags
+                .has_location()
+                .map(|flag| quote! { .with_has_location(#flag) });
+            let is_destination = flags
+                .is_destination()
+                .map(|flag| quote! { .with_is_destination(#flag) });
+            let mission_only = flags
+                .mission_only()
+                .map(|flag| quote! { .with_mission_only(#flag) });
+            quote! { #has_location #is_destination #mission_only }
+        });
+
+        quote! {
+            mavspec::rust::spec::CommandInfo::new(#value, #name, #description, &[#(#params),*])
+                #flags
+        }
+    });
+
+    let info_match_arms = spec.entries().iter().enumerate().map(|(idx, entry)| {
+        let entry_ident = format_ident!("{}", enum_entry_name(entry.name_stripped()));
+        quote! { Self::#entry_ident => &Self::COMMANDS[#idx], }
+    });
+
+    quote! {
+        #[cfg(not(doctest))]
+        impl #enum_ident {
+            /// Metadata for all commands retained in this dialect sorted by command value.
+            pub const COMMANDS: &'static [mavspec::rust::spec::CommandInfo] = &[
+                #(#command_infos),*
+            ];
+
+            /// Returns command metadata including parameter specifications.
+            ///
+            /// See [`CommandInfo`](mavspec::rust::spec::CommandInfo).
+            pub const fn info(&self) -> &'static mavspec::rust::spec::CommandInfo {
+                match self {
+                    #(#info_match_arms)*
+                }
+            }
         }
     }
 }
@@ -155,6 +577,19 @@ pub(crate) fn enum_inherited_module(spec: &EnumInheritedModuleSpec) -> syn::File
     let enum_mod_ident = format_ident!("{}", enum_mod_name(spec.name()));
     let enum_doc_comment = format!(" Originally defined in [`{dialect_mod_ident}::enums::{enum_mod_ident}`](dialect::enums::{enum_ident})");
 
+    if spec.params().output_layout == OutputLayout::Workspace {
+        let dialect_crate_ident =
+            format_ident!("{}", dialect_crate_name(spec.original_dialect_name()));
+
+        return syn::parse2(quote! {
+            #![doc = #module_doc_comment]
+
+            #[doc(inline)]
+            pub use #dialect_crate_ident::enums::#enum_mod_ident::#enum_ident;
+        })
+        .unwrap();
+    }
+
     syn::parse2(quote! {
         #![doc = #module_doc_comment]
 
@@ -1,14 +1,22 @@
+use mavinspect::protocol::{MavType, MessageFieldInvalidValue};
 use quote::{format_ident, quote};
 
 use crate::conventions::{
-    dialect_enum_name, dialect_mod_name, enum_rust_name, message_mod_name, message_struct_name,
-    rust_var_name,
+    dialect_crate_name, dialect_mod_name, enum_rust_name, message_mod_name, message_struct_name,
+    rust_var_name, scaled_accessor_name, units_type_name,
 };
+use crate::generator::OutputLayout;
 use crate::specs::dialects::dialect::messages::{
-    MessageImplModuleSpec, MessageInheritedModuleSpec, MessagesRootModuleSpec,
+    FieldSpec, MessageImplModuleSpec, MessageInheritedModuleSpec, MessagesRootModuleSpec,
 };
+use crate::specs::dialects::dialect::scales::{DurationUnits, FieldScale};
 use crate::specs::Spec;
-use crate::templates::helpers::make_serde_derive_annotation;
+use crate::templates::helpers::{
+    make_arbitrary_derive_annotation, make_deprecated_annotation, make_pseudo_random_fill,
+    make_sentinel_expr, make_serde_derive_annotation, make_typed_value_expr,
+};
+use crate::test_vectors::TestVectorValue;
+use crate::type_attributes::{make_type_attributes, TypeKind};
 
 /// Messages root module template.
 pub(crate) fn messages_root_module(spec: &MessagesRootModuleSpec) -> syn::File {
@@ -33,7 +41,6 @@ pub(crate) fn messages_root_module(spec: &MessagesRootModuleSpec) -> syn::File {
 
 pub(crate) fn message_module(spec: &MessageImplModuleSpec) -> syn::File {
     let module_doc_comment = format!(" # MAVLink `{}` message implementation.", spec.name());
-    let dialect_enum_ident = format_ident!("{}", dialect_enum_name(spec.dialect_name()));
     let message_id: syn::LitInt = syn::parse_str(format!("{}", spec.id()).as_str()).unwrap();
     let crc_extra: syn::LitInt = syn::parse_str(format!("{}", spec.crc_extra()).as_str()).unwrap();
     let message_leading_doc_comment = format!(" MAVLink `{}` message.", spec.name());
@@ -45,6 +52,19 @@ pub(crate) fn message_module(spec: &MessageImplModuleSpec) -> syn::File {
         quote! { #[doc = #line] }
     });
     let derive_serde = make_serde_derive_annotation(spec.params().serde);
+    let derive_arbitrary = make_arbitrary_derive_annotation(spec.params().arbitrary);
+    let type_attributes = make_type_attributes(
+        &spec.params().type_attributes,
+        TypeKind::Message,
+        spec.name(),
+        spec.has_floats(),
+    );
+    let deprecated = make_deprecated_annotation(spec.deprecated());
+    let builder = if spec.params().generate_builders {
+        quote! { #[builder] }
+    } else {
+        quote!()
+    };
 
     let message_struct_ident = spec.ident();
     let message_encode_decode_doc_comment =
@@ -62,11 +82,6 @@ pub(crate) fn message_module(spec: &MessageImplModuleSpec) -> syn::File {
             quote!()
         };
         let field_ident = format_ident!("{}", rust_var_name(field.name()));
-        let field_rust_type: syn::Type =
-            syn::parse_str(field.r#type().rust_type().as_str()).unwrap();
-        let enum_ident = format_ident!("{}", enum_rust_name(field.enum_name()));
-        let array_length = field.array_length();
-
         let field_base_type: syn::Type =
             syn::parse_str(field.r#type().base_type().rust_type().as_str()).unwrap();
         let base_type_attr = if field.is_enum() {
@@ -85,26 +100,36 @@ pub(crate) fn message_module(spec: &MessageImplModuleSpec) -> syn::File {
         } else {
             quote!()
         };
+        let default_value_attr = match field.default() {
+            Some(value) if !field.is_string() => {
+                let value = make_typed_value_expr(value);
+                quote! { #[default_value(#value)] }
+            }
+            _ => quote!(),
+        };
         let extension_attr = if field.is_extension() {
             quote! { #[extension] }
         } else {
             quote!()
         };
-
-        let field_definition = if field.is_enum() {
-            if field.is_array() {
-                quote! {
-                    pub #field_ident: [super::super::enums::#enum_ident; #array_length],
-                }
-            } else {
-                quote! {
-                    pub #field_ident: super::super::enums::#enum_ident,
-                }
-            }
+        let instance_attr = if field.is_instance() {
+            quote! { #[instance] }
         } else {
+            quote!()
+        };
+
+        let quantity_attr = if field.is_quantity() {
             quote! {
-                pub #field_ident: #field_rust_type,
+                #[quantity]
+                #[base_type(#field_base_type)]
             }
+        } else {
+            quote!()
+        };
+
+        let field_type = make_field_type(field);
+        let field_definition = quote! {
+            pub #field_ident: #field_type,
         };
 
         quote! {
@@ -113,19 +138,54 @@ pub(crate) fn message_module(spec: &MessageImplModuleSpec) -> syn::File {
             #(#description_doc_comments)*
             #serde_arrays_attr
             #bitmask_attr
+            #quantity_attr
             #base_type_attr
             #repr_type_attr
+            #default_value_attr
             #extension_attr
+            #instance_attr
             #field_definition
         }
     });
 
+    let field_infos = spec.fields().iter().map(|field| {
+        let name = field.name();
+        let units = field.units().map(|units| {
+            let units = units.to_str();
+            quote! { .with_units(#units) }
+        });
+        let invalid = field.invalid().map(|invalid| {
+            let invalid = match invalid {
+                MessageFieldInvalidValue::Value(value) => {
+                    let value = make_sentinel_expr(value);
+                    quote! { Value(#value) }
+                }
+                MessageFieldInvalidValue::AllItems(value) => {
+                    let value = make_sentinel_expr(value);
+                    quote! { AllItems(#value) }
+                }
+                MessageFieldInvalidValue::FirstItem(value) => {
+                    let value = make_sentinel_expr(value);
+                    quote! { FirstItem(#value) }
+                }
+                MessageFieldInvalidValue::EnumEntryValue(name) => quote! { EnumEntry(#name) },
+            };
+            quote! { .with_invalid(mavspec::rust::spec::InvalidValue::#invalid) }
+        });
+
+        quote! {
+            FieldInfo::new(#name) #units #invalid
+        }
+    });
+
+    let scaled_accessors = make_scaled_accessors(spec);
+    let invalid_accessors = make_invalid_accessors(spec);
     let tests = generate_tests(spec);
 
     syn::parse2(quote! {
         #![doc = #module_doc_comment]
 
-        use mavspec::rust::spec::{MessageInfo, MessageSpec};
+        use mavspec::rust::spec::{FieldInfo, MessageInfo, MessageSpec};
         use mavspec::rust::spec::types::{MessageId, CrcExtra};
 
         /// Message ID.
@@ -134,6 +194,8 @@ pub(crate) fn message_module(spec: &MessageImplModuleSpec) -> syn::File {
         pub(crate) const CRC_EXTRA: CrcExtra = #crc_extra;
         /// Generic message info that contains all message metadata.
         pub(crate) const MESSAGE_INFO: MessageInfo = MessageInfo::new(MESSAGE_ID, CRC_EXTRA);
+        /// Message fields metadata in the order of XML definition.
+        pub const FIELDS: &[FieldInfo] = &[#(#field_infos),*];
 
         /// MAVLink message specification
         #[inline]
@@ -160,29 +222,366 @@ pub(crate) fn message_module(spec: &MessageImplModuleSpec) -> syn::File {
         #[derive(mavspec::rust::derive::Message)]
         #[derive(core::clone::Clone, core::fmt::Debug)]
         #derive_serde
+        #derive_arbitrary
+        #type_attributes
         #[message_id(#message_id)]
         #[crc_extra(#crc_extra)]
+        #builder
+        #deprecated
         pub struct #message_struct_ident {
             #(#message_fields)*
         }
 
-        impl core::convert::From<#message_struct_ident> for super::super::#dialect_enum_ident {
-            fn from(value: #message_struct_ident) -> Self {
-                Self::#message_struct_ident(value)
-            }
-        }
+        #scaled_accessors
+
+        #invalid_accessors
 
         #tests
     })
     .unwrap()
 }
 
+/// Returns expression for reading raw value of a `field` from `target`.
+fn make_raw_field_value(
+    target: &proc_macro2::TokenStream,
+    field: &FieldSpec,
+) -> proc_macro2::TokenStream {
+    let field_ident = format_ident!("{}", rust_var_name(field.name()));
+
+    if field.is_quantity() {
+        quote! { #target.#field_ident.raw() }
+    } else {
+        quote! { #target.#field_ident }
+    }
+}
+
+/// Returns statement which assigns raw `value` to a `field` of `target`.
+fn make_raw_field_assign(
+    target: &proc_macro2::TokenStream,
+    field: &FieldSpec,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let field_ident = format_ident!("{}", rust_var_name(field.name()));
+
+    if field.is_quantity() {
+        quote! { #target.#field_ident = mavspec::rust::spec::units::Quantity::new(#value); }
+    } else {
+        quote! { #target.#field_ident = #value; }
+    }
+}
+
+fn make_scaled_accessors(spec: &MessageImplModuleSpec) -> proc_macro2::TokenStream {
+    let message_struct_ident = spec.ident();
+
+    let accessors = spec.fields().iter().filter_map(|field| {
+        let scale = field.scale()?;
+        let field_ident = format_ident!("{}", rust_var_name(field.name()));
+        let field_type: syn::Type = syn::parse_str(field.r#type().rust_type().as_str()).unwrap();
+        let raw_units = field.units().map(|units| units.to_str()).unwrap_or_default();
+        let raw_value = make_raw_field_value(&quote!(self), field);
+
+        Some(match scale {
+            FieldScale::Fixed {
+                suffix,
+                units,
+                multiplier,
+                divisor,
+            } => {
+                let getter_ident = format_ident!("{}", scaled_accessor_name(field.name(), suffix));
+                let setter_ident =
+                    format_ident!("set_{}", scaled_accessor_name(field.name(), suffix));
+                let getter_doc = format!(
+                    " Value of [`{field_ident}`](Self::{field_ident}) in `{units}` (raw units: `{raw_units}`)."
+                );
+                let setter_doc = format!(
+                    " Sets [`{field_ident}`](Self::{field_ident}) from value in `{units}` (raw units: `{raw_units}`)."
+                );
+
+                let multiplier = proc_macro2::Literal::f64_unsuffixed(*multiplier as f64);
+                let divisor = proc_macro2::Literal::f64_unsuffixed(*divisor as f64);
+                let set_raw = make_raw_field_assign(
+                    &quote!(self),
+                    field,
+                    quote! {
+                        mavspec::rust::spec::units::round_half_away_from_zero(
+                            value * #divisor / #multiplier
+                        ) as #field_type
+                    },
+                );
+
+                quote! {
+                    #[doc = #getter_doc]
+                    #[inline]
+                    pub fn #getter_ident(&self) -> f64 {
+                        #raw_value as f64 * #multiplier / #divisor
+                    }
+
+                    #[doc = #setter_doc]
+                    ///
+                    /// Value is rounded to the nearest raw value (ties are rounded away from zero) and saturated
+                    /// to the range of the field type. `NaN` is converted to zero. See
+                    /// [`round_half_away_from_zero`](mavspec::rust::spec::units::round_half_away_from_zero).
+                    #[inline]
+                    pub fn #setter_ident(&mut self, value: f64) {
+                        #set_raw
+                    }
+                }
+            }
+            FieldScale::Duration { units } => {
+                let getter_ident = format_ident!("{}", scaled_accessor_name(field.name(), "duration"));
+                let setter_ident =
+                    format_ident!("set_{}", scaled_accessor_name(field.name(), "duration"));
+                let getter_doc = format!(
+                    " Value of [`{field_ident}`](Self::{field_ident}) as [`Duration`](core::time::Duration) (raw units: `{raw_units}`)."
+                );
+                let setter_doc = format!(
+                    " Sets [`{field_ident}`](Self::{field_ident}) from [`Duration`](core::time::Duration) (raw units: `{raw_units}`)."
+                );
+                let unit_nanos = proc_macro2::Literal::u128_unsuffixed(duration_unit_nanos(units));
+                let set_raw = make_raw_field_assign(
+                    &quote!(self),
+                    field,
+                    quote! { #field_type::try_from(raw).unwrap_or(#field_type::MAX) },
+                );
+                let raw_u64 = if matches!(field.r#type(), MavType::UInt64) {
+                    raw_value
+                } else {
+                    quote! { u64::from(#raw_value) }
+                };
+                let getter_expr = match units {
+                    DurationUnits::Seconds => quote! { core::time::Duration::from_secs(#raw_u64) },
+                    DurationUnits::DeciSeconds => quote! { core::time::Duration::from_millis(#raw_u64.saturating_mul(100)) },
+                    DurationUnits::CentiSeconds => quote! { core::time::Duration::from_millis(#raw_u64.saturating_mul(10)) },
+                    DurationUnits::MilliSeconds => quote! { core::time::Duration::from_millis(#raw_u64) },
+                    DurationUnits::MicroSeconds => quote! { core::time::Duration::from_micros(#raw_u64) },
+                    DurationUnits::NanoSeconds => quote! { core::time::Duration::from_nanos(#raw_u64) },
+                };
+
+                quote! {
+                    #[doc = #getter_doc]
+                    #[inline]
+                    pub fn #getter_ident(&self) -> core::time::Duration {
+                        #getter_expr
+                    }
+
+                    #[doc = #setter_doc]
+                    ///
+                    /// Value is rounded to the nearest raw value (ties are rounded up) and saturated to the maximum
+                    /// value of the field type.
+                    #[inline]
+                    pub fn #setter_ident(&mut self, value: core::time::Duration) {
+                        const UNIT_NANOS: u128 = #unit_nanos;
+                        let raw = (value.as_nanos() + UNIT_NANOS / 2) / UNIT_NANOS;
+                        #set_raw
+                    }
+                }
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    if accessors.is_empty() {
+        return quote!();
+    }
+
+    quote! {
+        /// Scaled accessors for fixed-point fields.
+        impl #message_struct_ident {
+            #(#accessors)*
+        }
+    }
+}
+
+/// Returns Rust type of a message `field`.
+fn make_field_type(field: &FieldSpec) -> proc_macro2::TokenStream {
+    let field_base_type: syn::Type =
+        syn::parse_str(field.r#type().base_type().rust_type().as_str()).unwrap();
+    let array_length = field.array_length();
+
+    if field.is_string() {
+        let array_length = proc_macro2::Literal::usize_unsuffixed(array_length);
+        return quote! { mavspec::rust::spec::MavString<#array_length> };
+    }
+
+    let item_type = if field.is_enum() {
+        let enum_ident = format_ident!("{}", enum_rust_name(field.enum_name()));
+        quote! { super::super::enums::#enum_ident }
+    } else if let (true, Some(units_name)) =
+        (field.is_quantity(), field.units().and_then(units_type_name))
+    {
+        let units_ident = format_ident!("{}", units_name);
+        quote! {
+            mavspec::rust::spec::units::Quantity<
+                #field_base_type,
+                mavspec::rust::spec::units::#units_ident
+            >
+        }
+    } else {
+        let field_rust_type: syn::Type =
+            syn::parse_str(field.r#type().rust_type().as_str()).unwrap();
+        return quote! { #field_rust_type };
+    };
+
+    if field.is_array() {
+        quote! { [#item_type; #array_length] }
+    } else {
+        item_type
+    }
+}
+
+/// Returns sentinel value for a single item of a field with `invalid` attribute.
+fn invalid_sentinel(field: &FieldSpec) -> Option<&mavinspect::protocol::Value> {
+    match field.invalid()? {
+        MessageFieldInvalidValue::Value(value)
+        | MessageFieldInvalidValue::AllItems(value)
+        | MessageFieldInvalidValue::FirstItem(value) => Some(value),
+        MessageFieldInvalidValue::EnumEntryValue(_) => None,
+    }
+}
+
+/// Returns expression which checks whether raw `value` is equal to `expected` (`NaN` is equal to `NaN`).
+fn make_value_check(
+    expected: &mavinspect::protocol::Value,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match expected {
+        mavinspect::protocol::Value::Float(val) if val.is_nan() => quote! { #value.is_nan() },
+        mavinspect::protocol::Value::Double(val) if val.is_nan() => quote! { #value.is_nan() },
+        _ => {
+            let expected = make_typed_value_expr(expected);
+            quote! { #value == #expected }
+        }
+    }
+}
+
+fn make_invalid_accessors(spec: &MessageImplModuleSpec) -> proc_macro2::TokenStream {
+    let message_struct_ident = spec.ident();
+
+    let accessors = spec
+        .fields()
+        .iter()
+        .filter(|field| field.has_invalid_accessors())
+        .filter_map(|field| {
+            let sentinel = invalid_sentinel(field)?;
+            let field_ident = format_ident!("{}", rust_var_name(field.name()));
+            let getter_ident = format_ident!("{}", scaled_accessor_name(field.name(), "opt"));
+            let setter_ident =
+                format_ident!("set_{}", scaled_accessor_name(field.name(), "opt"));
+            let field_type = make_field_type(field);
+
+            let sentinel_expr = make_typed_value_expr(sentinel);
+            let sentinel_item = if field.is_quantity() {
+                quote! { mavspec::rust::spec::units::Quantity::new(#sentinel_expr) }
+            } else {
+                sentinel_expr
+            };
+            let item_raw = |item: proc_macro2::TokenStream| {
+                if field.is_quantity() {
+                    quote! { #item.raw() }
+                } else {
+                    item
+                }
+            };
+
+            let (is_invalid, sentinel_value, invalid_doc) = match field.invalid()? {
+                MessageFieldInvalidValue::AllItems(_) => {
+                    let array_length = proc_macro2::Literal::usize_unsuffixed(field.array_length());
+                    (
+                        make_value_check(sentinel, item_raw(quote!(item))),
+                        quote! { [#sentinel_item; #array_length] },
+                        "all items are equal to",
+                    )
+                }
+                MessageFieldInvalidValue::FirstItem(_) => {
+                    let array_length = proc_macro2::Literal::usize_unsuffixed(field.array_length());
+                    (
+                        make_value_check(sentinel, item_raw(quote!(self.#field_ident[0]))),
+                        quote! { [#sentinel_item; #array_length] },
+                        "first item is equal to",
+                    )
+                }
+                _ => (
+                    make_value_check(sentinel, item_raw(quote!(self.#field_ident))),
+                    sentinel_item,
+                    "equal to",
+                ),
+            };
+            let is_invalid = if matches!(field.invalid(), Some(MessageFieldInvalidValue::AllItems(_))) {
+                quote! { self.#field_ident.iter().all(|&item| #is_invalid) }
+            } else {
+                is_invalid
+            };
+
+            let sentinel_str = make_typed_value_expr(sentinel).to_string().replace(' ', "");
+            let getter_doc = format!(
+                " Value of [`{field_ident}`](Self::{field_ident}) or [`None`] if {invalid_doc} `{sentinel_str}` (not provided)."
+            );
+            let setter_doc = format!(
+                " Sets [`{field_ident}`](Self::{field_ident}), [`None`] is encoded as `{sentinel_str}` sentinel."
+            );
+
+            Some(quote! {
+                #[doc = #getter_doc]
+                #[inline]
+                pub fn #getter_ident(&self) -> Option<#field_type> {
+                    if #is_invalid {
+                        None
+                    } else {
+                        Some(self.#field_ident)
+                    }
+                }
+
+                #[doc = #setter_doc]
+                #[inline]
+                pub fn #setter_ident(&mut self, value: Option<#field_type>) {
+                    self.#field_ident = value.unwrap_or(#sentinel_value);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if accessors.is_empty() {
+        return quote!();
+    }
+
+    quote! {
+        /// Accessors for fields with invalid (not provided) values.
+        impl #message_struct_ident {
+            #(#accessors)*
+        }
+    }
+}
+
+fn duration_unit_nanos(units: &DurationUnits) -> u128 {
+    match units {
+        DurationUnits::Seconds => 1_000_000_000,
+        DurationUnits::DeciSeconds => 100_000_000,
+        DurationUnits::CentiSeconds => 10_000_000,
+        DurationUnits::MilliSeconds => 1_000_000,
+        DurationUnits::MicroSeconds => 1_000,
+        DurationUnits::NanoSeconds => 1,
+    }
+}
+
 fn generate_tests(spec: &MessageImplModuleSpec) -> proc_macro2::TokenStream {
     if !spec.params().generate_tests {
         return quote!();
     }
 
     let message_struct_ident = spec.ident();
+    let message_id = spec.id();
+    let crc_extra = spec.crc_extra();
+    let payload_v1_size = spec.payload_v1_size();
+    let payload_v2_size = spec.payload_v2_size();
+
+    let const_tests = quote! {
+        #[test]
+        fn constants() {
+            assert_eq!(#message_struct_ident::ID, #message_id);
+            assert_eq!(#message_struct_ident::CRC_EXTRA, #crc_extra);
+            assert_eq!(#message_struct_ident::PAYLOAD_SIZE_V1, #payload_v1_size);
+            assert_eq!(#message_struct_ident::PAYLOAD_SIZE_V2, #payload_v2_size);
+        }
+    };
 
     let v2_tests = quote! {
         #[test]
@@ -216,6 +615,12 @@ fn generate_tests(spec: &MessageImplModuleSpec) -> proc_macro2::TokenStream {
         quote!()
     };
 
+    let scaled_accessor_tests = make_scaled_accessor_tests(spec);
+    let invalid_accessor_tests = make_invalid_accessor_tests(spec);
+    let default_value_tests = make_default_value_tests(spec);
+    let arbitrary_tests = make_arbitrary_tests(spec);
+    let known_answer_tests = make_known_answer_tests(spec);
+
     quote! {
         #[cfg(test)]
         mod tests {
@@ -223,14 +628,449 @@ fn generate_tests(spec: &MessageImplModuleSpec) -> proc_macro2::TokenStream {
 
             use super::*;
 
+            #const_tests
             #v2_tests
             #v1_tests
+            #scaled_accessor_tests
+            #invalid_accessor_tests
+            #default_value_tests
+            #arbitrary_tests
+            #known_answer_tests
+        }
+    }
+}
+
+/// Generates randomized encoding/decoding round-trips for messages produced by `arbitrary`.
+///
+/// Messages are compared by their payloads since they may contain `NaN` values and do not necessarily implement
+/// [`PartialEq`].
+fn make_arbitrary_tests(spec: &MessageImplModuleSpec) -> proc_macro2::TokenStream {
+    if !spec.params().arbitrary {
+        return quote!();
+    }
+
+    let message_struct_ident = spec.ident();
+    let message_id = spec.id();
+    let fill_data = make_pseudo_random_fill(&quote!(state), &quote!(data));
+
+    let field_checks = spec.fields().iter().map(|field| {
+        let field_ident = format_ident!("{}", rust_var_name(field.name()));
+        let field_name = field.name();
+        let (decoded_key, message_key) = match make_comparable_value_expr(field, quote!(item)) {
+            None => (quote!(decoded.#field_ident), quote!(message.#field_ident)),
+            Some(item) if field.is_array() && !field.is_string() => (
+                quote! { decoded.#field_ident.map(|item| #item) },
+                quote! { message.#field_ident.map(|item| #item) },
+            ),
+            Some(_) => (
+                make_comparable_value_expr(field, quote!(decoded.#field_ident)).unwrap(),
+                make_comparable_value_expr(field, quote!(message.#field_ident)).unwrap(),
+            ),
+        };
+        let check = quote! {
+            assert_eq!(#decoded_key, #message_key, "field `{}`", #field_name);
+        };
+
+        if field.is_extension() {
+            quote! {
+                if extensions {
+                    #check
+                }
+            }
+        } else {
+            check
+        }
+    });
+
+    let v1_round_trip = if spec.is_v1_compatible() {
+        quote! {
+            let payload = message.encode(MavLinkVersion::V1).unwrap();
+            assert_eq!(payload.bytes().len(), #message_struct_ident::PAYLOAD_SIZE_V1);
+            let decoded = #message_struct_ident::try_from(&payload).unwrap();
+            assert_fields_eq(&decoded, &message, false);
+            assert_eq!(decoded.encode(MavLinkVersion::V1).unwrap().bytes(), payload.bytes());
+        }
+    } else {
+        quote!()
+    };
+
+    // Zero padding of truncated payloads is not a valid value for enums without zero entry
+    let has_enums = spec
+        .fields()
+        .iter()
+        .any(|field| field.is_enum() && !field.is_bitmask());
+    let allowed_truncation_error = if has_enums {
+        quote! {
+            Err(mavspec::rust::spec::SpecError::InvalidEnumValue { .. }) => {}
+        }
+    } else {
+        quote!()
+    };
+
+    quote! {
+        #[test]
+        #[cfg(feature = "arbitrary")]
+        fn arbitrary_round_trips() {
+            use arbitrary::{Arbitrary, Unstructured};
+            use mavspec::rust::spec::Payload;
+
+            /// Compares fields by value, floats are compared bitwise (i.e. `NaN` is equal to itself).
+            #[allow(unused_variables)]
+            fn assert_fields_eq(decoded: &#message_struct_ident, message: &#message_struct_ident, extensions: bool) {
+                #(#field_checks)*
+            }
+
+            let mut state: u64 = 0x9E37_79B9_7F4A_7C15 ^ #message_id as u64;
+            let mut data = [0u8; 1024];
+
+            for _ in 0..32 {
+                #fill_data
+                let message = #message_struct_ident::arbitrary(&mut Unstructured::new(&data)).unwrap();
+
+                #v1_round_trip
+
+                let payload = message.encode(MavLinkVersion::V2).unwrap();
+                let decoded = #message_struct_ident::try_from(&payload).unwrap();
+                assert_fields_eq(&decoded, &message, true);
+                assert_eq!(decoded.encode(MavLinkVersion::V2).unwrap().bytes(), payload.bytes());
+
+                // Trailing zero bytes are truncated, but the first byte is always present
+                assert!(payload.bytes().len() <= #message_struct_ident::PAYLOAD_SIZE_V2);
+                assert!(payload.bytes().len() == 1 || payload.bytes().last() != Some(&0));
+
+                // Truncated payloads are padded with zeros
+                for len in 1..payload.bytes().len() {
+                    let truncated = Payload::new(#message_struct_ident::ID, &payload.bytes()[..len], MavLinkVersion::V2);
+                    match #message_struct_ident::try_from(&truncated) {
+                        Ok(decoded) => {
+                            assert_eq!(decoded.encode(MavLinkVersion::V2).unwrap().bytes(), truncated.bytes());
+                        }
+                        #allowed_truncation_error
+                        Err(err) => panic!("can't decode payload truncated to {len} bytes: {err:?}"),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns expression which converts a single (non-array) field `value` into a comparable value with `Debug`.
+///
+/// Floats are converted to bits, enums and bitmasks to their numeric values, and strings to raw bytes. Returns
+/// [`None`] if value can be compared as it is.
+fn make_comparable_value_expr(
+    field: &FieldSpec,
+    value: proc_macro2::TokenStream,
+) -> Option<proc_macro2::TokenStream> {
+    if field.is_string() {
+        return Some(quote! { *#value.raw() });
+    }
+    if field.is_bitmask() {
+        return Some(quote! { #value.bits() });
+    }
+    if field.is_enum() {
+        return Some(quote! { #value as u64 });
+    }
+
+    let is_float = matches!(field.r#type().base_type(), MavType::Float | MavType::Double);
+    match (field.is_quantity(), is_float) {
+        (true, true) => Some(quote! { #value.raw().to_bits() }),
+        (true, false) => Some(quote! { #value.raw() }),
+        (false, true) => Some(quote! { #value.to_bits() }),
+        (false, false) => None,
+    }
+}
+
+/// Generates known-answer tests from test vectors: messages should be encoded exactly into expected payloads and
+/// expected payloads should be decoded into equivalent messages.
+fn make_known_answer_tests(spec: &MessageImplModuleSpec) -> proc_macro2::TokenStream {
+    let message_struct_ident = spec.ident();
+
+    let checks = spec
+        .params()
+        .test_vectors
+        .iter()
+        .filter(|vector| vector.dialect() == spec.dialect_name() && vector.message() == spec.name())
+        .map(|vector| {
+            let fields = vector.fields().iter().map(|(name, value)| {
+                let field = spec
+                    .fields()
+                    .iter()
+                    .find(|field| field.name() == name)
+                    .unwrap();
+                let field_ident = format_ident!("{}", rust_var_name(field.name()));
+                let value = make_test_vector_value_expr(field, value);
+                quote! { #field_ident: #value }
+            });
+            let version = match vector.version() {
+                1 => quote!(MavLinkVersion::V1),
+                _ => quote!(MavLinkVersion::V2),
+            };
+            let expected = vector.payload();
+            let source = vector.source();
+
+            quote! {
+                {
+                    let message = #message_struct_ident {
+                        #(#fields,)*
+                        ..Default::default()
+                    };
+                    let expected: &[u8] = &[#(#expected),*];
+
+                    let payload = message.encode(#version).unwrap();
+                    assert_eq!(payload.bytes(), expected, "encoding of {}", #source);
+
+                    let decoded = #message_struct_ident::try_from(
+                        &Payload::new(#message_struct_ident::ID, expected, #version)
+                    ).unwrap();
+                    assert_eq!(decoded.encode(#version).unwrap().bytes(), expected, "decoding of {}", #source);
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if checks.is_empty() {
+        return quote!();
+    }
+
+    quote! {
+        #[test]
+        #[allow(clippy::needless_update)]
+        fn known_answers() {
+            use mavspec::rust::spec::Payload;
+
+            #(#checks)*
+        }
+    }
+}
+
+/// Returns expression for a field `value` from a test vector within `tests` module of a message.
+fn make_test_vector_value_expr(
+    field: &FieldSpec,
+    value: &TestVectorValue,
+) -> proc_macro2::TokenStream {
+    let make_item = |value: &mavinspect::protocol::Value| {
+        let raw = make_typed_value_expr(value);
+        let enum_ident = format_ident!("{}", enum_rust_name(field.enum_name()));
+
+        if field.is_bitmask() {
+            quote! { super::super::super::enums::#enum_ident::from_bits_retain(#raw) }
+        } else if field.is_enum() {
+            quote! { super::super::super::enums::#enum_ident::try_from(#raw).unwrap() }
+        } else if field.is_quantity() {
+            quote! { mavspec::rust::spec::units::Quantity::new(#raw) }
+        } else {
+            raw
+        }
+    };
+
+    match value {
+        TestVectorValue::Scalar(value) => make_item(value),
+        TestVectorValue::Array(items) => {
+            let items = items.iter().map(make_item);
+            quote! { [#(#items),*] }
+        }
+        TestVectorValue::String(value) => {
+            quote! { mavspec::rust::spec::MavString::try_from(#value).unwrap() }
+        }
+    }
+}
+
+fn make_default_value_tests(spec: &MessageImplModuleSpec) -> proc_macro2::TokenStream {
+    let message_struct_ident = spec.ident();
+
+    let checks = spec
+        .fields()
+        .iter()
+        .filter(|field| !field.is_enum() && !matches!(field.r#type().base_type(), MavType::Char))
+        .filter_map(|field| {
+            let value = field.default()?;
+            let item = if field.is_array() {
+                quote!(item)
+            } else {
+                let field_ident = format_ident!("{}", rust_var_name(field.name()));
+                quote!(message.#field_ident)
+            };
+            let item = if field.is_quantity() {
+                quote! { #item.raw() }
+            } else {
+                item
+            };
+            let check = make_value_check(value, item);
+
+            Some(if field.is_array() {
+                let field_ident = format_ident!("{}", rust_var_name(field.name()));
+                quote! {
+                    assert!(message.#field_ident.iter().all(|&item| #check));
+                }
+            } else {
+                quote! {
+                    assert!(#check);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if checks.is_empty() {
+        return quote!();
+    }
+
+    quote! {
+        #[test]
+        fn default_values() {
+            let message = #message_struct_ident::default();
+
+            #(#checks)*
+        }
+    }
+}
+
+fn make_invalid_accessor_tests(spec: &MessageImplModuleSpec) -> proc_macro2::TokenStream {
+    use mavinspect::protocol::Value;
+
+    let message_struct_ident = spec.ident();
+
+    let checks = spec
+        .fields()
+        .iter()
+        .filter(|field| field.has_invalid_accessors())
+        .filter_map(|field| {
+            let sentinel = invalid_sentinel(field)?;
+            let getter_ident = format_ident!("{}", scaled_accessor_name(field.name(), "opt"));
+            let setter_ident = format_ident!("set_{}", scaled_accessor_name(field.name(), "opt"));
+
+            // Any value which differs from the sentinel
+            let valid_item = match sentinel {
+                Value::Int8(0) => make_typed_value_expr(&Value::Int8(1)),
+                Value::Int16(0) => make_typed_value_expr(&Value::Int16(1)),
+                Value::Int32(0) => make_typed_value_expr(&Value::Int32(1)),
+                Value::Int64(0) => make_typed_value_expr(&Value::Int64(1)),
+                Value::UInt8(0) => make_typed_value_expr(&Value::UInt8(1)),
+                Value::UInt16(0) => make_typed_value_expr(&Value::UInt16(1)),
+                Value::UInt32(0) => make_typed_value_expr(&Value::UInt32(1)),
+                Value::UInt64(0) => make_typed_value_expr(&Value::UInt64(1)),
+                Value::Float(val) if *val == 0.0 => make_typed_value_expr(&Value::Float(1.0)),
+                Value::Double(val) if *val == 0.0 => make_typed_value_expr(&Value::Double(1.0)),
+                _ => quote! { Default::default() },
+            };
+            let valid_item = if field.is_quantity() {
+                quote! { mavspec::rust::spec::units::Quantity::new(#valid_item) }
+            } else {
+                valid_item
+            };
+            let valid_value = if field.is_array() {
+                let array_length = proc_macro2::Literal::usize_unsuffixed(field.array_length());
+                quote! { [#valid_item; #array_length] }
+            } else {
+                valid_item
+            };
+
+            Some(quote! {
+                message.#setter_ident(None);
+                assert!(message.#getter_ident().is_none());
+                message.#setter_ident(Some(#valid_value));
+                assert!(message.#getter_ident().is_some());
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if checks.is_empty() {
+        return quote!();
+    }
+
+    quote! {
+        #[test]
+        fn invalid_accessors() {
+            let mut message = #message_struct_ident::default();
+
+            #(#checks)*
+        }
+    }
+}
+
+fn make_scaled_accessor_tests(spec: &MessageImplModuleSpec) -> proc_macro2::TokenStream {
+    let message_struct_ident = spec.ident();
+
+    let checks = spec.fields().iter().filter_map(|field| {
+        let scale = field.scale()?;
+        let field_type: syn::Type = syn::parse_str(field.r#type().rust_type().as_str()).unwrap();
+        let raw_value = make_raw_field_value(&quote!(message), field);
+        let set_three = make_raw_field_assign(&quote!(message), field, quote!(3));
+
+        Some(match scale {
+            FieldScale::Fixed {
+                suffix,
+                multiplier,
+                divisor,
+                ..
+            } => {
+                let getter_ident = format_ident!("{}", scaled_accessor_name(field.name(), suffix));
+                let setter_ident =
+                    format_ident!("set_{}", scaled_accessor_name(field.name(), suffix));
+                let unit = proc_macro2::Literal::f64_unsuffixed(*multiplier as f64 / *divisor as f64);
+
+                quote! {
+                    #set_three
+                    assert!((message.#getter_ident() - 3.0 * #unit).abs() < #unit * 1e-9);
+                    message.#setter_ident(message.#getter_ident());
+                    assert_eq!(#raw_value, 3);
+
+                    message.#setter_ident(3.25 * #unit);
+                    assert_eq!(#raw_value, 3);
+                    message.#setter_ident(3.75 * #unit);
+                    assert_eq!(#raw_value, 4);
+
+                    message.#setter_ident(f64::MAX);
+                    assert_eq!(#raw_value, #field_type::MAX);
+                    message.#setter_ident(f64::MIN);
+                    assert_eq!(#raw_value, #field_type::MIN);
+                    message.#setter_ident(f64::NAN);
+                    assert_eq!(#raw_value, 0);
+                }
+            }
+            FieldScale::Duration { units } => {
+                let getter_ident = format_ident!("{}", scaled_accessor_name(field.name(), "duration"));
+                let setter_ident =
+                    format_ident!("set_{}", scaled_accessor_name(field.name(), "duration"));
+                let unit_nanos = proc_macro2::Literal::u64_unsuffixed(duration_unit_nanos(units) as u64);
+
+                quote! {
+                    #set_three
+                    assert_eq!(message.#getter_ident(), core::time::Duration::from_nanos(3 * #unit_nanos));
+                    message.#setter_ident(message.#getter_ident());
+                    assert_eq!(#raw_value, 3);
+
+                    message.#setter_ident(core::time::Duration::from_nanos(3 * #unit_nanos + #unit_nanos / 4));
+                    assert_eq!(#raw_value, 3);
+
+                    message.#setter_ident(core::time::Duration::MAX);
+                    assert_eq!(#raw_value, #field_type::MAX);
+                }
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    if checks.is_empty() {
+        return quote!();
+    }
+
+    quote! {
+        #[test]
+        #[allow(clippy::field_reassign_with_default)]
+        fn scaled_accessors() {
+            let mut message = #message_struct_ident::default();
+
+            #(#checks)*
         }
     }
 }
 
 /// Inherited message module template.
 pub(crate) fn inherited_message_module(spec: &MessageInheritedModuleSpec) -> syn::File {
+    if spec.params().output_layout == OutputLayout::Workspace {
+        return inherited_message_crate_module(spec);
+    }
+
     let dialect_mod_name = dialect_mod_name(spec.dialect_name().into());
 
     let module_doc_comment = format!(
@@ -269,6 +1109,39 @@ pub(crate) fn inherited_message_module(spec: &MessageInheritedModuleSpec) -> syn
 
         #[doc = #reexported_from_dialect_doc_comment]
         pub use dialect::messages::#message_mod_ident::spec;
+        #[doc = #reexported_from_dialect_doc_comment]
+        pub use dialect::messages::#message_mod_ident::FIELDS;
+    })
+    .unwrap()
+}
+
+/// Inherited message module template for dialect crates, re-exports message from the crate of original dialect.
+fn inherited_message_crate_module(spec: &MessageInheritedModuleSpec) -> syn::File {
+    let dialect_crate_name = dialect_crate_name(spec.dialect_name());
+    let module_doc_comment = format!(
+        " MAVLink message `{}` inherited from [`{dialect_crate_name}`] dialect crate.",
+        spec.message_name(),
+    );
+
+    let dialect_crate_ident = format_ident!("{}", &dialect_crate_name);
+    let message_mod_ident = format_ident!("{}", message_mod_name(spec.message_name()));
+    let message_struct_ident = format_ident!("{}", message_struct_name(spec.message_name()));
+
+    syn::parse2(quote! {
+        #![doc = #module_doc_comment]
+
+        use mavspec::rust::spec::MessageInfo;
+        use mavspec::rust::spec::types::{CrcExtra, MessageId};
+
+        #[doc(inline)]
+        pub use #dialect_crate_ident::messages::#message_mod_ident::{spec, #message_struct_ident, FIELDS};
+
+        /// Message ID.
+        pub(crate) const MESSAGE_ID: MessageId = #message_struct_ident::ID;
+        /// `CRC_EXTRA` calculated from message XML definition.
+        pub(crate) const CRC_EXTRA: CrcExtra = #message_struct_ident::CRC_EXTRA;
+        /// Generic message info that contains all message metadata.
+        pub(crate) const MESSAGE_INFO: MessageInfo = MessageInfo::new(MESSAGE_ID, CRC_EXTRA);
     })
     .unwrap()
 }
@@ -1,10 +1,15 @@
 use crate::conventions::{
-    dialect_enum_name, message_mod_name, message_struct_name, messages_enum_entry_name,
+    dialect_enum_name, message_accessor_name, message_handler_name, message_mod_name,
+    message_struct_name, messages_enum_entry_name,
 };
 use quote::{format_ident, quote};
 
 use crate::specs::dialects::dialect::DialectModuleSpec;
 use crate::specs::Spec;
+use crate::templates::helpers::{
+    make_arbitrary_derive_annotation, make_pseudo_random_fill, make_serde_derive_annotation,
+};
+use crate::type_attributes::{make_type_attributes, message_has_floats, TypeKind};
 
 pub fn dialect_module(specs: &DialectModuleSpec) -> syn::File {
     let leading_module_comment = format!("# MAVLink dialect `{}`", specs.name());
@@ -27,6 +32,12 @@ pub fn dialect_module(specs: &DialectModuleSpec) -> syn::File {
 
     let message_spec_const_ident = format_ident!("__MAVSPEC__MESSAGES");
     let messages_count = specs.messages().len();
+    let max_payload_size = specs
+        .messages()
+        .iter()
+        .map(|msg| msg.size_v2())
+        .max()
+        .unwrap_or_default();
     let dialect_spec_const_ident = format_ident!("__MAVSPEC__DIALECT_SPEC");
 
     let messages_specs = specs.messages().iter().map(|msg| {
@@ -37,13 +48,38 @@ pub fn dialect_module(specs: &DialectModuleSpec) -> syn::File {
         }
     });
 
+    let serde_enabled = specs.params().serde;
+    let derive_serde = make_serde_derive_annotation(serde_enabled);
+    // Arbitrary can't be derived for enums without variants
+    let derive_arbitrary =
+        make_arbitrary_derive_annotation(specs.params().arbitrary && !specs.messages().is_empty());
+    let serde_tag = if serde_enabled {
+        quote! {
+            ///
+            /// When `serde` feature is enabled, messages are represented as
+            /// `{"message": "HEARTBEAT", "fields": {...}}`, where `message` is a MAVLink message name.
+            #[cfg_attr(feature = "serde", serde(tag = "message", content = "fields"))]
+        }
+    } else {
+        quote!()
+    };
+
     let messages_variants = specs.messages().iter().map(|msg| {
         let comment = format!(" MAVLink message `{}`.", msg.name());
         let messages_enum_entry_name = format_ident!("{}", messages_enum_entry_name(msg.name()));
         let message_struct_name = format_ident!("{}", message_struct_name(msg.name()));
+        let serde_rename = if serde_enabled {
+            let name = msg.name();
+            quote! {
+                #[cfg_attr(feature = "serde", serde(rename = #name))]
+            }
+        } else {
+            quote!()
+        };
 
         quote! {
             #[doc = #comment]
+            #serde_rename
             #messages_enum_entry_name(messages::#message_struct_name),
         }
     });
@@ -57,6 +93,12 @@ pub fn dialect_module(specs: &DialectModuleSpec) -> syn::File {
     });
 
     let dialect_enum_ident = format_ident!("{}", dialect_enum_name(specs.name()));
+    let type_attributes = make_type_attributes(
+        &specs.params().type_attributes,
+        TypeKind::Dialect,
+        specs.name(),
+        specs.messages().iter().any(|msg| message_has_floats(msg)),
+    );
 
     let message_spec_id_arms = specs.messages().iter().map(|msg| {
         let message_mod_name = format_ident!("{}", message_mod_name(msg.name()));
@@ -108,6 +150,78 @@ pub fn dialect_module(specs: &DialectModuleSpec) -> syn::File {
         }
     });
 
+    let allow_unreachable = quote! {
+        #[allow(unreachable_patterns)]
+        #[allow(unreachable_code)]
+    };
+
+    let message_name_arms = specs.messages().iter().map(|msg| {
+        let messages_enum_entry_name = format_ident!("{}", messages_enum_entry_name(msg.name()));
+        let message_name = msg.name();
+
+        quote! {
+            #dialect_enum_ident::#messages_enum_entry_name(_) => #message_name,
+        }
+    });
+
+    let stream_key_arms = specs.messages().iter().map(|msg| {
+        let messages_enum_entry_name = format_ident!("{}", messages_enum_entry_name(msg.name()));
+
+        quote! {
+            #dialect_enum_ident::#messages_enum_entry_name(message) => mavspec::rust::spec::Message::stream_key(message),
+        }
+    });
+
+    let message_accessors = specs.messages().iter().map(|msg| {
+        let messages_enum_entry_name = format_ident!("{}", messages_enum_entry_name(msg.name()));
+        let message_struct_name = format_ident!("{}", message_struct_name(msg.name()));
+        let accessor_ident = format_ident!("{}", message_accessor_name(msg.name()));
+        let comment = format!(
+            " Returns reference to [`messages::{message_struct_name}`] if this is a `{}` message.",
+            msg.name()
+        );
+
+        quote! {
+            #[doc = #comment]
+            #[inline]
+            pub fn #accessor_ident(&self) -> Option<&messages::#message_struct_name> {
+                #allow_unreachable
+                match self {
+                    #dialect_enum_ident::#messages_enum_entry_name(message) => Some(message),
+                    _ => None,
+                }
+            }
+        }
+    });
+
+    let message_conversions = specs.messages().iter().map(|msg| {
+        let messages_enum_entry_name = format_ident!("{}", messages_enum_entry_name(msg.name()));
+        let message_struct_name = format_ident!("{}", message_struct_name(msg.name()));
+
+        quote! {
+            impl core::convert::From<messages::#message_struct_name> for #dialect_enum_ident {
+                fn from(value: messages::#message_struct_name) -> Self {
+                    #dialect_enum_ident::#messages_enum_entry_name(value)
+                }
+            }
+
+            impl core::convert::TryFrom<#dialect_enum_ident> for messages::#message_struct_name {
+                type Error = #dialect_enum_ident;
+
+                /// Extracts message from dialect enum. Returns original value if it contains another message.
+                fn try_from(value: #dialect_enum_ident) -> Result<Self, Self::Error> {
+                    #allow_unreachable
+                    match value {
+                        #dialect_enum_ident::#messages_enum_entry_name(message) => Ok(message),
+                        _ => Err(value),
+                    }
+                }
+            }
+        }
+    });
+
+    let handlers = make_handlers(specs);
+
     let tests = if specs.params().generate_tests {
         let ids = specs.messages().iter().map(|msg| {
             let id = msg.id();
@@ -116,6 +230,34 @@ pub fn dialect_module(specs: &DialectModuleSpec) -> syn::File {
             }
         });
 
+        let arbitrary_tests = if specs.params().arbitrary && !specs.messages().is_empty() {
+            let fill_data = make_pseudo_random_fill(&quote!(state), &quote!(data));
+
+            quote! {
+                #[test]
+                #[cfg(feature = "arbitrary")]
+                fn arbitrary_round_trips() {
+                    use arbitrary::{Arbitrary, Unstructured};
+
+                    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+                    let mut data = [0u8; 1024];
+
+                    for _ in 0..#dialect_enum_ident::MESSAGE_COUNT * 4 {
+                        #fill_data
+                        let message = #dialect_enum_ident::arbitrary(&mut Unstructured::new(&data)).unwrap();
+
+                        let payload = message.encode(MavLinkVersion::V2).unwrap();
+                        let decoded = #dialect_enum_ident::decode(&payload).unwrap();
+
+                        assert_eq!(decoded.message_name(), message.message_name());
+                        assert_eq!(decoded.encode(MavLinkVersion::V2).unwrap().bytes(), payload.bytes());
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
         quote! {
             #[cfg(test)]
             mod tests {
@@ -123,25 +265,23 @@ pub fn dialect_module(specs: &DialectModuleSpec) -> syn::File {
 
                 #[test]
                 fn retrieve_message_info() {
-                    for id in [
+                    let ids: &[u32] = &[
                         #(#ids,)*
-                    ] {
+                    ];
+                    for &id in ids {
                         let msg_info = #dialect_enum_ident::message_info(id);
                         assert!(msg_info.is_ok());
                         assert_eq!(msg_info.unwrap().id(), id);
                     }
                 }
+
+                #arbitrary_tests
             }
         }
     } else {
         quote!()
     };
 
-    let allow_unreachable = quote! {
-        #[allow(unreachable_patterns)]
-        #[allow(unreachable_code)]
-    };
-
     syn::parse2(quote! {
         #![doc = #leading_module_comment]
 
@@ -166,12 +306,48 @@ pub fn dialect_module(specs: &DialectModuleSpec) -> syn::File {
 
         #[doc = #messages_enum_comment]
         #[derive(core::clone::Clone, core::fmt::Debug)]
-        // {{#if params.serde}}#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]{{/if}}
+        #derive_serde
+        #serde_tag
+        #derive_arbitrary
+        #type_attributes
         #[allow(clippy::large_enum_variant)]
         pub enum #dialect_enum_ident {
             #(#messages_variants)*
         }
 
+        impl #dialect_enum_ident {
+            /// Number of messages in this dialect.
+            pub const MESSAGE_COUNT: usize = #messages_count;
+
+            /// Maximum `MAVLink 2` payload size among messages of this dialect.
+            ///
+            /// Can be used to allocate buffers at compile time.
+            pub const MAX_PAYLOAD_SIZE: usize = #max_payload_size;
+
+            /// MAVLink name of the contained message (i.e. `HEARTBEAT`).
+            pub fn message_name(&self) -> &'static str {
+                match self {
+                    #(#message_name_arms)*
+                }
+            }
+
+            /// Key of a message stream which combines message ID and message instance.
+            ///
+            /// Messages with several instances (i.e. `BATTERY_STATUS` for different batteries) have a field marked
+            /// with `instance="true"` in XML definition, such messages have `instance()` accessor.
+            pub fn stream_key(&self) -> mavspec::rust::spec::StreamKey {
+                match self {
+                    #(#stream_key_arms)*
+                }
+            }
+
+            #(#message_accessors)*
+        }
+
+        #(#message_conversions)*
+
+        #handlers
+
         impl Dialect for #dialect_enum_ident {
             /// Dialect name as it appears in XML definition.
             #[inline]
@@ -270,3 +446,136 @@ pub fn dialect_module(specs: &DialectModuleSpec) -> syn::File {
     })
     .unwrap()
 }
+
+/// Message handler traits and dispatch methods.
+fn make_handlers(specs: &DialectModuleSpec) -> proc_macro2::TokenStream {
+    let dialect_enum_ident = format_ident!("{}", dialect_enum_name(specs.name()));
+    let handler_ident = format_ident!("{}Handler", dialect_enum_ident);
+    let async_handler_ident = format_ident!("{}AsyncHandler", dialect_enum_ident);
+
+    let handler_doc = format!(" Handler for messages of `{}` dialect.", specs.name());
+    let dispatch_doc =
+        format!(" Dispatches message to the corresponding method of [`{handler_ident}`].");
+
+    let handler_methods = specs.messages().iter().map(|msg| {
+        let message_struct_name = format_ident!("{}", message_struct_name(msg.name()));
+        let handler_method_ident = format_ident!("{}", message_handler_name(msg.name()));
+        let comment = format!(" Handles `{}` message.", msg.name());
+
+        quote! {
+            #[doc = #comment]
+            ///
+            /// Falls through to [`Self::on_unhandled`] by default.
+            fn #handler_method_ident(&mut self, message: &messages::#message_struct_name) {
+                self.on_unhandled(message)
+            }
+        }
+    });
+
+    let dispatch_arms = specs.messages().iter().map(|msg| {
+        let messages_enum_entry_name = format_ident!("{}", messages_enum_entry_name(msg.name()));
+        let handler_method_ident = format_ident!("{}", message_handler_name(msg.name()));
+
+        quote! {
+            #dialect_enum_ident::#messages_enum_entry_name(message) => handler.#handler_method_ident(message),
+        }
+    });
+
+    let async_handlers = if specs.params().async_handlers {
+        let async_handler_doc =
+            format!(" Async handler for messages of `{}` dialect.", specs.name());
+        let dispatch_async_doc = format!(
+            " Dispatches message to the corresponding method of [`{async_handler_ident}`]."
+        );
+
+        let async_handler_methods = specs.messages().iter().map(|msg| {
+            let message_struct_name = format_ident!("{}", message_struct_name(msg.name()));
+            let handler_method_ident = format_ident!("{}", message_handler_name(msg.name()));
+            let comment = format!(" Handles `{}` message.", msg.name());
+
+            quote! {
+                #[doc = #comment]
+                ///
+                /// Falls through to [`Self::on_unhandled`] by default.
+                fn #handler_method_ident(
+                    &mut self,
+                    message: &messages::#message_struct_name,
+                ) -> impl core::future::Future<Output = ()> + Send {
+                    async move { self.on_unhandled(message).await }
+                }
+            }
+        });
+
+        let dispatch_async_arms = specs.messages().iter().map(|msg| {
+            let messages_enum_entry_name =
+                format_ident!("{}", messages_enum_entry_name(msg.name()));
+            let handler_method_ident = format_ident!("{}", message_handler_name(msg.name()));
+
+            quote! {
+                #dialect_enum_ident::#messages_enum_entry_name(message) => {
+                    handler.#handler_method_ident(message).await
+                }
+            }
+        });
+
+        quote! {
+            #[doc = #async_handler_doc]
+            ///
+            /// Each method has a default implementation which calls [`Self::on_unhandled`].
+            #[cfg(feature = "async")]
+            pub trait #async_handler_ident: Send {
+                /// Handles messages without dedicated handler.
+                ///
+                /// Does nothing by default.
+                fn on_unhandled(
+                    &mut self,
+                    message: &(dyn mavspec::rust::spec::Message + Sync),
+                ) -> impl core::future::Future<Output = ()> + Send {
+                    let _ = message;
+                    async {}
+                }
+
+                #(#async_handler_methods)*
+            }
+
+            #[cfg(feature = "async")]
+            impl #dialect_enum_ident {
+                #[doc = #dispatch_async_doc]
+                pub async fn dispatch_async<H: #async_handler_ident>(&self, handler: &mut H) {
+                    match self {
+                        #(#dispatch_async_arms)*
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    quote! {
+        #[doc = #handler_doc]
+        ///
+        /// Each method has a default implementation which calls [`Self::on_unhandled`].
+        pub trait #handler_ident {
+            /// Handles messages without dedicated handler.
+            ///
+            /// Does nothing by default.
+            fn on_unhandled(&mut self, message: &dyn mavspec::rust::spec::Message) {
+                let _ = message;
+            }
+
+            #(#handler_methods)*
+        }
+
+        impl #dialect_enum_ident {
+            #[doc = #dispatch_doc]
+            pub fn dispatch<H: #handler_ident>(&self, handler: &mut H) {
+                match self {
+                    #(#dispatch_arms)*
+                }
+            }
+        }
+
+        #async_handlers
+    }
+}
@@ -26,6 +26,7 @@ pub fn dialects_root_module(spec: &DialectsRootModuleSpec) -> syn::File {
 
     syn::parse2(quote! {
         #![warn(missing_docs)]
+        #![allow(deprecated)]
         #![deny(rustdoc::broken_intra_doc_links)]
         //! # Autogenerated MAVLink dialects
         //!
@@ -1,4 +1,5 @@
-use quote::quote;
+use mavinspect::protocol::{MavType, Value};
+use quote::{format_ident, quote, ToTokens};
 
 pub(crate) fn make_serde_derive_annotation(enabled: bool) -> proc_macro2::TokenStream {
     if enabled {
@@ -9,3 +10,152 @@ pub(crate) fn make_serde_derive_annotation(enabled: bool) -> proc_macro2::TokenS
         quote!()
     }
 }
+
+pub(crate) fn make_arbitrary_derive_annotation(enabled: bool) -> proc_macro2::TokenStream {
+    if enabled {
+        quote! {
+            #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+        }
+    } else {
+        quote!()
+    }
+}
+
+/// Makes statement which fills `data` byte array with deterministic pseudo-random bytes (`xorshift64*`) using
+/// `u64` `state`.
+///
+/// Used by generated tests to feed `arbitrary::Unstructured`, so test failures are reproducible.
+pub(crate) fn make_pseudo_random_fill(
+    state: &proc_macro2::TokenStream,
+    data: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        for byte in #data.iter_mut() {
+            #state ^= #state >> 12;
+            #state ^= #state << 25;
+            #state ^= #state >> 27;
+            *byte = (#state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 56) as u8;
+        }
+    }
+}
+
+/// Makes `#[deprecated]` attribute for deprecated or work-in-progress entities.
+pub(crate) fn make_deprecated_annotation(note: Option<&str>) -> proc_macro2::TokenStream {
+    match note {
+        Some(note) => quote! { #[deprecated(note = #note)] },
+        None => quote!(),
+    }
+}
+
+pub(crate) fn make_f32_value_expr(value: &Value) -> proc_macro2::TokenStream {
+    make_float_value_expr(value, "f32")
+}
+
+pub(crate) fn make_f64_value_expr(value: &Value) -> proc_macro2::TokenStream {
+    make_float_value_expr(value, "f64")
+}
+
+/// Makes expression for `value` in its own Rust type (i.e. `65535u16`, `-1i32`, `f32::NAN`, `u16::MAX`).
+pub(crate) fn make_typed_value_expr(value: &Value) -> proc_macro2::TokenStream {
+    let expr = match value {
+        Value::Int8(val) => format!("{val}i8"),
+        Value::Int16(val) => format!("{val}i16"),
+        Value::Int32(val) => format!("{val}i32"),
+        Value::Int64(val) => format!("{val}i64"),
+        Value::UInt8(val) => format!("{val}u8"),
+        Value::UInt16(val) => format!("{val}u16"),
+        Value::UInt32(val) => format!("{val}u32"),
+        Value::UInt64(val) => format!("{val}u64"),
+        Value::Char(val) => format!("{val}u8"),
+        Value::Float(_) => return make_f32_value_expr(value),
+        Value::Double(_) => return make_f64_value_expr(value),
+        Value::Max(mav_type) => format!("{}::MAX", mav_type.base_type().rust_type()),
+    };
+
+    syn::parse_str::<syn::Expr>(expr.as_str())
+        .unwrap()
+        .into_token_stream()
+}
+
+/// Makes `mavspec::rust::spec::Sentinel` expression which keeps integer `value` exact.
+pub(crate) fn make_sentinel_expr(value: &Value) -> proc_macro2::TokenStream {
+    let int = |value: i64| {
+        let literal = proc_macro2::Literal::i64_unsuffixed(value.abs());
+        if value < 0 {
+            quote! { mavspec::rust::spec::Sentinel::Int(-#literal) }
+        } else {
+            quote! { mavspec::rust::spec::Sentinel::Int(#literal) }
+        }
+    };
+    let uint = |value: u64| {
+        let literal = proc_macro2::Literal::u64_unsuffixed(value);
+        quote! { mavspec::rust::spec::Sentinel::UInt(#literal) }
+    };
+    let float = |value: &Value| {
+        let value = make_f64_value_expr(value);
+        quote! { mavspec::rust::spec::Sentinel::Float(#value) }
+    };
+
+    match value {
+        Value::Int8(val) => int(*val as i64),
+        Value::Int16(val) => int(*val as i64),
+        Value::Int32(val) => int(*val as i64),
+        Value::Int64(val) => int(*val),
+        Value::UInt8(val) => uint(*val as u64),
+        Value::UInt16(val) => uint(*val as u64),
+        Value::UInt32(val) => uint(*val as u64),
+        Value::UInt64(val) => uint(*val),
+        Value::Char(val) => uint(*val as u64),
+        Value::Float(_) | Value::Double(_) => float(value),
+        Value::Max(mav_type) => match mav_type.base_type() {
+            MavType::Int8 => int(i8::MAX as i64),
+            MavType::Int16 => int(i16::MAX as i64),
+            MavType::Int32 => int(i32::MAX as i64),
+            MavType::Int64 => int(i64::MAX),
+            MavType::Float | MavType::Double => float(value),
+            MavType::UInt16 => uint(u16::MAX as u64),
+            MavType::UInt32 => uint(u32::MAX as u64),
+            MavType::UInt64 => uint(u64::MAX),
+            _ => uint(u8::MAX as u64),
+        },
+    }
+}
+
+fn make_float_value_expr(value: &Value, float_type: &str) -> proc_macro2::TokenStream {
+    let float_type = format_ident!("{float_type}");
+    let value = match value {
+        Value::Int8(val) => *val as f64,
+        Value::Int16(val) => *val as f64,
+        Value::Int32(val) => *val as f64,
+        Value::Int64(val) => *val as f64,
+        Value::UInt8(val) => *val as f64,
+        Value::UInt16(val) => *val as f64,
+        Value::UInt32(val) => *val as f64,
+        Value::UInt64(val) => *val as f64,
+        Value::Float(val) => *val as f64,
+        Value::Double(val) => *val,
+        Value::Char(val) => *val as f64,
+        Value::Max(mav_type) => {
+            let rust_type = format_ident!("{}", mav_type.base_type().rust_type());
+            return quote! { #rust_type::MAX as #float_type };
+        }
+    };
+
+    if value.is_nan() {
+        quote! { #float_type::NAN }
+    } else if value.is_infinite() && value.is_sign_positive() {
+        quote! { #float_type::INFINITY }
+    } else if value.is_infinite() {
+        quote! { #float_type::NEG_INFINITY }
+    } else {
+        let literal = match float_type.to_string().as_str() {
+            "f32" => proc_macro2::Literal::f32_suffixed(value.abs() as f32),
+            _ => proc_macro2::Literal::f64_suffixed(value.abs()),
+        };
+        if value.is_sign_negative() {
+            quote! { -#literal }
+        } else {
+            quote! { #literal }
+        }
+    }
+}
@@ -3,3 +3,4 @@ pub(crate) use root::root_module;
 
 pub(crate) mod dialects;
 pub(crate) mod helpers;
+pub(crate) mod workspace;
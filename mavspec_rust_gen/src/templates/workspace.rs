@@ -0,0 +1,136 @@
+use quote::quote;
+
+use crate::specs::workspace::{DialectCrateSpec, WorkspaceSpec};
+use crate::specs::Spec;
+
+/// Versions of optional dependencies of generated crates.
+const SERDE_VERSION: &str = "1.0.193";
+const SERDE_ARRAYS_VERSION: &str = "0.1.0";
+const ARBITRARY_VERSION: &str = "1.3.2";
+
+/// Cargo workspace manifest template.
+pub(crate) fn workspace_manifest(spec: &WorkspaceSpec) -> String {
+    let members = spec
+        .members()
+        .iter()
+        .map(|member| format!("    {},\n", toml_string(member)))
+        .collect::<String>();
+
+    format!(
+        "# Autogenerated by MAVSpec: one crate per MAVLink dialect.\n\
+         [workspace]\n\
+         resolver = \"2\"\n\
+         members = [\n{members}]\n"
+    )
+}
+
+/// Dialect crate manifest template.
+pub(crate) fn dialect_crate_manifest(spec: &DialectCrateSpec) -> String {
+    let params = spec.params();
+
+    let mavspec_dependency = match spec.mavspec_path() {
+        Some(path) => format!("path = {}", toml_string(&path.to_string_lossy())),
+        None => format!("version = \"{}\"", env!("CARGO_PKG_VERSION")),
+    };
+    let mut dependencies = vec![format!(
+        "mavspec = {{ {mavspec_dependency}, default-features = false, features = [\"rust\"] }}"
+    )];
+    for (crate_name, crate_dir) in spec.dependencies() {
+        dependencies.push(format!(
+            "{crate_name} = {{ path = {}, default-features = false }}",
+            toml_string(&format!("../{crate_dir}"))
+        ));
+    }
+
+    // Each feature enables the same feature of `mavspec` and dependency crates
+    let feature = |name: &str, own: &[&str]| -> String {
+        let values = own
+            .iter()
+            .map(|value| value.to_string())
+            .chain(
+                spec.dependencies()
+                    .iter()
+                    .map(|(crate_name, _)| format!("{crate_name}/{name}")),
+            )
+            .map(|value| toml_string(&value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{name} = [{values}]")
+    };
+
+    let mut features = vec![
+        "default = []".to_string(),
+        feature("alloc", &["mavspec/alloc"]),
+        feature("std", &["alloc", "mavspec/std"]),
+    ];
+    if params.serde {
+        dependencies.push(format!(
+            "serde = {{ version = \"{SERDE_VERSION}\", default-features = false, features = [\"derive\"], optional = true }}"
+        ));
+        dependencies.push(format!(
+            "serde_arrays = {{ version = \"{SERDE_ARRAYS_VERSION}\", default-features = false, optional = true }}"
+        ));
+        features.push(feature(
+            "serde",
+            &["dep:serde", "dep:serde_arrays", "mavspec/serde"],
+        ));
+    }
+    if params.arbitrary {
+        dependencies.push(format!(
+            "arbitrary = {{ version = \"{ARBITRARY_VERSION}\", features = [\"derive\"], optional = true }}"
+        ));
+        features.push(feature(
+            "arbitrary",
+            &["dep:arbitrary", "mavspec/arbitrary", "std"],
+        ));
+    }
+    if params.async_handlers {
+        features.push(feature("async", &[]));
+    }
+
+    format!(
+        "# Autogenerated by MAVSpec: MAVLink `{dialect_name}` dialect.\n\
+         [package]\n\
+         name = \"{crate_name}\"\n\
+         description = \"MAVLink `{dialect_name}` dialect\"\n\
+         version = \"0.0.0\"\n\
+         edition = \"2021\"\n\
+         publish = false\n\
+         \n\
+         [dependencies]\n\
+         {dependencies}\n\
+         \n\
+         [features]\n\
+         {features}\n",
+        dialect_name = spec.dialect_name(),
+        crate_name = spec.crate_name(),
+        dependencies = dependencies.join("\n"),
+        features = features.join("\n"),
+    )
+}
+
+/// Turns dialect module into a root module of dialect crate.
+pub(crate) fn dialect_crate_root(dialect_module: syn::File) -> syn::File {
+    // Generated documentation is not meant to be tested, as for dialect modules of other layouts
+    let crate_root: syn::File = syn::parse2(quote! {
+        #![cfg(not(doctest))]
+        #![cfg_attr(not(feature = "std"), no_std)]
+        #![warn(missing_docs)]
+        #![allow(deprecated)]
+        #![deny(rustdoc::broken_intra_doc_links)]
+
+        #[cfg(feature = "alloc")]
+        extern crate alloc;
+    })
+    .unwrap();
+
+    let mut dialect_module = dialect_module;
+    dialect_module.attrs.extend(crate_root.attrs);
+    dialect_module.items.splice(0..0, crate_root.items);
+    dialect_module
+}
+
+/// Quotes and escapes TOML basic string.
+fn toml_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
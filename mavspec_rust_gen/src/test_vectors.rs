@@ -0,0 +1,1125 @@
+//! # Test vectors
+//!
+//! Test vectors are sample messages with their encoded payloads. They are used to check generated code against other
+//! MAVLink implementations (i.e. C library or `pymavlink`) and vice versa.
+//!
+//! [`BuildHelperBuilder::set_test_vectors`](crate::BuildHelperBuilder::set_test_vectors) loads test vectors from JSON
+//! or CSV files and generates known-answer tests. [`sample_test_vectors`] produces deterministic pseudo-random test
+//! vectors for a dialect, which can be written by [`write_test_vectors`] and checked by other implementations.
+//!
+//! ## Format
+//!
+//! Each test vector contains:
+//!
+//! * `dialect` (optional): name of the dialect (i.e. `common`), required only when dialects define different messages
+//!   with the same name.
+//! * `message`: MAVLink message name (i.e. `HEARTBEAT`).
+//! * `id` (optional): MAVLink message ID, checked if present.
+//! * `crc_extra` (optional): message `CRC_EXTRA`, checked if present.
+//! * `version`: MAVLink protocol version of the payload, `1` or `2`.
+//! * `fields`: JSON object with field values by their MAVLink names. Fields which are not specified have their
+//!   default values. `MAVLink 1` payloads do not contain extension fields even if they are specified.
+//! * `payload`: expected payload as a hex string (i.e. `"030000000203810403"`). `MAVLink 2` payloads are expected to
+//!   have trailing zero bytes truncated (at least one byte is always present), as reference implementations do.
+//!
+//! JSON files contain an array of such objects. CSV files have a header with `message`, `version`, `fields`, and
+//! `payload` columns (other columns are optional), where `fields` column contains a JSON object.
+//!
+//! Field values are numbers or strings accepted by MAVLink XML definitions (i.e. `"NaN"` or `"UINT16_MAX"`). Enums
+//! accept entry names, bitmasks accept `|`-separated flag names. Arrays are represented by JSON arrays of the exact
+//! length, `char[N]` strings by JSON strings.
+//!
+//! Generated test vectors always contain all fields, including `dialect`, `id`, and `crc_extra`. Enums and bitmasks
+//! are represented by numeric values.
+
+use std::collections::HashMap;
+use std::fs::{read_to_string, File};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use mavinspect::protocol::{Dialect, Enum, MavType, Message, MessageField, Protocol, Value};
+use serde::{Deserialize, Serialize};
+
+use mavspec_rust_spec::tbytes::errors::TBytesError;
+use mavspec_rust_spec::tbytes::{TBytesWriter, TBytesWriterBackend, TBytesWriterFor};
+use mavspec_rust_spec::{MavLinkVersion, Payload};
+
+use crate::error::{RustGenError, RustGenResult};
+use crate::generator::WipPolicy;
+
+/// Test vector validated against MAVLink protocol.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct TestVector {
+    source: String,
+    dialect: String,
+    message: String,
+    version: u8,
+    fields: Vec<(String, TestVectorValue)>,
+    payload: Vec<u8>,
+}
+
+/// Value of a message field from a test vector.
+///
+/// Values of enum and bitmask fields have types of corresponding enums.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) enum TestVectorValue {
+    Scalar(Value),
+    Array(Vec<Value>),
+    String(String),
+}
+
+/// Test vector as it appears in a file.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawTestVector {
+    #[serde(default)]
+    dialect: Option<String>,
+    message: String,
+    #[serde(default)]
+    id: Option<u32>,
+    #[serde(default)]
+    crc_extra: Option<u8>,
+    version: u8,
+    #[serde(default)]
+    fields: serde_json::Map<String, serde_json::Value>,
+    payload: String,
+}
+
+/// Test vector generated by [`sample_test_vectors`].
+///
+/// See [module](self) documentation for details on the format.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SampleTestVector {
+    /// Name of the dialect.
+    pub dialect: String,
+    /// MAVLink message name.
+    pub message: String,
+    /// MAVLink message ID.
+    pub id: u32,
+    /// Message `CRC_EXTRA`.
+    pub crc_extra: u8,
+    /// MAVLink protocol version, either `1` or `2`.
+    pub version: u8,
+    /// Field values by MAVLink field names.
+    pub fields: serde_json::Map<String, serde_json::Value>,
+    /// Payload as a hex string.
+    pub payload: String,
+}
+
+impl TestVector {
+    /// Loads test vectors from `paths` and validates them against `protocol`.
+    pub(crate) fn load_all<T: AsRef<Path>>(
+        paths: &[T],
+        protocol: &Protocol,
+        wip: WipPolicy,
+    ) -> RustGenResult<Vec<TestVector>> {
+        let mut vectors = Vec::new();
+
+        for path in paths {
+            let path = path.as_ref();
+            let content = read_to_string(path).map_err(|err| {
+                RustGenError::InvalidTestVectors(format!("unable to read {path:?}: {err}"))
+            })?;
+
+            let raw_vectors = match path.extension().and_then(|ext| ext.to_str()) {
+                Some("json") => parse_json(&content),
+                Some("csv") => parse_csv(&content),
+                _ => Err("unsupported file type, expected `.json` or `.csv`".to_string()),
+            }
+            .map_err(|err| RustGenError::InvalidTestVectors(format!("{path:?}: {err}")))?;
+
+            for (idx, raw) in raw_vectors.iter().enumerate() {
+                let source = format!("{}#{}", path.display(), idx + 1);
+                let vector = Self::from_raw(raw, source.clone(), protocol, wip)
+                    .map_err(|err| RustGenError::InvalidTestVectors(format!("{source}: {err}")))?;
+                vectors.push(vector);
+            }
+        }
+
+        Ok(vectors)
+    }
+
+    /// Location of this vector in the form of `<path>#<number>`.
+    pub(crate) fn source(&self) -> &str {
+        self.source.as_str()
+    }
+
+    /// Name of the dialect where message is defined.
+    pub(crate) fn dialect(&self) -> &str {
+        self.dialect.as_str()
+    }
+
+    /// Name of MAVLink message.
+    pub(crate) fn message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    /// MAVLink protocol version, either `1` or `2`.
+    pub(crate) fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Field values by MAVLink field names.
+    pub(crate) fn fields(&self) -> &[(String, TestVectorValue)] {
+        self.fields.as_slice()
+    }
+
+    /// Expected payload bytes.
+    pub(crate) fn payload(&self) -> &[u8] {
+        self.payload.as_slice()
+    }
+
+    fn from_raw(
+        raw: &RawTestVector,
+        source: String,
+        protocol: &Protocol,
+        wip: WipPolicy,
+    ) -> Result<Self, String> {
+        let mut candidates = protocol
+            .dialects()
+            .filter(|dialect| match &raw.dialect {
+                Some(name) => dialect.name() == name || dialect.canonical_name() == name,
+                None => true,
+            })
+            .filter_map(|dialect| {
+                let message = dialect.get_message_by_name(raw.message.as_str())?;
+                let defined_in = message.defined_in().map(String::as_str);
+                Some((dialect, message, defined_in.unwrap_or(dialect.name())))
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|(_, _, defined_in)| *defined_in);
+        candidates.dedup_by_key(|(_, _, defined_in)| *defined_in);
+
+        let (dialect, message, defined_in) = match candidates.as_slice() {
+            [] => {
+                return Err(match &raw.dialect {
+                    Some(dialect) => {
+                        format!("unknown message `{}` in dialect `{dialect}`", raw.message)
+                    }
+                    None => format!("unknown message `{}`", raw.message),
+                })
+            }
+            [candidate] => *candidate,
+            candidates => {
+                let dialects: Vec<_> = candidates.iter().map(|(_, _, name)| *name).collect();
+                return Err(format!(
+                    "message `{}` is defined in several dialects ({}), specify `dialect`",
+                    raw.message,
+                    dialects.join(", ")
+                ));
+            }
+        };
+        if wip == WipPolicy::Exclude && message.wip() {
+            return Err(format!(
+                "message `{}` is work in progress and excluded by WIP policy",
+                raw.message
+            ));
+        }
+
+        if let Some(id) = raw.id.filter(|&id| id != message.id()) {
+            return Err(format!(
+                "message `{}` has ID {}, got {id}",
+                raw.message,
+                message.id()
+            ));
+        }
+        if let Some(crc_extra) = raw.crc_extra.filter(|&crc| crc != message.crc_extra()) {
+            return Err(format!(
+                "message `{}` has CRC_EXTRA {}, got {crc_extra}",
+                raw.message,
+                message.crc_extra()
+            ));
+        }
+
+        let payload = decode_hex(raw.payload.as_str())?;
+        match raw.version {
+            1 if !message.is_v1_compatible() => {
+                return Err(format!(
+                    "message `{}` is not compatible with MAVLink 1",
+                    raw.message
+                ));
+            }
+            1 if payload.len() != message.size_v1() => {
+                return Err(format!(
+                    "expected {} payload bytes for `MAVLink 1`, got {}",
+                    message.size_v1(),
+                    payload.len()
+                ));
+            }
+            2 if payload.is_empty() || payload.len() > message.size_v2() => {
+                return Err(format!(
+                    "expected from 1 to {} payload bytes for `MAVLink 2`, got {}",
+                    message.size_v2(),
+                    payload.len()
+                ));
+            }
+            1 | 2 => {}
+            version => {
+                return Err(format!(
+                    "invalid MAVLink version {version}, expected 1 or 2"
+                ));
+            }
+        }
+
+        let mut fields = Vec::new();
+        for (name, value) in &raw.fields {
+            let field = message
+                .fields()
+                .iter()
+                .find(|field| field.name() == name)
+                .ok_or_else(|| format!("unknown field `{name}` of message `{}`", raw.message))?;
+            let value = field_value(dialect, field, value, wip)
+                .map_err(|err| format!("invalid value of `{}.{name}`: {err}", raw.message))?;
+            fields.push((name.clone(), value));
+        }
+
+        Ok(Self {
+            source,
+            dialect: defined_in.to_string(),
+            message: message.name().to_string(),
+            version: raw.version,
+            fields,
+            payload,
+        })
+    }
+}
+
+/// Generates deterministic pseudo-random test vectors for all messages of a `dialect`.
+///
+/// Messages are sorted by ID. Each message gets a `MAVLink 2` test vector and, if compatible, a `MAVLink 1` test vector
+/// with the same field values. Values depend only on `seed` and message ID, so adding messages to a dialect does not
+/// change test vectors of other messages.
+///
+/// Field values are chosen to be exactly representable in JSON: floats are binary fractions with at most 15
+/// significant digits, enums are valid entries, bitmasks contain only known flags, and strings contain printable ASCII
+/// characters. Fields of `uint8_t_mavlink_version` type are always set to `3`, as reference implementations do.
+/// Sampled values are validated as loaded test vectors and encoded by the same writer as generated messages.
+///
+/// Work-in-progress messages and enum entries are skipped if excluded by `wip` policy.
+///
+/// Returns [`RustGenError::InvalidTestVectors`] if an enum field has no entries which fit into the field type.
+pub fn sample_test_vectors(
+    dialect: &Dialect,
+    seed: u64,
+    wip: WipPolicy,
+) -> RustGenResult<Vec<SampleTestVector>> {
+    let mut messages: Vec<&Message> = dialect
+        .messages()
+        .into_iter()
+        .filter(|message| wip != WipPolicy::Exclude || !message.wip())
+        .collect();
+    messages.sort_by_key(|message| message.id());
+
+    let mut vectors = Vec::new();
+    for message in messages {
+        let mut rng =
+            SampleRng::new(seed ^ (message.id() as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let invalid = |err: String| {
+            RustGenError::InvalidTestVectors(format!(
+                "`{}.{}`: {err}",
+                dialect.name(),
+                message.name()
+            ))
+        };
+
+        let mut fields = serde_json::Map::new();
+        let mut values = HashMap::new();
+        for field in message.fields() {
+            let mav_enum = field
+                .r#enum()
+                .and_then(|enum_name| dialect.get_enum_by_name(enum_name))
+                .filter(|mav_enum| !wip.excludes_enum(mav_enum));
+            let value = sample_field(&mut rng, field.r#type(), mav_enum, wip)
+                .map_err(|err| invalid(format!("unable to sample `{}`: {err}", field.name())))?;
+            values.insert(
+                field.name(),
+                field_value(dialect, field, &value, wip).map_err(invalid)?,
+            );
+            fields.insert(field.name().to_string(), value);
+        }
+
+        let mut versions = Vec::new();
+        if message.is_v1_compatible() {
+            versions.push(MavLinkVersion::V1);
+        }
+        versions.push(MavLinkVersion::V2);
+
+        for version in versions {
+            let payload = encode_payload(message, &values, version).map_err(invalid)?;
+            vectors.push(SampleTestVector {
+                dialect: dialect.name().to_string(),
+                message: message.name().to_string(),
+                id: message.id(),
+                crc_extra: message.crc_extra(),
+                version: match version {
+                    MavLinkVersion::V1 => 1,
+                    MavLinkVersion::V2 => 2,
+                },
+                fields: fields.clone(),
+                payload: payload.iter().map(|byte| format!("{byte:02x}")).collect(),
+            });
+        }
+    }
+
+    Ok(vectors)
+}
+
+/// Writes test vectors to a JSON file at `path`, one test vector per line.
+pub fn write_test_vectors<T: AsRef<Path>>(
+    vectors: &[SampleTestVector],
+    path: T,
+) -> RustGenResult<()> {
+    let lines = vectors
+        .iter()
+        .map(|vector| serde_json::to_string(vector).map(|line| format!("  {line}")))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| RustGenError::InvalidTestVectors(err.to_string()))?;
+
+    let mut file = File::create(path)?;
+    if lines.is_empty() {
+        file.write_all(b"[]\n")?;
+    } else {
+        file.write_all(format!("[\n{}\n]\n", lines.join(",\n")).as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Deterministic pseudo-random numbers generator (`xorshift64*`).
+struct SampleRng(u64);
+
+impl SampleRng {
+    fn new(seed: u64) -> Self {
+        // `SplitMix64` step, so similar seeds produce unrelated sequences and state is never zero
+        let mut state = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        state = (state ^ (state >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        state = (state ^ (state >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Self((state ^ (state >> 31)) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn printable_char(&mut self) -> u8 {
+        b' ' + self.below(95) as u8
+    }
+}
+
+/// Returns sample JSON value of a field.
+fn sample_field(
+    rng: &mut SampleRng,
+    mav_type: &MavType,
+    mav_enum: Option<&Enum>,
+    wip: WipPolicy,
+) -> Result<serde_json::Value, String> {
+    match mav_type {
+        MavType::Array(base_type, len) if mav_enum.is_none() && **base_type == MavType::Char => {
+            let text_len = rng.below(len + 1);
+            let text: String = (0..text_len)
+                .map(|_| rng.printable_char() as char)
+                .collect();
+            Ok(serde_json::Value::String(text))
+        }
+        MavType::Array(base_type, len) => (0..*len)
+            .map(|_| sample_item(rng, base_type, mav_enum, wip))
+            .collect(),
+        mav_type => sample_item(rng, mav_type, mav_enum, wip),
+    }
+}
+
+fn sample_item(
+    rng: &mut SampleRng,
+    mav_type: &MavType,
+    mav_enum: Option<&Enum>,
+    wip: WipPolicy,
+) -> Result<serde_json::Value, String> {
+    if let Some(mav_enum) = mav_enum {
+        return sample_enum_value(rng, mav_type, mav_enum, wip).map(serde_json::Value::from);
+    }
+
+    let bits = rng.next();
+    Ok(match mav_type {
+        MavType::Int8 => (bits as i8).into(),
+        MavType::Int16 => (bits as i16).into(),
+        MavType::Int32 => (bits as i32).into(),
+        MavType::Int64 => (bits as i64).into(),
+        MavType::UInt8 => (bits as u8).into(),
+        MavType::UInt16 => (bits as u16).into(),
+        MavType::UInt32 => (bits as u32).into(),
+        MavType::UInt64 => bits.into(),
+        // Binary fractions with at most 15 significant digits are exactly parsed by any JSON parser
+        MavType::Float => (((bits as i32) >> 8) as f32 / 256.0).into(),
+        MavType::Double => (((bits as i64) >> 32) as f64 / 16.0).into(),
+        MavType::Char => rng.printable_char().into(),
+        MavType::UInt8MavlinkVersion => 3u8.into(),
+        MavType::Array(base_type, _) => return sample_item(rng, base_type, None, wip),
+    })
+}
+
+/// Picks a random entry of an enum or a random combination of bitmask flags which fits into `mav_type`.
+///
+/// Returns error if enum has no entries which fit into `mav_type`.
+fn sample_enum_value(
+    rng: &mut SampleRng,
+    mav_type: &MavType,
+    mav_enum: &Enum,
+    wip: WipPolicy,
+) -> Result<u64, String> {
+    let excludes_wip = wip == WipPolicy::Exclude;
+    let max = match mav_type {
+        MavType::Int8 => i8::MAX as u64,
+        MavType::Int16 => i16::MAX as u64,
+        MavType::Int32 => i32::MAX as u64,
+        MavType::UInt16 => u16::MAX as u64,
+        MavType::UInt32 => u32::MAX as u64,
+        MavType::Int64 | MavType::UInt64 => u64::MAX,
+        _ => u8::MAX as u64,
+    };
+    let values: Vec<u64> = mav_enum
+        .entries()
+        .iter()
+        .filter(|entry| !(excludes_wip && entry.wip()))
+        .map(|entry| entry.value() as u64)
+        .filter(|&value| value <= max)
+        .collect();
+
+    if mav_enum.bitmask() {
+        Ok(values
+            .iter()
+            .filter(|_| rng.next() & 1 == 1)
+            .fold(0, |bits, flag| bits | flag))
+    } else if values.is_empty() {
+        Err(format!(
+            "enum `{}` has no entries which fit into `{}`",
+            mav_enum.name(),
+            mav_type.definition_name()
+        ))
+    } else {
+        Ok(values[rng.below(values.len())])
+    }
+}
+
+/// Encodes field `values` of a `message` with [`TBytesWriter`] and truncates payload by [`Payload`] exactly as
+/// generated messages do.
+fn encode_payload(
+    message: &Message,
+    values: &HashMap<&str, TestVectorValue>,
+    version: MavLinkVersion,
+) -> Result<Vec<u8>, String> {
+    let (fields, size) = match version {
+        MavLinkVersion::V1 => (message.fields_v1(), message.size_v1()),
+        MavLinkVersion::V2 => (message.fields_v2(), message.size_v2()),
+    };
+
+    let mut buf = vec![0u8; size];
+    let mut writer = TBytesWriter::from(buf.as_mut_slice());
+    for field in fields {
+        let value = values
+            .get(field.name())
+            .ok_or_else(|| format!("missing value of `{}`", field.name()))?;
+        let base_type = field.r#type().base_type();
+        let written = match value {
+            TestVectorValue::Scalar(value) => write_value(&mut writer, base_type, value),
+            TestVectorValue::Array(items) => items.iter().try_fold(0, |len, item| {
+                Ok(len + write_value(&mut writer, base_type, item)?)
+            }),
+            TestVectorValue::String(value) => {
+                let mut raw = value.as_bytes().to_vec();
+                raw.resize(field.r#type().size(), 0);
+                writer.write_slice(raw.as_slice())
+            }
+        };
+        written.map_err(|err| format!("unable to encode `{}`: {err:?}", field.name()))?;
+    }
+
+    Ok(Payload::new(message.id(), buf.as_slice(), version)
+        .bytes()
+        .to_vec())
+}
+
+/// Writes `value` as `mav_type`, enum values are converted to field type as generated messages do.
+fn write_value<B: TBytesWriterBackend>(
+    writer: &mut TBytesWriter<B>,
+    mav_type: &MavType,
+    value: &Value,
+) -> Result<usize, TBytesError> {
+    macro_rules! write_as {
+        ($t:ty) => {
+            writer.write(match *value {
+                Value::Int8(val) => val as $t,
+                Value::Int16(val) => val as $t,
+                Value::Int32(val) => val as $t,
+                Value::Int64(val) => val as $t,
+                Value::UInt8(val) => val as $t,
+                Value::UInt16(val) => val as $t,
+                Value::UInt32(val) => val as $t,
+                Value::UInt64(val) => val as $t,
+                Value::Float(val) => val as $t,
+                Value::Double(val) => val as $t,
+                Value::Char(val) => val as $t,
+                Value::Max(_) => <$t>::MAX,
+            })
+        };
+    }
+
+    match mav_type {
+        MavType::Int8 => write_as!(i8),
+        MavType::Int16 => write_as!(i16),
+        MavType::Int32 => write_as!(i32),
+        MavType::Int64 => write_as!(i64),
+        MavType::UInt8 | MavType::Char | MavType::UInt8MavlinkVersion => write_as!(u8),
+        MavType::UInt16 => write_as!(u16),
+        MavType::UInt32 => write_as!(u32),
+        MavType::UInt64 => write_as!(u64),
+        MavType::Float => write_as!(f32),
+        MavType::Double => write_as!(f64),
+        MavType::Array(base_type, _) => write_value(writer, base_type, value),
+    }
+}
+
+fn field_value(
+    dialect: &Dialect,
+    field: &MessageField,
+    value: &serde_json::Value,
+    wip: WipPolicy,
+) -> Result<TestVectorValue, String> {
+    // Fields with enums missing from the dialect or excluded by WIP policy are generated as plain numeric fields
+    let mav_enum = field
+        .r#enum()
+        .and_then(|enum_name| dialect.get_enum_by_name(enum_name))
+        .filter(|mav_enum| !wip.excludes_enum(mav_enum));
+    let item_value = |value: &serde_json::Value| match mav_enum {
+        Some(mav_enum) => enum_value(mav_enum, value, wip),
+        None => scalar_value(field.r#type().base_type(), value),
+    };
+
+    match (field.r#type(), value) {
+        (MavType::Array(base_type, len), serde_json::Value::String(value))
+            if mav_enum.is_none() && matches!(base_type.as_ref(), MavType::Char) =>
+        {
+            if value.len() > *len {
+                return Err(format!(
+                    "string of {} bytes exceeds capacity of {len} bytes",
+                    value.len()
+                ));
+            }
+            if value.contains('\0') {
+                return Err("string contains NUL characters".to_string());
+            }
+            Ok(TestVectorValue::String(value.clone()))
+        }
+        (MavType::Array(_, len), serde_json::Value::Array(items)) => {
+            if items.len() != *len {
+                return Err(format!(
+                    "expected array of {len} items, got {}",
+                    items.len()
+                ));
+            }
+            Ok(TestVectorValue::Array(
+                items.iter().map(item_value).collect::<Result<_, _>>()?,
+            ))
+        }
+        (MavType::Array(_, _), _) => Err("expected array".to_string()),
+        (_, value) => Ok(TestVectorValue::Scalar(item_value(value)?)),
+    }
+}
+
+fn scalar_value(mav_type: &MavType, value: &serde_json::Value) -> Result<Value, String> {
+    let value = match value {
+        serde_json::Value::Number(value) => value.to_string(),
+        serde_json::Value::String(value) => value.clone(),
+        _ => return Err("expected number or string".to_string()),
+    };
+
+    Value::parse(value.as_str(), mav_type).map_err(|err| {
+        format!(
+            "`{value}` is not a valid `{}`: {err:?}",
+            mav_type.definition_name()
+        )
+    })
+}
+
+fn enum_value(mav_enum: &Enum, value: &serde_json::Value, wip: WipPolicy) -> Result<Value, String> {
+    let excludes_wip = wip == WipPolicy::Exclude;
+    let find_entry = |name: &str| {
+        mav_enum
+            .entries()
+            .iter()
+            .find(|entry| entry.name() == name)
+            .ok_or_else(|| format!("unknown entry `{name}` of enum `{}`", mav_enum.name()))
+    };
+
+    let raw = match value {
+        serde_json::Value::Number(value) => value
+            .as_u64()
+            .ok_or_else(|| format!("`{value}` is not a valid value of `{}`", mav_enum.name()))?,
+        serde_json::Value::String(value) if mav_enum.bitmask() => {
+            let mut bits = 0u64;
+            for name in value.split('|').map(str::trim) {
+                bits |= find_entry(name)?.value() as u64;
+            }
+            bits
+        }
+        serde_json::Value::String(value) => find_entry(value)?.value() as u64,
+        _ => return Err("expected number or entry name".to_string()),
+    };
+
+    if !mav_enum.bitmask() {
+        let entry = mav_enum
+            .entries()
+            .iter()
+            .find(|entry| entry.value() as u64 == raw)
+            .ok_or_else(|| format!("{raw} is not a valid entry of enum `{}`", mav_enum.name()))?;
+        if excludes_wip && entry.wip() {
+            return Err(format!(
+                "entry `{}` is work in progress and excluded by WIP policy",
+                entry.name()
+            ));
+        }
+    }
+
+    Value::parse(raw.to_string().as_str(), &mav_enum.inferred_type())
+        .map_err(|_| format!("{raw} does not fit into enum `{}`", mav_enum.name()))
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<u8> = hex
+        .bytes()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect();
+    let pairs = digits.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(format!("payload `{hex}` has odd number of hex digits"));
+    }
+
+    pairs
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| format!("payload `{hex}` is not a valid hex string"))
+        })
+        .collect()
+}
+
+fn parse_json(content: &str) -> Result<Vec<RawTestVector>, String> {
+    serde_json::from_str(content).map_err(|err| format!("invalid JSON: {err}"))
+}
+
+fn parse_csv(content: &str) -> Result<Vec<RawTestVector>, String> {
+    let mut rows = parse_csv_rows(content)?.into_iter();
+    let (_, header) = rows.next().ok_or("missing CSV header")?;
+    let columns: HashMap<&str, usize> = header
+        .iter()
+        .enumerate()
+        .map(|(idx, column)| (column.trim(), idx))
+        .collect();
+
+    let column = |name: &str| {
+        columns
+            .get(name)
+            .copied()
+            .ok_or_else(|| format!("missing `{name}` column"))
+    };
+    let optional_column = |name: &str| columns.get(name).copied();
+    let (dialect, id, crc_extra) = (
+        optional_column("dialect"),
+        optional_column("id"),
+        optional_column("crc_extra"),
+    );
+    let (message, version, fields, payload) = (
+        column("message")?,
+        column("version")?,
+        column("fields")?,
+        column("payload")?,
+    );
+
+    rows.filter(|(_, row)| !row.iter().all(|cell| cell.trim().is_empty()))
+        .map(|(line, row)| {
+            let cell = |idx: usize| row.get(idx).map(|cell| cell.trim()).unwrap_or_default();
+
+            let optional_cell = |idx: Option<usize>| idx.map(cell).filter(|cell| !cell.is_empty());
+
+            Ok(RawTestVector {
+                dialect: optional_cell(dialect).map(str::to_string),
+                message: cell(message).to_string(),
+                id: parse_optional_cell(optional_cell(id), line, "message ID")?,
+                crc_extra: parse_optional_cell(optional_cell(crc_extra), line, "CRC_EXTRA")?,
+                version: cell(version)
+                    .parse()
+                    .map_err(|_| format!("line {line}: invalid version `{}`", cell(version)))?,
+                fields: match cell(fields) {
+                    "" => Default::default(),
+                    fields => serde_json::from_str(fields)
+                        .map_err(|err| format!("line {line}: invalid fields: {err}"))?,
+                },
+                payload: cell(payload).to_string(),
+            })
+        })
+        .collect()
+}
+
+fn parse_optional_cell<T: FromStr>(
+    value: Option<&str>,
+    line: usize,
+    name: &str,
+) -> Result<Option<T>, String> {
+    value
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| format!("line {line}: invalid {name} `{value}`"))
+        })
+        .transpose()
+}
+
+/// Splits CSV `content` into rows of cells. Quoted cells may contain commas, line breaks, and escaped (`""`) quotes.
+///
+/// Each row is returned along with the number of the line where it starts.
+fn parse_csv_rows(content: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+    let mut line = 1;
+    let mut row_line = 1;
+
+    while let Some(ch) = chars.next() {
+        if ch == '\n' {
+            line += 1;
+        }
+        match (quoted, ch) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, ch) => cell.push(ch),
+            (false, '"') => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut cell)),
+            (false, '\r') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut cell));
+                rows.push((row_line, std::mem::take(&mut row)));
+                row_line = line;
+            }
+            (false, ch) => cell.push(ch),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted CSV cell".to_string());
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push((row_line, row));
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mavinspect::Inspector;
+    use std::fs::{create_dir_all, remove_file, write};
+    use std::sync::OnceLock;
+
+    fn protocol() -> &'static Protocol {
+        static PROTOCOL: OnceLock<Protocol> = OnceLock::new();
+
+        PROTOCOL.get_or_init(|| {
+            Inspector::builder()
+                .set_sources(&[
+                    "../message_definitions/standard",
+                    "../message_definitions/extra",
+                ])
+                .set_include(&["common", "MAVInspect_test"])
+                .build()
+                .unwrap()
+                .parse()
+                .unwrap()
+        })
+    }
+
+    fn load(name: &str, content: &str) -> RustGenResult<Vec<TestVector>> {
+        let dir = Path::new("../tmp/test_vectors");
+        create_dir_all(dir).unwrap();
+        let path = dir.join(name);
+        write(&path, content).unwrap();
+        let vectors = TestVector::load_all(&[&path], protocol(), WipPolicy::Include);
+        remove_file(path).unwrap();
+        vectors
+    }
+
+    fn field<'a>(vector: &'a TestVector, name: &str) -> &'a TestVectorValue {
+        &vector
+            .fields()
+            .iter()
+            .find(|(field, _)| field == name)
+            .unwrap()
+            .1
+    }
+
+    fn error(name: &str, content: &str) -> String {
+        match load(name, content) {
+            Err(RustGenError::InvalidTestVectors(err)) => err,
+            other => panic!("expected invalid test vectors error, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn json_vectors() {
+        let vectors = load(
+            "valid.json",
+            r#"[
+                {
+                    "message": "HEARTBEAT",
+                    "version": 1,
+                    "fields": {
+                        "custom_mode": 3,
+                        "type": "MAV_TYPE_QUADROTOR",
+                        "base_mode": "MAV_MODE_FLAG_SAFETY_ARMED | MAV_MODE_FLAG_CUSTOM_MODE_ENABLED"
+                    },
+                    "payload": "03000000 02 00 81 00 00"
+                },
+                {
+                    "message": "STATUSTEXT",
+                    "version": 2,
+                    "fields": { "text": "Hello", "severity": 6 },
+                    "payload": "0648656c6c6f"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(vectors.len(), 2);
+        assert_eq!(vectors[0].dialect(), "minimal");
+        assert_eq!(vectors[1].dialect(), "common");
+        assert_eq!(vectors[0].message(), "HEARTBEAT");
+        assert_eq!(vectors[0].version(), 1);
+        assert_eq!(vectors[0].payload(), [3, 0, 0, 0, 2, 0, 0x81, 0, 0]);
+        assert!(vectors[0].source().ends_with("valid.json#1"));
+        assert!(matches!(
+            field(&vectors[0], "type"),
+            TestVectorValue::Scalar(Value::UInt8(2))
+        ));
+        assert!(matches!(
+            field(&vectors[0], "base_mode"),
+            TestVectorValue::Scalar(Value::UInt8(0x81))
+        ));
+        assert!(matches!(
+            field(&vectors[1], "text"),
+            TestVectorValue::String(text) if text == "Hello"
+        ));
+    }
+
+    #[test]
+    fn csv_vectors() {
+        let vectors = load(
+            "valid.csv",
+            "message,version,fields,payload,dialect\n\
+             PROTOCOL_VERSION,2,\"{\"\"version\"\": 200, \"\"spec_version_hash\"\": [1, 2, 3, 4, 5, 6, 7, 8]}\",c800000000000102030405060708,\r\n\
+             \n\
+             DEBUG,2,\"{\"\"debug\"\": \"\"DEBUG_CLONE\"\"}\",01,mav_inspect_test\n",
+        )
+        .unwrap();
+
+        assert_eq!(vectors.len(), 2);
+        assert_eq!(vectors[0].fields().len(), 2);
+        assert!(matches!(
+            field(&vectors[0], "spec_version_hash"),
+            TestVectorValue::Array(items) if items.len() == 8
+        ));
+        assert_eq!(vectors[1].dialect(), "MAVInspect_test");
+        assert_eq!(vectors[1].payload(), [1]);
+    }
+
+    #[test]
+    fn invalid_vectors() {
+        let vector = |message: &str, version: u8, fields: &str, payload: &str| {
+            format!(
+                r#"[{{"message": "{message}", "version": {version}, "fields": {fields}, "payload": "{payload}"}}]"#
+            )
+        };
+
+        for (content, expected) in [
+            (vector("UNKNOWN", 2, "{}", "00"), "unknown message `UNKNOWN`"),
+            (
+                vector("HEARTBEAT", 2, r#"{"mode": 1}"#, "00"),
+                "unknown field `mode` of message `HEARTBEAT`",
+            ),
+            (
+                vector("HEARTBEAT", 2, r#"{"type": "MAV_TYPE_UNKNOWN"}"#, "00"),
+                "unknown entry `MAV_TYPE_UNKNOWN` of enum `MAV_TYPE`",
+            ),
+            (
+                vector("HEARTBEAT", 2, r#"{"type": 255}"#, "00"),
+                "255 is not a valid entry of enum `MAV_TYPE`",
+            ),
+            (
+                vector("HEARTBEAT", 2, r#"{"custom_mode": -1}"#, "00"),
+                "`-1` is not a valid `uint32_t`",
+            ),
+            (
+                vector("STATUSTEXT", 2, r#"{"text": [1]}"#, "00"),
+                "expected array of 50 items, got 1",
+            ),
+            (
+                vector("PROTOCOL_VERSION", 1, "{}", "00"),
+                "not compatible with MAVLink 1",
+            ),
+            (
+                vector("HEARTBEAT", 1, "{}", "00"),
+                "expected 9 payload bytes",
+            ),
+            (vector("HEARTBEAT", 3, "{}", "00"), "invalid MAVLink version 3"),
+            (
+                vector("DEBUG", 2, "{}", "00"),
+                "message `DEBUG` is defined in several dialects (MAVInspect_test, common)",
+            ),
+            (
+                r#"[{"dialect": "minimal", "message": "STATUSTEXT", "version": 2, "payload": "00"}]"#.to_string(),
+                "unknown message `STATUSTEXT` in dialect `minimal`",
+            ),
+            (vector("HEARTBEAT", 2, "{}", "0g"), "not a valid hex string"),
+        ] {
+            let err = error("invalid.json", &content);
+            assert!(err.contains(expected), "{err:?} should contain {expected:?}");
+            assert!(err.contains("invalid.json#1"));
+        }
+
+        let too_long = "x".repeat(51);
+        assert!(error(
+            "invalid.json",
+            &vector(
+                "STATUSTEXT",
+                2,
+                &format!(r#"{{"text": "{too_long}"}}"#),
+                "00"
+            )
+        )
+        .contains("exceeds capacity of 50 bytes"));
+
+        assert!(
+            error("invalid.csv", "message,version,payload\n").contains("missing `fields` column")
+        );
+        assert!(error(
+            "invalid.csv",
+            "message,version,fields,payload\n\
+             HEARTBEAT,2,\"{\n\"\"type\"\": 1\n}\",00\n\
+             HEARTBEAT,x,,00\n",
+        )
+        .contains("line 5: invalid version `x`"));
+        assert!(error("invalid.txt", "").contains("unsupported file type"));
+
+        assert!(error(
+            "invalid.json",
+            r#"[{"message": "HEARTBEAT", "id": 1, "version": 2, "payload": "00"}]"#
+        )
+        .contains("message `HEARTBEAT` has ID 0, got 1"));
+        assert!(error(
+            "invalid.json",
+            r#"[{"message": "HEARTBEAT", "crc_extra": 51, "version": 2, "payload": "00"}]"#
+        )
+        .contains("message `HEARTBEAT` has CRC_EXTRA 50, got 51"));
+    }
+
+    #[test]
+    fn sampled_vectors() {
+        let dialect = protocol().get_dialect_by_name("common").unwrap();
+        let vectors = sample_test_vectors(dialect, 42, WipPolicy::Include).unwrap();
+
+        assert_eq!(
+            vectors,
+            sample_test_vectors(dialect, 42, WipPolicy::Include).unwrap()
+        );
+        assert_ne!(
+            vectors,
+            sample_test_vectors(dialect, 43, WipPolicy::Include).unwrap()
+        );
+
+        let heartbeat: Vec<_> = vectors
+            .iter()
+            .filter(|v| v.message == "HEARTBEAT")
+            .collect();
+        assert_eq!(heartbeat.len(), 2);
+        assert_eq!((heartbeat[0].version, heartbeat[1].version), (1, 2));
+        assert_eq!((heartbeat[0].id, heartbeat[0].crc_extra), (0, 50));
+        assert_eq!(heartbeat[0].fields, heartbeat[1].fields);
+        assert_eq!(heartbeat[0].fields["mavlink_version"], 3);
+
+        // Messages with ID above 255 have only `MAVLink 2` test vectors
+        assert!(vectors
+            .iter()
+            .filter(|v| v.id > 255)
+            .all(|v| v.version == 2));
+
+        let dir = Path::new("../tmp/test_vectors");
+        create_dir_all(dir).unwrap();
+        let path = dir.join("sampled.json");
+        write_test_vectors(&vectors, &path).unwrap();
+        let content = read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), vectors.len() + 2);
+
+        let loaded = TestVector::load_all(&[&path], protocol(), WipPolicy::Include).unwrap();
+        remove_file(path).unwrap();
+        assert_eq!(loaded.len(), vectors.len());
+        for (sampled, loaded) in vectors.iter().zip(&loaded) {
+            assert_eq!(loaded.message(), sampled.message);
+            assert_eq!(hex(loaded.payload()), sampled.payload);
+            assert_eq!(loaded.fields().len(), sampled.fields.len());
+        }
+    }
+
+    #[test]
+    fn sampled_vectors_exclude_wip() {
+        let dialect = protocol().get_dialect_by_name("common").unwrap();
+        let wip_messages: Vec<_> = dialect
+            .messages()
+            .into_iter()
+            .filter(|message| message.wip())
+            .map(|message| message.name().to_string())
+            .collect();
+        assert!(!wip_messages.is_empty());
+
+        let vectors = sample_test_vectors(dialect, 0, WipPolicy::Exclude).unwrap();
+        assert!(vectors.iter().all(|v| !wip_messages.contains(&v.message)));
+    }
+
+    #[test]
+    fn sampled_enum_without_fitting_entries() {
+        let protocol = Inspector::builder()
+            .set_sources(&["../message_definitions/standard"])
+            .set_include(&["storm32"])
+            .build()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let dialect = protocol.get_dialect_by_name("storm32").unwrap();
+        let mav_enum = dialect
+            .get_enum_by_name("MAV_STORM32_TUNNEL_PAYLOAD_TYPE")
+            .unwrap();
+
+        let mut rng = SampleRng::new(0);
+        let err =
+            sample_enum_value(&mut rng, &MavType::Int8, mav_enum, WipPolicy::Include).unwrap_err();
+        assert!(err.contains("has no entries which fit into `int8_t`"));
+        assert!(
+            sample_enum_value(&mut rng, &MavType::UInt16, mav_enum, WipPolicy::Include).is_ok()
+        );
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
@@ -0,0 +1,259 @@
+//! Extra derives and attributes for generated types.
+
+use std::str::FromStr;
+
+use mavinspect::protocol::{MavType, Message};
+use quote::{quote, ToTokens};
+use serde::Serialize;
+
+use crate::error::{RustGenError, RustGenResult};
+
+/// Derives which are not possible for types with floating point fields.
+const FLOAT_INCOMPATIBLE_DERIVES: [&str; 3] = ["Eq", "Ord", "Hash"];
+
+/// Kind of generated type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum TypeKind {
+    /// Message structs.
+    Message,
+    /// Enums.
+    Enum,
+    /// Bitmask structs.
+    Bitmask,
+    /// Dialect enums which contain all dialect messages.
+    Dialect,
+}
+
+impl TypeKind {
+    /// Derives which are always present for generated types of this kind.
+    fn builtin_derives(&self) -> &'static [&'static str] {
+        match self {
+            TypeKind::Message | TypeKind::Dialect => &["Clone", "Debug"],
+            TypeKind::Enum | TypeKind::Bitmask => &["Copy", "Clone", "Debug", "Default"],
+        }
+    }
+}
+
+impl FromStr for TypeKind {
+    type Err = RustGenError;
+
+    /// Parses type kind from `messages`, `enums`, `bitmasks`, or `dialects`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "messages" => Ok(TypeKind::Message),
+            "enums" => Ok(TypeKind::Enum),
+            "bitmasks" => Ok(TypeKind::Bitmask),
+            "dialects" => Ok(TypeKind::Dialect),
+            _ => Err(RustGenError::InvalidTypeAttributes(format!(
+                "invalid type kind `{s}`, expected `messages`, `enums`, `bitmasks`, or `dialects`"
+            ))),
+        }
+    }
+}
+
+/// Extra derives and attributes for generated types selected by kind and name.
+///
+/// Types are selected by their MAVLink names (i.e. `HEARTBEAT` for messages, `MAV_TYPE` for enums, or `common` for
+/// dialects). Postfix wildcards are accepted (i.e. `PREFIX_*`).
+///
+/// `Eq`, `Ord`, and `Hash` derives are skipped for messages with floating point fields and for dialects which contain
+/// such messages. Derives which are already present in generated code (like `Clone` or `Debug`) are skipped as well.
+///
+/// Note that derives like `PartialEq` for messages require the same derives for enums and bitmasks used as field
+/// types. Leave [`TypeAttributes::kinds`] empty to apply derives to all generated types.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TypeAttributes {
+    /// Kinds of types to which this applies. All kinds are selected if empty.
+    pub kinds: Vec<TypeKind>,
+    /// MAVLink names of types to which this applies. All types are selected if empty.
+    pub names: Vec<String>,
+    /// Extra derives (i.e. `PartialEq` or `serde::Serialize`).
+    pub derives: Vec<String>,
+    /// Extra attributes (i.e. `#[non_exhaustive]`).
+    pub attributes: Vec<String>,
+}
+
+impl TypeAttributes {
+    /// Checks that derives are valid paths and attributes are valid outer attributes.
+    pub fn validate(&self) -> RustGenResult<()> {
+        for derive in &self.derives {
+            Self::parse_derive(derive)?;
+        }
+        for attribute in &self.attributes {
+            Self::parse_attributes(attribute)?;
+        }
+        Ok(())
+    }
+
+    /// Whether this applies to a type of specified `kind` and `name`.
+    pub(crate) fn applies_to(&self, kind: TypeKind, name: &str) -> bool {
+        (self.kinds.is_empty() || self.kinds.contains(&kind))
+            && (self.names.is_empty()
+                || self
+                    .names
+                    .iter()
+                    .any(|pattern| Self::matches_pattern(pattern, name)))
+    }
+
+    fn matches_pattern(pattern: &str, name: &str) -> bool {
+        match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => pattern == name,
+        }
+    }
+
+    fn parse_derive(derive: &str) -> RustGenResult<syn::Path> {
+        syn::parse_str(derive).map_err(|err| {
+            RustGenError::InvalidTypeAttributes(format!("invalid derive `{derive}`: {err}"))
+        })
+    }
+
+    fn parse_attributes(attribute: &str) -> RustGenResult<Vec<syn::Attribute>> {
+        use syn::parse::Parser;
+
+        syn::Attribute::parse_outer
+            .parse_str(attribute)
+            .map_err(|err| {
+                RustGenError::InvalidTypeAttributes(format!(
+                    "invalid attribute `{attribute}`: {err}"
+                ))
+            })
+    }
+}
+
+/// Whether message contains floating point fields.
+pub(crate) fn message_has_floats(message: &Message) -> bool {
+    message
+        .fields()
+        .iter()
+        .any(|field| matches!(field.r#type().base_type(), MavType::Float | MavType::Double))
+}
+
+/// Makes extra derives and attributes for a generated type.
+///
+/// Type attributes are expected to be validated by [`TypeAttributes::validate`].
+pub(crate) fn make_type_attributes(
+    type_attributes: &[TypeAttributes],
+    kind: TypeKind,
+    name: &str,
+    has_floats: bool,
+) -> proc_macro2::TokenStream {
+    let mut derives: Vec<syn::Path> = Vec::new();
+    let mut attributes: Vec<syn::Attribute> = Vec::new();
+
+    for item in type_attributes
+        .iter()
+        .filter(|item| item.applies_to(kind, name))
+    {
+        for derive in &item.derives {
+            let path = TypeAttributes::parse_derive(derive).unwrap();
+            let ident = path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+                .unwrap_or_default();
+
+            let is_duplicate = derives.iter().any(|existing| {
+                existing.to_token_stream().to_string() == path.to_token_stream().to_string()
+            });
+            if kind.builtin_derives().contains(&ident.as_str()) || is_duplicate {
+                continue;
+            }
+            if has_floats && FLOAT_INCOMPATIBLE_DERIVES.contains(&ident.as_str()) {
+                log::debug!("Skipping `{derive}` derive for `{name}` since it contains floats.");
+                continue;
+            }
+            derives.push(path);
+        }
+
+        for attribute in &item.attributes {
+            attributes.extend(TypeAttributes::parse_attributes(attribute).unwrap());
+        }
+    }
+
+    let derives = if derives.is_empty() {
+        quote!()
+    } else {
+        quote! { #[derive(#(#derives),*)] }
+    };
+
+    quote! {
+        #derives
+        #(#attributes)*
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_attributes_selection() {
+        let type_attributes = TypeAttributes {
+            kinds: vec![TypeKind::Message, TypeKind::Enum],
+            names: vec!["HEARTBEAT".to_string(), "MAV_*".to_string()],
+            ..Default::default()
+        };
+
+        assert!(type_attributes.applies_to(TypeKind::Message, "HEARTBEAT"));
+        assert!(type_attributes.applies_to(TypeKind::Enum, "MAV_TYPE"));
+        assert!(!type_attributes.applies_to(TypeKind::Bitmask, "MAV_MODE_FLAG"));
+        assert!(!type_attributes.applies_to(TypeKind::Message, "HEARTBEAT_EXTENDED"));
+        assert!(TypeAttributes::default().applies_to(TypeKind::Dialect, "common"));
+    }
+
+    #[test]
+    fn type_attributes_tokens() {
+        let type_attributes = [TypeAttributes {
+            derives: vec![
+                "PartialEq".to_string(),
+                "core::cmp::Eq".to_string(),
+                "Hash".to_string(),
+                "Clone".to_string(),
+            ],
+            attributes: vec!["#[allow(missing_docs)] #[non_exhaustive]".to_string()],
+            ..Default::default()
+        }];
+
+        let tokens = make_type_attributes(&type_attributes, TypeKind::Message, "PING", false);
+        assert_eq!(
+            tokens.to_string(),
+            quote! {
+                #[derive(PartialEq, core::cmp::Eq, Hash)]
+                #[allow(missing_docs)]
+                #[non_exhaustive]
+            }
+            .to_string()
+        );
+
+        let tokens = make_type_attributes(&type_attributes, TypeKind::Message, "ATTITUDE", true);
+        assert!(tokens.to_string().starts_with("# [derive (PartialEq)]"));
+    }
+
+    #[test]
+    fn type_attributes_validation() {
+        assert!(TypeAttributes {
+            derives: vec!["serde::Serialize".to_string()],
+            attributes: vec!["#[repr(C)]".to_string()],
+            ..Default::default()
+        }
+        .validate()
+        .is_ok());
+
+        for type_attributes in [
+            TypeAttributes {
+                derives: vec!["Partial Eq".to_string()],
+                ..Default::default()
+            },
+            TypeAttributes {
+                attributes: vec!["non_exhaustive".to_string()],
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                type_attributes.validate(),
+                Err(RustGenError::InvalidTypeAttributes(_))
+            ));
+        }
+    }
+}
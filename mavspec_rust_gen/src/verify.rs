@@ -0,0 +1,307 @@
+//! # Verification of existing generated code
+//!
+//! Compares generated code with files in output directory. See [`crate::BuildHelper::verify`].
+
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// Number of unchanged lines shown around changes in unified diff.
+const DIFF_CONTEXT: usize = 3;
+/// Maximum number of edits computed per file, files with more changes are shown as entirely replaced.
+const DIFF_MAX_EDITS: usize = 1000;
+
+/// Differences between generated code and existing files.
+///
+/// Paths are relative to the output directory.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// Files that should be generated but do not exist.
+    pub missing: Vec<PathBuf>,
+    /// Existing files listed as generated by the previous generation that are no longer generated.
+    ///
+    /// Files which were never generated (i.e. `README.md` or `Cargo.lock`) are not reported.
+    pub extra: Vec<PathBuf>,
+    /// Files with content different from generated code.
+    pub differing: Vec<PathBuf>,
+    /// Unified diff between existing (`a/`) and generated (`b/`) content of differing files.
+    pub diff: String,
+}
+
+impl VerificationReport {
+    /// Returns `true` if existing files are the same as generated ones.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.differing.is_empty()
+    }
+}
+
+impl Display for VerificationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "generated code is outdated: {} missing, {} extra, {} differing files",
+            self.missing.len(),
+            self.extra.len(),
+            self.differing.len()
+        )?;
+        for path in &self.missing {
+            writeln!(f, "missing: {}", path.display())?;
+        }
+        for path in &self.extra {
+            writeln!(f, "extra: {}", path.display())?;
+        }
+        for path in &self.differing {
+            writeln!(f, "differs: {}", path.display())?;
+        }
+        write!(f, "{}", self.diff)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Unified diff of `old` and `new` content of a file at `path`.
+///
+/// Lines are compared with their terminators, so files which differ only by line endings or by a newline at the end of
+/// file are not reported as equal. Last lines without newline are marked as in GNU diff.
+pub(crate) fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.split_inclusive('\n').collect();
+    let new: Vec<&str> = new.split_inclusive('\n').collect();
+    let edits = diff_lines(&old, &new);
+
+    let mut diff = format!("--- a/{path}\n+++ b/{path}\n");
+
+    // Positions of edits paired with positions in old and new content
+    let mut positions = Vec::with_capacity(edits.len());
+    let (mut old_pos, mut new_pos) = (0, 0);
+    for &edit in &edits {
+        positions.push((edit, old_pos, new_pos));
+        match edit {
+            Edit::Equal => {
+                old_pos += 1;
+                new_pos += 1;
+            }
+            Edit::Delete => old_pos += 1,
+            Edit::Insert => new_pos += 1,
+        }
+    }
+
+    let changes: Vec<usize> = (0..edits.len())
+        .filter(|&i| edits[i] != Edit::Equal)
+        .collect();
+    let mut i = 0;
+    while i < changes.len() {
+        // Merge changes separated by short runs of unchanged lines into a single hunk
+        let mut j = i;
+        while j + 1 < changes.len() && changes[j + 1] - changes[j] <= 2 * DIFF_CONTEXT + 1 {
+            j += 1;
+        }
+        let start = changes[i].saturating_sub(DIFF_CONTEXT);
+        let end = (changes[j] + DIFF_CONTEXT + 1).min(edits.len());
+
+        let hunk = &positions[start..end];
+        let old_len = hunk.iter().filter(|(e, ..)| *e != Edit::Insert).count();
+        let new_len = hunk.iter().filter(|(e, ..)| *e != Edit::Delete).count();
+        let (_, old_start, new_start) = hunk[0];
+        // Empty ranges start at the line preceding the hunk
+        let old_start = if old_len == 0 {
+            old_start
+        } else {
+            old_start + 1
+        };
+        let new_start = if new_len == 0 {
+            new_start
+        } else {
+            new_start + 1
+        };
+        diff.push_str(&format!(
+            "@@ -{old_start},{old_len} +{new_start},{new_len} @@\n"
+        ));
+
+        for &(edit, old_pos, new_pos) in hunk {
+            let (prefix, line) = match edit {
+                Edit::Equal => (' ', old[old_pos]),
+                Edit::Delete => ('-', old[old_pos]),
+                Edit::Insert => ('+', new[new_pos]),
+            };
+            diff.push(prefix);
+            diff.push_str(line);
+            if !line.ends_with('\n') {
+                diff.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+
+        i = j + 1;
+    }
+
+    diff
+}
+
+/// Shortest sequence of line edits transforming `old` into `new`.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut edits = vec![Edit::Equal; prefix];
+    match myers(old_middle, new_middle) {
+        Some(middle) => edits.extend(middle),
+        None => {
+            edits.resize(edits.len() + old_middle.len(), Edit::Delete);
+            edits.resize(edits.len() + new_middle.len(), Edit::Insert);
+        }
+    }
+    edits.resize(edits.len() + suffix, Edit::Equal);
+
+    edits
+}
+
+/// Myers' diff algorithm.
+///
+/// Returns [`None`] if edit distance exceeds [`DIFF_MAX_EDITS`].
+fn myers(old: &[&str], new: &[&str]) -> Option<Vec<Edit>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = (n + m) as usize;
+    let offset = max as isize + 1;
+    let mut v = vec![0isize; 2 * max + 3];
+    // Furthest reaching paths for diagonals `-d-1..=d+1` before each step `d`
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'search: for d in 0..=max.min(DIFF_MAX_EDITS) as isize {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let idx = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+        if d as usize == max.min(DIFF_MAX_EDITS) {
+            return None;
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            edits.push(Edit::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            edits.push(if x == prev_x {
+                Edit::Insert
+            } else {
+                Edit::Delete
+            });
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    edits.reverse();
+
+    Some(edits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_of_equal_content_is_empty() {
+        assert_eq!(
+            unified_diff("a.rs", "a\nb\n", "a\nb\n"),
+            "--- a/a.rs\n+++ b/a.rs\n"
+        );
+    }
+
+    #[test]
+    fn diff_hunks() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\nseventeen\n";
+
+        assert_eq!(
+            unified_diff("mod.rs", old, new),
+            "--- a/mod.rs\n+++ b/mod.rs\n\
+             @@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n\
+             @@ -14,3 +14,4 @@\n 14\n 15\n 16\n+seventeen\n"
+        );
+    }
+
+    #[test]
+    fn diff_added_and_removed_files() {
+        assert_eq!(
+            unified_diff("mod.rs", "", "a\nb\n"),
+            "--- a/mod.rs\n+++ b/mod.rs\n@@ -0,0 +1,2 @@\n+a\n+b\n"
+        );
+        assert_eq!(
+            unified_diff("mod.rs", "a\nb\n", ""),
+            "--- a/mod.rs\n+++ b/mod.rs\n@@ -1,2 +0,0 @@\n-a\n-b\n"
+        );
+    }
+
+    #[test]
+    fn diff_of_newline_at_end_of_file() {
+        assert_eq!(
+            unified_diff("mod.rs", "a\nb", "a\nb\n"),
+            "--- a/mod.rs\n+++ b/mod.rs\n@@ -1,2 +1,2 @@\n a\n-b\n\\ No newline at end of file\n+b\n"
+        );
+        assert_eq!(
+            unified_diff("mod.rs", "a\nb\n", "a\nc"),
+            "--- a/mod.rs\n+++ b/mod.rs\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n\\ No newline at end of file\n"
+        );
+        assert_eq!(
+            unified_diff("mod.rs", "a\r\n", "a\n"),
+            "--- a/mod.rs\n+++ b/mod.rs\n@@ -1,1 +1,1 @@\n-a\r\n+a\n"
+        );
+    }
+
+    #[test]
+    fn diff_is_minimal() {
+        let old = ["a", "b", "c", "a", "b", "b", "a"];
+        let new = ["c", "b", "a", "b", "a", "c"];
+        let edits = myers(&old, &new).unwrap();
+
+        let changes = edits.iter().filter(|&&e| e != Edit::Equal).count();
+        assert_eq!(changes, 5);
+        assert_eq!(
+            edits.iter().filter(|&&e| e != Edit::Insert).count(),
+            old.len()
+        );
+        assert_eq!(
+            edits.iter().filter(|&&e| e != Edit::Delete).count(),
+            new.len()
+        );
+    }
+}
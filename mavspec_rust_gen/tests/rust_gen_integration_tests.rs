@@ -1,12 +1,13 @@
 mod tests {
     use std::collections::HashSet;
-    use std::fs::remove_dir_all;
+    use std::fs::{read_to_string, remove_dir_all};
     use std::path::PathBuf;
+    use std::process::Command;
 
     use mavinspect::protocol::Microservices;
     use mavinspect::Inspector;
 
-    use mavspec_rust_gen::BuildHelper;
+    use mavspec_rust_gen::{BuildHelper, OutputLayout, TypeAttributes, TypeKind, WipPolicy};
 
     const CARGO_MANIFEST_PATH_TESTS: &str = "../tests/rust/Cargo.toml";
     const CARGO_MANIFEST_PATH_EXAMPLES: &str = "../examples/rust/Cargo.toml";
@@ -40,6 +41,7 @@ mod tests {
         BuildHelper::builder(&out_path)
             .set_protocol(protocol)
             .set_serde(true)
+            .set_arbitrary(true)
             .set_generate_tests(true)
             .generate()
             .unwrap();
@@ -62,12 +64,19 @@ mod tests {
             .unwrap();
 
         assert!(helper.generate_tests());
+        assert!(helper.generate_builders());
         assert_eq!(
             HashSet::from_iter(helper.messages().unwrap().iter().copied()),
             HashSet::from([
                 "DEFAULT",
                 "1ST_CLASS_MESSAGE",
                 "PROTOCOL_VERSION",
+                "GLOBAL_POSITION_INT",
+                "SYS_STATUS",
+                "STATUSTEXT",
+                "BATTERY_STATUS",
+                "NAMED_VALUE_FLOAT",
+                "HIGH_LATENCY2",
                 "TRY_FROM",
                 "DEBUG",
                 "MAV_INSPECT_V1",
@@ -76,6 +85,7 @@ mod tests {
                 "FROM",
                 "TRY_INTO",
                 "COPY",
+                "DEFAULT_VALUES",
             ])
         );
         assert!(helper
@@ -108,4 +118,142 @@ mod tests {
 
         remove_dir_all(out_path).unwrap();
     }
+
+    #[test]
+    fn generate_rust_wip_policies() {
+        let out_path = out_path().join("wip_policies");
+        // `LINK_NODE_STATUS` message is marked as work in progress, `SET_MODE` is deprecated
+        let messages_dir = out_path.join("dialects").join("common").join("messages");
+        let wip_message_path = messages_dir.join("link_node_status.rs");
+        let deprecated_message_path = messages_dir.join("set_mode.rs");
+
+        for wip in [WipPolicy::Include, WipPolicy::Exclude, WipPolicy::Warn] {
+            if let Err(err) = remove_dir_all(&out_path) {
+                log::debug!("Can't delete temporary directory '{out_path:?}': {err:?}. Proceed.");
+            }
+
+            BuildHelper::builder(&out_path)
+                .set_sources(&xml_definition_paths())
+                .set_include_dialects(&["common"])
+                .set_messages(&["LINK_NODE_STATUS", "SET_MODE"])
+                .set_wip(wip)
+                .generate()
+                .unwrap();
+
+            let deprecated_message = read_to_string(&deprecated_message_path).unwrap();
+            assert!(deprecated_message.contains("#[deprecated("));
+            assert!(deprecated_message.contains(
+                "note = \"Deprecated since 2015-12, replaced by `MAV_CMD_DO_SET_MODE`.\""
+            ));
+
+            match wip {
+                WipPolicy::Include => {
+                    let wip_message = read_to_string(&wip_message_path).unwrap();
+                    assert!(!wip_message.contains("#[deprecated"));
+                }
+                WipPolicy::Exclude => assert!(!wip_message_path.exists()),
+                WipPolicy::Warn => {
+                    let wip_message = read_to_string(&wip_message_path).unwrap();
+                    assert!(wip_message.contains("#[deprecated("));
+                    assert!(wip_message.contains("note = \"Work in progress"));
+                }
+            }
+        }
+
+        remove_dir_all(out_path).unwrap();
+    }
+
+    #[test]
+    fn generate_rust_type_attributes() {
+        let out_path = out_path().join("type_attributes");
+        let dialect_dir = out_path.join("dialects").join("mav_inspect_test");
+
+        if let Err(err) = remove_dir_all(&out_path) {
+            log::debug!("Can't delete temporary directory '{out_path:?}': {err:?}. Proceed.");
+        }
+
+        BuildHelper::builder(&out_path)
+            .set_sources(&xml_definition_paths())
+            .set_include_dialects(&["MAVInspect_test"])
+            .set_messages(&["MAV_INSPECT_V1", "DEFAULT_VALUES"])
+            .set_type_attributes(&[
+                TypeAttributes {
+                    derives: vec![
+                        "PartialEq".to_string(),
+                        "Eq".to_string(),
+                        "Debug".to_string(),
+                    ],
+                    ..Default::default()
+                },
+                TypeAttributes {
+                    kinds: vec![TypeKind::Message],
+                    names: vec!["MAV_INSPECT_*".to_string()],
+                    attributes: vec!["#[must_use]".to_string()],
+                    ..Default::default()
+                },
+            ])
+            .generate()
+            .unwrap();
+
+        let messages_dir = dialect_dir.join("messages");
+        let with_ints = read_to_string(messages_dir.join("mav_inspect_v1.rs")).unwrap();
+        let with_floats = read_to_string(messages_dir.join("default_values.rs")).unwrap();
+        let enumeration = read_to_string(dialect_dir.join("enums").join("small_enum.rs")).unwrap();
+        let dialect = read_to_string(dialect_dir.join("mod.rs")).unwrap();
+
+        assert!(with_ints.contains("#[derive(PartialEq, Eq)]"));
+        assert!(with_ints.contains("#[must_use]"));
+        assert!(with_floats.contains("#[derive(PartialEq)]"));
+        assert!(!with_floats.contains("#[must_use]"));
+        assert!(enumeration.contains("#[derive(PartialEq, Eq)]"));
+        assert!(dialect.contains("#[derive(PartialEq)]"));
+
+        assert!(BuildHelper::builder(&out_path)
+            .set_sources(&xml_definition_paths())
+            .set_type_attributes(&[TypeAttributes {
+                derives: vec!["#[derive(PartialEq)]".to_string()],
+                ..Default::default()
+            }])
+            .build()
+            .is_err());
+
+        remove_dir_all(out_path).unwrap();
+    }
+
+    /// Builds a generated workspace with `serde` and `std` features. Takes a while, run with `--ignored` flag.
+    #[test]
+    #[ignore]
+    fn build_generated_workspace() {
+        let out_path = out_path().join("workspace_build");
+
+        if let Err(err) = remove_dir_all(&out_path) {
+            log::debug!("Can't delete temporary directory '{out_path:?}': {err:?}. Proceed.");
+        }
+
+        BuildHelper::builder(&out_path)
+            .set_sources(&xml_definition_paths())
+            .set_include_dialects(&["MAVInspect_test"])
+            .set_serde(true)
+            .set_output_layout(OutputLayout::Workspace)
+            .set_mavspec_path("../mavspec")
+            .generate()
+            .unwrap();
+
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let status = Command::new(cargo)
+            .current_dir(&out_path)
+            .args(["build", "-p", "mavlink_mav_inspect_test"])
+            .args(["--features", "serde,std"])
+            .env(
+                "CARGO_TARGET_DIR",
+                std::env::current_dir()
+                    .unwrap()
+                    .join("../target/generated_workspace"),
+            )
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        remove_dir_all(out_path).unwrap();
+    }
 }
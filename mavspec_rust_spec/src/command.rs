@@ -0,0 +1,310 @@
+//! # MAVLink command metadata
+
+/// Static metadata for a MAVLink [command](https://mavlink.io/en/messages/common.html#mav_commands).
+///
+/// Generated `MAV_CMD` enums expose tables of [`CommandInfo`] built from the command definitions in
+/// dialect XML. All data is stored in `'static` memory, which makes these tables available for
+/// `no_std` targets.
+#[derive(Copy, Clone, Debug)]
+pub struct CommandInfo {
+    value: u32,
+    name: &'static str,
+    description: &'static str,
+    has_location: Option<bool>,
+    is_destination: Option<bool>,
+    mission_only: Option<bool>,
+    params: &'static [CommandParamInfo],
+}
+
+impl CommandInfo {
+    /// Default constructor.
+    ///
+    /// Command flags are set to [`None`]. Use [`CommandInfo::with_has_location`],
+    /// [`CommandInfo::with_is_destination`], and [`CommandInfo::with_mission_only`] to define them.
+    pub const fn new(
+        value: u32,
+        name: &'static str,
+        description: &'static str,
+        params: &'static [CommandParamInfo],
+    ) -> Self {
+        Self {
+            value,
+            name,
+            description,
+            has_location: None,
+            is_destination: None,
+            mission_only: None,
+            params,
+        }
+    }
+
+    /// Sets `hasLocation` flag.
+    pub const fn with_has_location(mut self, has_location: bool) -> Self {
+        self.has_location = Some(has_location);
+        self
+    }
+
+    /// Sets `isDestination` flag.
+    pub const fn with_is_destination(mut self, is_destination: bool) -> Self {
+        self.is_destination = Some(is_destination);
+        self
+    }
+
+    /// Sets `missionOnly` flag.
+    pub const fn with_mission_only(mut self, mission_only: bool) -> Self {
+        self.mission_only = Some(mission_only);
+        self
+    }
+
+    /// Command value (`MAV_CMD` enum entry value).
+    #[inline]
+    pub const fn value(&self) -> u32 {
+        self.value
+    }
+
+    /// Command name as defined in XML (i.e. `MAV_CMD_NAV_WAYPOINT`).
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Command description.
+    #[inline]
+    pub const fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// Whether command contains location in params `5`, `6`, and `7` (`hasLocation` flag).
+    ///
+    /// Returns [`None`] if flag is not specified.
+    #[inline]
+    pub const fn has_location(&self) -> Option<bool> {
+        self.has_location
+    }
+
+    /// Whether command is a destination (`isDestination` flag).
+    ///
+    /// Returns [`None`] if flag is not specified.
+    #[inline]
+    pub const fn is_destination(&self) -> Option<bool> {
+        self.is_destination
+    }
+
+    /// Whether command can be used only in missions (`missionOnly` flag).
+    ///
+    /// Returns [`None`] if flag is not specified.
+    #[inline]
+    pub const fn mission_only(&self) -> Option<bool> {
+        self.mission_only
+    }
+
+    /// Command parameters sorted by index.
+    ///
+    /// Only parameters mentioned in XML definition are present.
+    #[inline]
+    pub const fn params(&self) -> &'static [CommandParamInfo] {
+        self.params
+    }
+
+    /// Returns parameter by its `index` (from `1` to `7`).
+    ///
+    /// Returns [`None`] if parameter is not defined.
+    pub fn param(&self, index: u8) -> Option<&'static CommandParamInfo> {
+        self.params.iter().find(|param| param.index == index)
+    }
+}
+
+/// Static metadata for a MAVLink command parameter.
+///
+/// See [`CommandInfo`].
+#[derive(Copy, Clone, Debug)]
+pub struct CommandParamInfo {
+    index: u8,
+    description: &'static str,
+    label: Option<&'static str>,
+    units: Option<&'static str>,
+    enum_name: Option<&'static str>,
+    decimal_places: Option<u8>,
+    increment: Option<f32>,
+    min_value: Option<f32>,
+    max_value: Option<f32>,
+    reserved: bool,
+    default: Option<f32>,
+}
+
+impl CommandParamInfo {
+    /// Default constructor.
+    ///
+    /// Optional metadata is set by `with_*` methods.
+    pub const fn new(index: u8, description: &'static str) -> Self {
+        Self {
+            index,
+            description,
+            label: None,
+            units: None,
+            enum_name: None,
+            decimal_places: None,
+            increment: None,
+            min_value: None,
+            max_value: None,
+            reserved: false,
+            default: None,
+        }
+    }
+
+    /// Sets parameter label.
+    pub const fn with_label(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Sets parameter units.
+    pub const fn with_units(mut self, units: &'static str) -> Self {
+        self.units = Some(units);
+        self
+    }
+
+    /// Sets name of the MAVLink enum which defines parameter values.
+    pub const fn with_enum_name(mut self, enum_name: &'static str) -> Self {
+        self.enum_name = Some(enum_name);
+        self
+    }
+
+    /// Sets number of decimal places.
+    pub const fn with_decimal_places(mut self, decimal_places: u8) -> Self {
+        self.decimal_places = Some(decimal_places);
+        self
+    }
+
+    /// Sets parameter increment.
+    pub const fn with_increment(mut self, increment: f32) -> Self {
+        self.increment = Some(increment);
+        self
+    }
+
+    /// Sets minimum parameter value.
+    pub const fn with_min_value(mut self, min_value: f32) -> Self {
+        self.min_value = Some(min_value);
+        self
+    }
+
+    /// Sets maximum parameter value.
+    pub const fn with_max_value(mut self, max_value: f32) -> Self {
+        self.max_value = Some(max_value);
+        self
+    }
+
+    /// Marks parameter as reserved.
+    pub const fn with_reserved(mut self, reserved: bool) -> Self {
+        self.reserved = reserved;
+        self
+    }
+
+    /// Sets default parameter value.
+    pub const fn with_default(mut self, default: f32) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    /// Parameter index (from `1` to `7`).
+    #[inline]
+    pub const fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Parameter description.
+    #[inline]
+    pub const fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// Parameter label used in user interfaces.
+    #[inline]
+    pub const fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+
+    /// Parameter units (i.e. `deg`, `m/s`).
+    #[inline]
+    pub const fn units(&self) -> Option<&'static str> {
+        self.units
+    }
+
+    /// Name of the MAVLink enum which defines parameter values (i.e. `MAV_FRAME`).
+    #[inline]
+    pub const fn enum_name(&self) -> Option<&'static str> {
+        self.enum_name
+    }
+
+    /// Number of decimal places for user interfaces.
+    #[inline]
+    pub const fn decimal_places(&self) -> Option<u8> {
+        self.decimal_places
+    }
+
+    /// Recommended increment for user interfaces.
+    #[inline]
+    pub const fn increment(&self) -> Option<f32> {
+        self.increment
+    }
+
+    /// Minimum parameter value.
+    #[inline]
+    pub const fn min_value(&self) -> Option<f32> {
+        self.min_value
+    }
+
+    /// Maximum parameter value.
+    #[inline]
+    pub const fn max_value(&self) -> Option<f32> {
+        self.max_value
+    }
+
+    /// Whether parameter is reserved.
+    ///
+    /// Reserved parameters should be set to their [`CommandParamInfo::default`] value (`0` if not
+    /// specified).
+    #[inline]
+    pub const fn reserved(&self) -> bool {
+        self.reserved
+    }
+
+    /// Default parameter value.
+    #[inline]
+    pub const fn default(&self) -> Option<f32> {
+        self.default
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[CommandParamInfo] = &[
+        CommandParamInfo::new(1, "Hold time.")
+            .with_label("Hold")
+            .with_units("s")
+            .with_min_value(0.0),
+        CommandParamInfo::new(3, "Empty.").with_reserved(true),
+    ];
+    const COMMAND: CommandInfo =
+        CommandInfo::new(16, "MAV_CMD_NAV_WAYPOINT", "Navigate to waypoint.", PARAMS)
+            .with_has_location(true);
+
+    #[test]
+    fn command_info() {
+        assert_eq!(COMMAND.value(), 16);
+        assert_eq!(COMMAND.has_location(), Some(true));
+        assert_eq!(COMMAND.is_destination(), None);
+        assert_eq!(COMMAND.params().len(), 2);
+
+        let param = COMMAND.param(1).unwrap();
+        assert_eq!(param.label(), Some("Hold"));
+        assert_eq!(param.units(), Some("s"));
+        assert_eq!(param.min_value(), Some(0.0));
+        assert!(!param.reserved());
+
+        assert!(COMMAND.param(3).unwrap().reserved());
+        assert!(COMMAND.param(2).is_none());
+    }
+}
@@ -0,0 +1,739 @@
+//! # MAVLink enum metadata
+//!
+//! [`EnumSpec`] exposes static metadata of MAVLink enums and bitmasks. Functions [`fmt_bitmask`] and [`parse_bitmask`]
+//! implement textual representation of bitmasks as `|`-separated flag names (i.e. `MAV_MODE_FLAG_SAFETY_ARMED|
+//! MAV_MODE_FLAG_TEST_ENABLED`). Generated bitmasks use them for [`Display`](core::fmt::Display) and
+//! [`FromStr`](core::str::FromStr).
+
+use core::fmt::Write;
+
+use bitflags::parser::{ParseHex, WriteHex};
+use bitflags::Flags;
+
+use crate::SpecError;
+
+/// Static metadata for a MAVLink enum entry or bitmask flag.
+///
+/// Generated enums and bitmasks expose tables of [`EnumEntryInfo`] through [`EnumSpec::ENTRIES`]. All data is stored
+/// in `'static` memory, which makes these tables available for `no_std` targets.
+#[derive(Copy, Clone, Debug)]
+pub struct EnumEntryInfo<T: 'static> {
+    value: T,
+    name: &'static str,
+    name_stripped: &'static str,
+    description: &'static str,
+}
+
+impl<T: Copy + 'static> EnumEntryInfo<T> {
+    /// Default constructor.
+    pub const fn new(
+        value: T,
+        name: &'static str,
+        name_stripped: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            value,
+            name,
+            name_stripped,
+            description,
+        }
+    }
+
+    /// Entry value (enum variant or bitmask flag).
+    #[inline]
+    pub const fn value(&self) -> T {
+        self.value
+    }
+
+    /// Canonical MAVLink name of the entry (i.e. `MAV_TYPE_QUADROTOR`).
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Entry name without enum name prefix (i.e. `QUADROTOR` for `MAV_TYPE_QUADROTOR`).
+    #[inline]
+    pub const fn name_stripped(&self) -> &'static str {
+        self.name_stripped
+    }
+
+    /// Entry description (empty if not specified).
+    #[inline]
+    pub const fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+/// MAVLink enum or bitmask with static metadata.
+///
+/// Implemented by generated enums and bitmasks, as well as by enums with `#[derive(Enum)]`.
+pub trait EnumSpec: Copy + 'static {
+    /// Canonical MAVLink name of the enum (i.e. `MAV_TYPE`).
+    const NAME: &'static str;
+
+    /// All enum entries (or bitmask flags) in order of their definition.
+    const ENTRIES: &'static [EnumEntryInfo<Self>];
+
+    /// Metadata for this value.
+    ///
+    /// Returns [`None`] for bitmask values which do not correspond to exactly one flag.
+    fn entry_info(&self) -> Option<&'static EnumEntryInfo<Self>>;
+
+    /// Metadata for entry with specified name.
+    ///
+    /// Accepts both canonical (`MAV_FRAME_GLOBAL_RELATIVE_ALT_INT`) and prefix-stripped (`GLOBAL_RELATIVE_ALT_INT`)
+    /// names. Comparison is ASCII case-insensitive, leading and trailing whitespaces are ignored.
+    fn entry_by_name(name: &str) -> Option<&'static EnumEntryInfo<Self>> {
+        let name = name.trim();
+        Self::ENTRIES.iter().find(|entry| {
+            entry.name().eq_ignore_ascii_case(name)
+                || entry.name_stripped().eq_ignore_ascii_case(name)
+        })
+    }
+}
+
+/// Writes bitmask as `|`-separated canonical flag names.
+///
+/// Bits which do not correspond to any flag are written as a single hex number (i.e. `MAV_MODE_FLAG_TEST_ENABLED|0x40`).
+/// Empty bitmask is written as an empty string.
+pub fn fmt_bitmask<T>(value: &T, f: &mut impl Write) -> core::fmt::Result
+where
+    T: EnumSpec + Flags,
+    T::Bits: WriteHex,
+{
+    let mut remaining = T::from_bits_retain(value.bits());
+    let mut is_first = true;
+
+    for entry in T::ENTRIES {
+        let flag = entry.value();
+        if flag.is_empty() || !value.contains(flag) || !remaining.intersects(flag) {
+            continue;
+        }
+        if !is_first {
+            f.write_char('|')?;
+        }
+        is_first = false;
+        f.write_str(entry.name())?;
+        remaining.remove(flag);
+    }
+
+    if !remaining.is_empty() {
+        if !is_first {
+            f.write_char('|')?;
+        }
+        f.write_str("0x")?;
+        remaining.bits().write_hex(&mut *f)?;
+    }
+
+    Ok(())
+}
+
+/// Parses bitmask from `|`-separated flag names.
+///
+/// Flag names are matched by [`EnumSpec::entry_by_name`], hex numbers (i.e. `0x40`) are interpreted as raw bits.
+/// Whitespaces around flag names are ignored and an empty string is parsed as an empty bitmask.
+///
+/// Returns [`SpecError::InvalidEnumName`] if any of the flags can't be recognized.
+pub fn parse_bitmask<T>(s: &str) -> Result<T, SpecError>
+where
+    T: EnumSpec + Flags,
+    T::Bits: ParseHex,
+{
+    let invalid_name = || SpecError::InvalidEnumName { enum_name: T::NAME };
+
+    let s = s.trim();
+    let mut value = T::empty();
+    if s.is_empty() {
+        return Ok(value);
+    }
+
+    for flag in s.split('|') {
+        let flag = flag.trim();
+        let flag = match flag.strip_prefix("0x").or_else(|| flag.strip_prefix("0X")) {
+            Some(hex) => T::from_bits_retain(T::Bits::parse_hex(hex).map_err(|_| invalid_name())?),
+            None => T::entry_by_name(flag).ok_or_else(invalid_name)?.value(),
+        };
+        value.insert(flag);
+    }
+
+    Ok(value)
+}
+
+/// Serializes bitmask as a sequence of canonical flag names.
+///
+/// Bits which do not correspond to any flag are serialized as a single hex string (i.e. `"0x40"`).
+#[cfg(feature = "serde")]
+pub fn serialize_bitmask_flags<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: EnumSpec + Flags,
+    T::Bits: WriteHex,
+    S: serde::Serializer,
+{
+    use serde::ser::SerializeSeq;
+
+    struct HexBits<T: Flags>(T);
+
+    impl<T: Flags> core::fmt::Display for HexBits<T>
+    where
+        T::Bits: WriteHex,
+    {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("0x")?;
+            self.0.bits().write_hex(f)
+        }
+    }
+
+    impl<T: Flags> serde::Serialize for HexBits<T>
+    where
+        T::Bits: WriteHex,
+    {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    let mut remaining = T::from_bits_retain(value.bits());
+    let mut seq = serializer.serialize_seq(None)?;
+
+    for entry in T::ENTRIES {
+        let flag = entry.value();
+        if flag.is_empty() || !value.contains(flag) || !remaining.intersects(flag) {
+            continue;
+        }
+        seq.serialize_element(entry.name())?;
+        remaining.remove(flag);
+    }
+    if !remaining.is_empty() {
+        seq.serialize_element(&HexBits(remaining))?;
+    }
+
+    seq.end()
+}
+
+/// Deserializes MAVLink enum from entry name (see [`EnumSpec::entry_by_name`]) or numeric value.
+///
+/// Numeric values are converted by `from_int`. For formats which are not
+/// [human-readable](serde::Deserializer::is_human_readable), only numeric value of type `R` is accepted.
+#[cfg(feature = "serde")]
+pub fn deserialize_enum<'de, T, R, D>(
+    deserializer: D,
+    from_int: fn(u64) -> Option<T>,
+) -> Result<T, D::Error>
+where
+    T: EnumSpec,
+    R: serde::Deserialize<'de> + Into<u64>,
+    D: serde::Deserializer<'de>,
+{
+    let visitor = EnumVisitor {
+        from_int,
+        from_str: |s| T::entry_by_name(s).map(|entry| entry.value()),
+        union: None,
+        variants: &[],
+    };
+    visitor.deserialize::<R, D>(deserializer)
+}
+
+/// Deserializes MAVLink enum which is serialized by `serde` derive macros.
+///
+/// Human-readable formats accept Rust variant names listed in `variants` along with everything accepted by
+/// [`deserialize_enum`]. Other formats expect variant index or name, the same way as derived
+/// [`Deserialize`](serde::Deserialize) does.
+///
+/// Variant names should be listed in the order of [`EnumSpec::ENTRIES`].
+#[cfg(feature = "serde")]
+pub fn deserialize_derived_enum<'de, T, R, D>(
+    deserializer: D,
+    from_int: fn(u64) -> Option<T>,
+    variants: &'static [&'static str],
+) -> Result<T, D::Error>
+where
+    T: EnumSpec,
+    R: serde::Deserialize<'de> + Into<u64>,
+    D: serde::Deserializer<'de>,
+{
+    let visitor = EnumVisitor {
+        from_int,
+        from_str: |s| T::entry_by_name(s).map(|entry| entry.value()),
+        union: None,
+        variants,
+    };
+    visitor.deserialize::<R, D>(deserializer)
+}
+
+/// Deserializes MAVLink bitmask from numeric value, `|`-separated flag names (see [`parse_bitmask`]), or a sequence
+/// of flag names.
+///
+/// Numeric values are converted by `from_int`. For formats which are not
+/// [human-readable](serde::Deserializer::is_human_readable), only numeric value of type `R` is accepted.
+#[cfg(feature = "serde")]
+pub fn deserialize_bitmask<'de, T, R, D>(
+    deserializer: D,
+    from_int: fn(u64) -> Option<T>,
+) -> Result<T, D::Error>
+where
+    T: EnumSpec + Flags,
+    T::Bits: ParseHex,
+    R: serde::Deserialize<'de> + Into<u64>,
+    D: serde::Deserializer<'de>,
+{
+    let visitor = EnumVisitor {
+        from_int,
+        from_str: |s| parse_bitmask(s).ok(),
+        union: Some(T::union),
+        variants: &[],
+    };
+    visitor.deserialize::<R, D>(deserializer)
+}
+
+#[cfg(feature = "serde")]
+#[derive(Copy, Clone)]
+struct EnumVisitor<T> {
+    from_int: fn(u64) -> Option<T>,
+    from_str: fn(&str) -> Option<T>,
+    union: Option<fn(T, T) -> T>,
+    variants: &'static [&'static str],
+}
+
+#[cfg(feature = "serde")]
+impl<T: EnumSpec> EnumVisitor<T> {
+    fn deserialize<'de, R, D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        R: serde::Deserialize<'de> + Into<u64>,
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(self)
+        } else if !self.variants.is_empty() {
+            deserializer.deserialize_enum(T::NAME, self.variants, self)
+        } else {
+            let value: u64 = R::deserialize(deserializer)?.into();
+            serde::de::Visitor::visit_u64(self, value)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: EnumSpec> serde::de::Visitor<'de> for EnumVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.union {
+            None => write!(formatter, "`{}` entry name or value", T::NAME),
+            Some(_) => write!(formatter, "`{}` flag names or value", T::NAME),
+        }
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        (self.from_int)(v)
+            .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        match u64::try_from(v) {
+            Ok(value) => self.visit_u64(value),
+            Err(_) => Err(E::invalid_value(serde::de::Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        (self.from_str)(v)
+            .or_else(|| {
+                let index = self.variants.iter().position(|variant| *variant == v)?;
+                Some(T::ENTRIES[index].value())
+            })
+            .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+    }
+
+    fn visit_enum<A: serde::de::EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        if self.variants.is_empty() {
+            return Err(serde::de::Error::invalid_type(
+                serde::de::Unexpected::Enum,
+                &self,
+            ));
+        }
+
+        let (index, variant) = data.variant_seed(VariantIndex(self.variants))?;
+        serde::de::VariantAccess::unit_variant(variant)?;
+        Ok(T::ENTRIES[index].value())
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let union = match self.union {
+            Some(union) => union,
+            None => {
+                return Err(serde::de::Error::invalid_type(
+                    serde::de::Unexpected::Seq,
+                    &self,
+                ))
+            }
+        };
+        let flag = Self {
+            union: None,
+            ..self
+        };
+
+        let mut value = self.visit_u64(0)?;
+        while let Some(next) = seq.next_element_seed(flag)? {
+            value = union(value, next);
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: EnumSpec> serde::de::DeserializeSeed<'de> for EnumVisitor<T> {
+    type Value = T;
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+/// Index of enum variant specified by its position or Rust name.
+#[cfg(feature = "serde")]
+#[derive(Copy, Clone)]
+struct VariantIndex(&'static [&'static str]);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::DeserializeSeed<'de> for VariantIndex {
+    type Value = usize;
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for VariantIndex {
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("variant identifier")
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<usize, E> {
+        match usize::try_from(v) {
+            Ok(index) if index < self.0.len() => Ok(index),
+            _ => Err(E::invalid_value(serde::de::Unexpected::Unsigned(v), &self)),
+        }
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<usize, E> {
+        self.0
+            .iter()
+            .position(|variant| *variant == v)
+            .ok_or_else(|| E::unknown_variant(v, self.0))
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<usize, E> {
+        self.0
+            .iter()
+            .position(|variant| variant.as_bytes() == v)
+            .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Bytes(v), &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    enum Mood {
+        Serious,
+        Grumpy,
+    }
+
+    impl EnumSpec for Mood {
+        const NAME: &'static str = "MOOD";
+        const ENTRIES: &'static [EnumEntryInfo<Self>] = &[
+            EnumEntryInfo::new(Mood::Serious, "MOOD_SERIOUS", "SERIOUS", "Serious mood."),
+            EnumEntryInfo::new(Mood::Grumpy, "MOOD_GRUMPY", "GRUMPY", ""),
+        ];
+
+        fn entry_info(&self) -> Option<&'static EnumEntryInfo<Self>> {
+            Self::ENTRIES.iter().find(|entry| entry.value() == *self)
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Flavour(u8);
+
+    bitflags::bitflags! {
+        impl Flavour: u8 {
+            const SWEET = 1;
+            const SOUR = 1 << 1;
+            const BITTER = 1 << 2;
+        }
+    }
+
+    impl EnumSpec for Flavour {
+        const NAME: &'static str = "FLAVOUR";
+        const ENTRIES: &'static [EnumEntryInfo<Self>] = &[
+            EnumEntryInfo::new(Flavour::SWEET, "FLAVOUR_SWEET", "SWEET", ""),
+            EnumEntryInfo::new(Flavour::SOUR, "FLAVOUR_SOUR", "SOUR", ""),
+            EnumEntryInfo::new(Flavour::BITTER, "FLAVOUR_BITTER", "BITTER", ""),
+        ];
+
+        fn entry_info(&self) -> Option<&'static EnumEntryInfo<Self>> {
+            Self::ENTRIES.iter().find(|entry| entry.value() == *self)
+        }
+    }
+
+    struct Buf<const N: usize> {
+        bytes: [u8; N],
+        len: usize,
+    }
+
+    impl<const N: usize> Buf<N> {
+        fn new() -> Self {
+            Self {
+                bytes: [0; N],
+                len: 0,
+            }
+        }
+
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+        }
+    }
+
+    impl<const N: usize> Write for Buf<N> {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            self.bytes[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+            self.len += s.len();
+            Ok(())
+        }
+    }
+
+    fn bitmask_to_buf(value: Flavour) -> Buf<64> {
+        let mut buf = Buf::new();
+        fmt_bitmask(&value, &mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn enum_entries() {
+        assert_eq!(Mood::NAME, "MOOD");
+        assert_eq!(Mood::ENTRIES.len(), 2);
+
+        let info = Mood::Grumpy.entry_info().unwrap();
+        assert_eq!(info.value(), Mood::Grumpy);
+        assert_eq!(info.name(), "MOOD_GRUMPY");
+        assert_eq!(info.name_stripped(), "GRUMPY");
+        assert_eq!(info.description(), "");
+        assert_eq!(
+            Mood::Serious.entry_info().unwrap().description(),
+            "Serious mood."
+        );
+    }
+
+    #[test]
+    fn entry_by_name() {
+        assert_eq!(
+            Mood::entry_by_name("MOOD_GRUMPY").unwrap().value(),
+            Mood::Grumpy
+        );
+        assert_eq!(Mood::entry_by_name("GRUMPY").unwrap().value(), Mood::Grumpy);
+        assert_eq!(
+            Mood::entry_by_name(" mood_serious ").unwrap().value(),
+            Mood::Serious
+        );
+        assert_eq!(
+            Mood::entry_by_name("serious").unwrap().value(),
+            Mood::Serious
+        );
+        assert!(Mood::entry_by_name("MOOD").is_none());
+        assert!(Mood::entry_by_name("").is_none());
+    }
+
+    #[test]
+    fn bitmask_display() {
+        assert_eq!(bitmask_to_buf(Flavour::empty()).as_str(), "");
+        assert_eq!(bitmask_to_buf(Flavour::SOUR).as_str(), "FLAVOUR_SOUR");
+        assert_eq!(
+            bitmask_to_buf(Flavour::SWEET | Flavour::BITTER).as_str(),
+            "FLAVOUR_SWEET|FLAVOUR_BITTER"
+        );
+        assert_eq!(
+            bitmask_to_buf(Flavour::SOUR | Flavour::from_bits_retain(0b1001_0000)).as_str(),
+            "FLAVOUR_SOUR|0x90"
+        );
+        assert_eq!(
+            bitmask_to_buf(Flavour::from_bits_retain(0x80)).as_str(),
+            "0x80"
+        );
+    }
+
+    #[test]
+    fn bitmask_parse() {
+        assert_eq!(parse_bitmask::<Flavour>("").unwrap(), Flavour::empty());
+        assert_eq!(
+            parse_bitmask::<Flavour>("FLAVOUR_SOUR").unwrap(),
+            Flavour::SOUR
+        );
+        assert_eq!(
+            parse_bitmask::<Flavour>("sweet | FLAVOUR_BITTER").unwrap(),
+            Flavour::SWEET | Flavour::BITTER
+        );
+        assert_eq!(
+            parse_bitmask::<Flavour>("SOUR|0x90").unwrap(),
+            Flavour::SOUR | Flavour::from_bits_retain(0x90)
+        );
+
+        for value in [
+            Flavour::empty(),
+            Flavour::all(),
+            Flavour::BITTER | Flavour::from_bits_retain(0x40),
+        ] {
+            let buf = bitmask_to_buf(value);
+            assert_eq!(parse_bitmask::<Flavour>(buf.as_str()).unwrap(), value);
+        }
+
+        for invalid in ["SALTY", "SWEET|", "SWEET||SOUR", "0xZZ", "0x1FF"] {
+            assert!(matches!(
+                parse_bitmask::<Flavour>(invalid),
+                Err(SpecError::InvalidEnumName {
+                    enum_name: "FLAVOUR"
+                })
+            ));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_support {
+        use super::*;
+
+        impl<'de> serde::Deserialize<'de> for Mood {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_enum::<Self, u8, D>(deserializer, |value| match value {
+                    0 => Some(Mood::Serious),
+                    1 => Some(Mood::Grumpy),
+                    _ => None,
+                })
+            }
+        }
+
+        impl serde::Serialize for Flavour {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_bitmask_flags(self, serializer)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for Flavour {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_bitmask::<Self, u8, D>(deserializer, |value| {
+                    u8::try_from(value).ok().map(Self::from_bits_retain)
+                })
+            }
+        }
+
+        #[test]
+        fn enum_deserialize() {
+            for json in ["1", "\"MOOD_GRUMPY\"", "\"grumpy\""] {
+                assert_eq!(serde_json::from_str::<Mood>(json).unwrap(), Mood::Grumpy);
+            }
+            for json in ["2", "-1", "\"MOOD_HAPPY\"", "[\"GRUMPY\"]"] {
+                assert!(serde_json::from_str::<Mood>(json).is_err());
+            }
+        }
+
+        #[test]
+        fn bitmask_serde() {
+            let value = Flavour::SWEET | Flavour::BITTER | Flavour::from_bits_retain(0x40);
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(json, "[\"FLAVOUR_SWEET\",\"FLAVOUR_BITTER\",\"0x40\"]");
+            assert_eq!(serde_json::from_str::<Flavour>(&json).unwrap(), value);
+
+            for json in [
+                "69",
+                "\"FLAVOUR_SWEET|BITTER|0x40\"",
+                "[\"sweet\", \"BITTER\", 64]",
+                "[\"SWEET|BITTER\", \"0x40\"]",
+            ] {
+                assert_eq!(serde_json::from_str::<Flavour>(json).unwrap(), value);
+            }
+            assert_eq!(
+                serde_json::from_str::<Flavour>("[]").unwrap(),
+                Flavour::empty()
+            );
+
+            for json in ["256", "\"SALTY\"", "[\"SWEET\", [\"SOUR\"]]"] {
+                assert!(serde_json::from_str::<Flavour>(json).is_err());
+            }
+        }
+
+        /// Mimics `serde` derive for `Mood` enum.
+        #[derive(Debug, PartialEq)]
+        struct DerivedMood(Mood);
+
+        impl<'de> serde::Deserialize<'de> for DerivedMood {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_derived_enum::<Mood, u8, D>(
+                    deserializer,
+                    |value| match value {
+                        0 => Some(Mood::Serious),
+                        1 => Some(Mood::Grumpy),
+                        _ => None,
+                    },
+                    &["Serious", "Grumpy"],
+                )
+                .map(DerivedMood)
+            }
+        }
+
+        /// Non-human-readable deserializer which represents enum variants by their indices.
+        struct Compact(u32);
+
+        impl<'de> serde::Deserializer<'de> for Compact {
+            type Error = serde::de::value::Error;
+
+            fn deserialize_any<V: serde::de::Visitor<'de>>(
+                self,
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                visitor.visit_u32(self.0)
+            }
+
+            fn deserialize_enum<V: serde::de::Visitor<'de>>(
+                self,
+                name: &'static str,
+                variants: &'static [&'static str],
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                serde::de::IntoDeserializer::<Self::Error>::into_deserializer(self.0)
+                    .deserialize_enum(name, variants, visitor)
+            }
+
+            fn is_human_readable(&self) -> bool {
+                false
+            }
+
+            serde::forward_to_deserialize_any! {
+                bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+                bytes byte_buf option unit unit_struct newtype_struct seq tuple
+                tuple_struct map struct identifier ignored_any
+            }
+        }
+
+        #[test]
+        fn derived_enum_deserialize() {
+            for json in ["1", "\"MOOD_GRUMPY\"", "\"grumpy\"", "\"Grumpy\""] {
+                assert_eq!(
+                    serde_json::from_str::<DerivedMood>(json).unwrap(),
+                    DerivedMood(Mood::Grumpy)
+                );
+            }
+            for json in ["2", "\"MOOD_HAPPY\"", "\"Happy\""] {
+                assert!(serde_json::from_str::<DerivedMood>(json).is_err());
+            }
+
+            use serde::Deserialize;
+            assert_eq!(
+                DerivedMood::deserialize(Compact(1)).unwrap(),
+                DerivedMood(Mood::Grumpy)
+            );
+            assert!(DerivedMood::deserialize(Compact(2)).is_err());
+        }
+    }
+}
@@ -1,12 +1,17 @@
 //! # MAVLib errors
 
+use core::fmt::{Display, Formatter};
+
 use crate::types::{MavLinkVersion, MessageId};
 
 /// Errors related to MAVLink message specification and encoding/decoding.
 ///
 /// All errors except [`SpecError::InvalidV1PayloadSize`] are related to wrong user input like
 /// incorrectly chosen MAVLink dialect or protocol version.
+///
+/// New variants may be added in minor releases, so matches should have a wildcard arm.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum SpecError {
     /// MAVLink version is not supported.
     UnsupportedMavLinkVersion {
@@ -22,6 +27,11 @@ pub enum SpecError {
         /// Enum name.
         enum_name: &'static str,
     },
+    /// String does not match any entry name of MAVLink enum (or any flag name of MAVLink bitmask).
+    InvalidEnumName {
+        /// Enum name.
+        enum_name: &'static str,
+    },
     /// Invalid size of the `MAVLink 1` payload.
     InvalidV1PayloadSize {
         /// Actual payload size in bytes.
@@ -29,4 +39,88 @@ pub enum SpecError {
         /// Expected payload size.
         expected: usize,
     },
+    /// String does not fit into MAVLink `char[N]` field.
+    StringTooLong {
+        /// Actual string length in bytes.
+        actual: usize,
+        /// Field capacity in bytes.
+        capacity: usize,
+    },
+    /// String contains NUL character which would terminate MAVLink string.
+    StringContainsNul {
+        /// Position of NUL character in bytes.
+        position: usize,
+    },
+    /// MAVLink string is not a valid UTF-8.
+    InvalidUtf8 {
+        /// Length of a valid UTF-8 prefix in bytes.
+        valid_up_to: usize,
+    },
+}
+
+impl Display for SpecError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SpecError::UnsupportedMavLinkVersion { actual, minimal } => write!(
+                f,
+                "MAVLink version {actual:?} is not supported, minimal version is {minimal:?}"
+            ),
+            SpecError::NotInDialect(id) => write!(f, "message with ID {id} is not in dialect"),
+            SpecError::InvalidEnumValue { enum_name } => {
+                write!(f, "invalid value of `{enum_name}` enum")
+            }
+            SpecError::InvalidEnumName { enum_name } => {
+                write!(f, "unknown entry name of `{enum_name}` enum")
+            }
+            SpecError::InvalidV1PayloadSize { actual, expected } => write!(
+                f,
+                "invalid `MAVLink 1` payload size: expected {expected} bytes, got {actual}"
+            ),
+            SpecError::StringTooLong { actual, capacity } => write!(
+                f,
+                "string of {actual} bytes exceeds capacity of {capacity} bytes"
+            ),
+            SpecError::StringContainsNul { position } => {
+                write!(f, "string contains NUL character at byte {position}")
+            }
+            SpecError::InvalidUtf8 { valid_up_to } => {
+                write!(f, "string is not valid UTF-8 after {valid_up_to} bytes")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SpecError {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            SpecError::InvalidEnumName {
+                enum_name: "MavType"
+            }
+            .to_string(),
+            "unknown entry name of `MavType` enum"
+        );
+        assert_eq!(
+            SpecError::StringTooLong {
+                actual: 51,
+                capacity: 50
+            }
+            .to_string(),
+            "string of 51 bytes exceeds capacity of 50 bytes"
+        );
+        assert_eq!(
+            SpecError::StringContainsNul { position: 3 }.to_string(),
+            "string contains NUL character at byte 3"
+        );
+        assert_eq!(
+            SpecError::InvalidUtf8 { valid_up_to: 2 }.to_string(),
+            "string is not valid UTF-8 after 2 bytes"
+        );
+    }
 }
@@ -0,0 +1,179 @@
+//! # MAVLink message field metadata
+
+/// Static metadata for a MAVLink message field.
+///
+/// Generated message modules expose tables of [`FieldInfo`] in the same order as fields appear in XML definition.
+/// All data is stored in `'static` memory, which makes these tables available for `no_std` targets.
+#[derive(Copy, Clone, Debug)]
+pub struct FieldInfo {
+    name: &'static str,
+    units: Option<&'static str>,
+    invalid: Option<InvalidValue>,
+}
+
+/// Sentinel which marks a field value as "not provided".
+///
+/// Defined by `invalid` attribute of a field in XML definition. Numeric sentinels are stored as [`Sentinel`] in the
+/// type class of a field, so 64-bit integer sentinels are exact.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InvalidValue {
+    /// Field is invalid when equals to this value (i.e. `UINT16_MAX`, `NaN`).
+    Value(Sentinel),
+    /// Array field is invalid when all items are equal to this value (i.e. `[UINT16_MAX]`).
+    AllItems(Sentinel),
+    /// Array field is invalid when its first item is equal to this value (i.e. `[NaN:]`).
+    FirstItem(Sentinel),
+    /// Field is invalid when equals to a value of enum entry with this name.
+    EnumEntry(&'static str),
+}
+
+/// Numeric sentinel value of [`InvalidValue`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Sentinel {
+    /// Sentinel of a signed integer field.
+    Int(i64),
+    /// Sentinel of an unsigned integer or `char` field.
+    UInt(u64),
+    /// Sentinel of a floating point field.
+    Float(f64),
+}
+
+impl FieldInfo {
+    /// Default constructor.
+    ///
+    /// Optional metadata is set by `with_*` methods.
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            units: None,
+            invalid: None,
+        }
+    }
+
+    /// Sets field units.
+    pub const fn with_units(mut self, units: &'static str) -> Self {
+        self.units = Some(units);
+        self
+    }
+
+    /// Sets sentinel which marks field value as invalid.
+    pub const fn with_invalid(mut self, invalid: InvalidValue) -> Self {
+        self.invalid = Some(invalid);
+        self
+    }
+
+    /// Field name as defined in XML (i.e. `time_boot_ms`).
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Field units as defined in XML (i.e. `degE7`, `cm/s`).
+    #[inline]
+    pub const fn units(&self) -> Option<&'static str> {
+        self.units
+    }
+
+    /// Sentinel which marks field value as invalid (not provided).
+    #[inline]
+    pub const fn invalid(&self) -> Option<InvalidValue> {
+        self.invalid
+    }
+}
+
+impl InvalidValue {
+    /// Checks whether numeric `value` is equal to the sentinel.
+    ///
+    /// For array sentinels, `value` is compared with the sentinel of a single item. Always returns `false` for
+    /// [`InvalidValue::EnumEntry`]. See [`Sentinel::matches`].
+    pub fn matches(&self, value: impl Into<Sentinel>) -> bool {
+        match self {
+            InvalidValue::Value(sentinel)
+            | InvalidValue::AllItems(sentinel)
+            | InvalidValue::FirstItem(sentinel) => sentinel.matches(value),
+            InvalidValue::EnumEntry(_) => false,
+        }
+    }
+}
+
+impl Sentinel {
+    /// Checks whether numeric `value` is equal to the sentinel.
+    ///
+    /// Values are compared exactly regardless of their types. `NaN` sentinel matches `NaN` values.
+    pub fn matches(&self, value: impl Into<Sentinel>) -> bool {
+        match (*self, value.into()) {
+            (Sentinel::Float(a), Sentinel::Float(b)) => (a.is_nan() && b.is_nan()) || a == b,
+            (Sentinel::Float(a), Sentinel::Int(b)) | (Sentinel::Int(b), Sentinel::Float(a)) => {
+                float_eq_int(a, b as i128)
+            }
+            (Sentinel::Float(a), Sentinel::UInt(b)) | (Sentinel::UInt(b), Sentinel::Float(a)) => {
+                float_eq_int(a, b as i128)
+            }
+            (Sentinel::Int(a), Sentinel::UInt(b)) | (Sentinel::UInt(b), Sentinel::Int(a)) => {
+                a as i128 == b as i128
+            }
+            (Sentinel::Int(a), Sentinel::Int(b)) => a == b,
+            (Sentinel::UInt(a), Sentinel::UInt(b)) => a == b,
+        }
+    }
+}
+
+/// Checks that float is an integer equal to `int` (`f64` has no `fract` in `no_std`).
+fn float_eq_int(float: f64, int: i128) -> bool {
+    // Saturating cast round trips only for integers within `i128` range, `NaN` becomes zero
+    float as i128 as f64 == float && float as i128 == int
+}
+
+macro_rules! impl_sentinel_from {
+    ($variant:ident, $target:ty, $($source:ty),+) => {
+        $(
+            impl From<$source> for Sentinel {
+                fn from(value: $source) -> Self {
+                    Sentinel::$variant(value as $target)
+                }
+            }
+        )+
+    };
+}
+
+impl_sentinel_from!(Int, i64, i8, i16, i32, i64);
+impl_sentinel_from!(UInt, u64, u8, u16, u32, u64);
+impl_sentinel_from!(Float, f64, f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_value_matches() {
+        assert!(InvalidValue::Value(Sentinel::UInt(u16::MAX as u64)).matches(u16::MAX));
+        assert!(!InvalidValue::Value(Sentinel::UInt(u16::MAX as u64)).matches(0u16));
+        assert!(InvalidValue::AllItems(Sentinel::Float(f64::NAN)).matches(f32::NAN));
+        assert!(!InvalidValue::FirstItem(Sentinel::Float(f64::NAN)).matches(1.0));
+        assert!(!InvalidValue::EnumEntry("MAV_LANDED_STATE_UNDEFINED").matches(0u8));
+
+        let info = FieldInfo::new("eph").with_invalid(InvalidValue::Value(Sentinel::UInt(65535)));
+        assert_eq!(
+            info.invalid(),
+            Some(InvalidValue::Value(Sentinel::UInt(65535)))
+        );
+    }
+
+    #[test]
+    fn sentinel_matches_exactly() {
+        // Not representable as `f64`
+        assert!(Sentinel::UInt(u64::MAX).matches(u64::MAX));
+        assert!(!Sentinel::UInt(u64::MAX).matches(u64::MAX - 1));
+        assert!(Sentinel::Int(i64::MAX).matches(i64::MAX));
+        assert!(!Sentinel::Int(i64::MAX).matches(i64::MAX - 1));
+        assert!(!Sentinel::UInt(u64::MAX).matches(u64::MAX as f64));
+
+        assert!(Sentinel::Int(-1).matches(-1i16));
+        assert!(!Sentinel::Int(-1).matches(u64::MAX));
+        assert!(Sentinel::Int(100).matches(100u8));
+        assert!(Sentinel::UInt(100).matches(100.0f32));
+        assert!(!Sentinel::UInt(100).matches(100.5));
+        assert!(!Sentinel::Int(0).matches(f64::NAN));
+        assert!(Sentinel::Float(f64::INFINITY).matches(f32::INFINITY));
+    }
+}
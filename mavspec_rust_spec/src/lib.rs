@@ -32,6 +32,33 @@
 //! dialect capabilities, or minor dialect version. It also exposes [`DialectSpec::message_info`] method which provides
 //! message specifications for dialect messages.  
 //!
+//! # Fields & Units
+//!
+//! [`FieldInfo`] provides static metadata for message fields such as units and [`InvalidValue`] sentinels which mark
+//! values as "not provided".
+//!
+//! Module [`units`] provides [`units::Quantity`] wrapper which tags raw field values with MAVLink units without
+//! changing their wire representation.
+//!
+//! # Strings
+//!
+//! [`MavString`] is a fixed-size string for MAVLink `char[N]` fields. It has the same wire representation as `[u8; N]`
+//! and provides UTF-8 decoding and construction from `&str`.
+//!
+//! # Enums
+//!
+//! [`EnumSpec`] is implemented by MAVLink enums and bitmasks. It exposes canonical enum name and static table of
+//! [`EnumEntryInfo`] with names, values, and descriptions of enum entries (or bitmask flags).
+//!
+//! Enums and bitmasks implement [`Display`](core::fmt::Display) and [`FromStr`](core::str::FromStr) using canonical
+//! MAVLink names. Bitmasks are represented as `|`-separated flag names, see [`enums::fmt_bitmask`] and
+//! [`enums::parse_bitmask`].
+//!
+//! # Commands
+//!
+//! [`CommandInfo`] and [`CommandParamInfo`] provide static metadata for MAVLink commands and their parameters.
+//! Generated `MAV_CMD` enums expose these tables for each command retained in a dialect.
+//!
 //! # Types & Conventions
 //!
 //! Modules [`consts`] and [`types`] provide constants, type aliases, enums and wrapper types. The entities are intended
@@ -49,19 +76,30 @@
 )]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod command;
 pub mod consts;
 mod dialect;
+pub mod enums;
 mod error;
+mod field;
 mod message;
 mod payload;
+mod stream;
+mod string;
 pub mod types;
+pub mod units;
 
 pub use bitflags;
 pub use tbytes;
 
+pub use command::{CommandInfo, CommandParamInfo};
 pub use dialect::{Dialect, DialectSpec};
+pub use enums::{EnumEntryInfo, EnumSpec};
 pub use error::SpecError;
+pub use field::{FieldInfo, InvalidValue, Sentinel};
 pub use message::{Message, MessageInfo, MessageSpec, MessageSpecStatic};
 pub use payload::{IntoPayload, Payload};
+pub use stream::{MessageInstance, StreamKey};
+pub use string::MavString;
 #[doc(inline)]
 pub use types::MavLinkVersion;
@@ -2,6 +2,7 @@
 
 use crate::consts::MESSAGE_ID_V1_MAX;
 use crate::payload::IntoPayload;
+use crate::stream::{MessageInstance, StreamKey};
 use crate::types::{CrcExtra, MavLinkVersion, MessageId};
 
 /// Generic MAVLink message specification.
@@ -60,7 +61,21 @@ pub trait MessageSpecStatic {
 ///
 /// Concrete MAVLink message that knows its specs through [`MessageSpec`] and allows to decode itself into
 /// [`Payload`](crate::payload::Payload) via [`IntoPayload`].
-pub trait Message: MessageSpec + IntoPayload {}
+pub trait Message: MessageSpec + IntoPayload {
+    /// Instance of a message stream defined by a field marked with `instance="true"`.
+    ///
+    /// Returns [`None`] for messages without instance fields.
+    #[inline]
+    fn stream_instance(&self) -> Option<MessageInstance> {
+        None
+    }
+
+    /// Key of a message stream which combines message ID and [`Message::stream_instance`].
+    #[inline]
+    fn stream_key(&self) -> StreamKey {
+        StreamKey::new(self.id(), self.stream_instance())
+    }
+}
 
 /// Generic information about MAVLink message.
 ///
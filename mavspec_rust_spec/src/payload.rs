@@ -161,6 +161,7 @@ impl Payload {
         Self::new(self.id, self.bytes(), MavLinkVersion::V2)
     }
 
+    /// Calculates payload length with trailing zero bytes truncated according to `MAVLink 2` rules.
     pub fn truncated_length(slice: &[u8]) -> usize {
         let mut n: usize = slice.len();
         // The first byte of the payload is never truncated, even if the payload consists entirely of zeros.
@@ -217,7 +218,7 @@ pub trait IntoPayload {
     /// # Errors
     ///
     /// * Returns [`SpecError::UnsupportedMavLinkVersion`] if specified
-    /// MAVLink `version` is not supported.
+    ///   MAVLink `version` is not supported.
     fn encode(&self, version: MavLinkVersion) -> Result<Payload, SpecError>;
 }
 
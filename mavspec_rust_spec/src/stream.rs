@@ -0,0 +1,125 @@
+//! # MAVLink message streams
+//!
+//! Messages like `BATTERY_STATUS` or `GPS_RAW_INT` may describe several independent entities (batteries, GNSS
+//! receivers, etc.). MAVLink definitions mark fields which identify such entities with `instance="true"` attribute.
+//! A message stream is therefore identified by a message ID together with an optional instance.
+
+use crate::string::MavString;
+use crate::types::MessageId;
+
+/// Instance of a MAVLink message stream specified by field marked with `instance="true"`.
+///
+/// Most instance fields are integers or enums (i.e. `BATTERY_STATUS.id`), which are represented as
+/// [`MessageInstance::Index`]. Some messages (i.e. `NAMED_VALUE_FLOAT`) use strings as instances, these are
+/// represented as [`MessageInstance::Name`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MessageInstance {
+    /// Numeric instance (integer field or raw value of an enum).
+    Index(u32),
+    /// Named instance.
+    ///
+    /// Names longer than [`MessageInstance::NAME_CAPACITY`] bytes are truncated.
+    Name(MavString<{ MessageInstance::NAME_CAPACITY }>),
+}
+
+impl MessageInstance {
+    /// Maximum length of a named instance in bytes.
+    pub const NAME_CAPACITY: usize = 16;
+
+    /// Creates named instance from raw bytes of a `char[N]` field.
+    ///
+    /// Bytes after the first NUL byte are ignored. Names longer than [`Self::NAME_CAPACITY`] are truncated at UTF-8
+    /// character boundary.
+    pub fn from_name(raw: &[u8]) -> Self {
+        let mut len = raw.iter().position(|&byte| byte == 0).unwrap_or(raw.len());
+        if len > Self::NAME_CAPACITY {
+            len = Self::NAME_CAPACITY;
+            // Step back over UTF-8 continuation bytes, so multibyte characters are not split
+            while len > 0 && raw[len] & 0b1100_0000 == 0b1000_0000 {
+                len -= 1;
+            }
+        }
+
+        let mut bytes = [0u8; Self::NAME_CAPACITY];
+        bytes[..len].copy_from_slice(&raw[..len]);
+        Self::Name(MavString::from_raw(bytes))
+    }
+}
+
+impl From<u32> for MessageInstance {
+    fn from(value: u32) -> Self {
+        Self::Index(value)
+    }
+}
+
+/// Key of a MAVLink message stream which combines message ID and [`MessageInstance`].
+///
+/// Messages without instance fields have a single stream per message ID. Use this key to store latest messages in
+/// telemetry caches, so values of different instances (i.e. batteries) will not overwrite each other.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamKey {
+    id: MessageId,
+    instance: Option<MessageInstance>,
+}
+
+impl StreamKey {
+    /// Default constructor.
+    #[inline]
+    pub const fn new(id: MessageId, instance: Option<MessageInstance>) -> Self {
+        Self { id, instance }
+    }
+
+    /// MAVLink message ID.
+    #[inline]
+    pub const fn id(&self) -> MessageId {
+        self.id
+    }
+
+    /// Message instance if message has a field marked as `instance`.
+    #[inline]
+    pub const fn instance(&self) -> Option<MessageInstance> {
+        self.instance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_instances() {
+        assert_eq!(
+            MessageInstance::from_name(b"temp\0\0\0\0\0\0"),
+            MessageInstance::Name(MavString::try_from("temp").unwrap())
+        );
+        assert_eq!(
+            MessageInstance::from_name(b"temp\0garbage"),
+            MessageInstance::from_name(b"temp")
+        );
+        assert_eq!(
+            MessageInstance::from_name(b"a_name_longer_than_capacity"),
+            MessageInstance::Name(MavString::try_from("a_name_longer_th").unwrap())
+        );
+        assert_eq!(
+            MessageInstance::from_name("температура".as_bytes()),
+            MessageInstance::Name(MavString::try_from("температ").unwrap())
+        );
+        assert_eq!(
+            MessageInstance::from_name("a_name_longer_xт".as_bytes()),
+            MessageInstance::Name(MavString::try_from("a_name_longer_x").unwrap())
+        );
+    }
+
+    #[test]
+    fn stream_keys() {
+        let battery_1 = StreamKey::new(147, Some(MessageInstance::Index(0)));
+        let battery_2 = StreamKey::new(147, Some(MessageInstance::from(1)));
+
+        assert_ne!(battery_1, battery_2);
+        assert_eq!(battery_2.id(), 147);
+        assert_eq!(battery_2.instance(), Some(MessageInstance::Index(1)));
+        assert_eq!(StreamKey::new(0, None).instance(), None);
+    }
+}
@@ -0,0 +1,386 @@
+//! # MAVLink strings
+//!
+//! Fixed-size strings for MAVLink `char[N]` fields.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::fmt::{Debug, Display, Formatter, Write};
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+
+use crate::SpecError;
+
+/// Fixed-size string for MAVLink `char[N]` fields (i.e. `STATUSTEXT.text` or `PARAM_VALUE.param_id`).
+///
+/// MAVLink strings are NUL-terminated if they are shorter than `N` and are not terminated at all if they occupy the
+/// whole field. [`MavString`] has exactly the same wire representation as `[u8; N]`: it keeps raw bytes as they were
+/// received and interprets them as a string up to the first NUL byte.
+///
+/// Since raw bytes are not guaranteed to be valid UTF-8, [`MavString::as_str`] is fallible. Use
+/// [`MavString::as_str_lossy`] to get the longest valid prefix instead.
+///
+/// Strings can be constructed from `&str` by [`TryFrom`] (or [`FromStr`]) which reject strings that do not fit or
+/// contain NUL bytes, or by [`MavString::from_str_truncated`] which truncates long strings at a character boundary.
+///
+/// When `serde` feature is enabled, [`MavString`] is (de)serialized as a string. Invalid UTF-8 sequences are
+/// serialized as `U+FFFD` replacement characters.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct MavString<const N: usize> {
+    bytes: [u8; N],
+}
+
+impl<const N: usize> MavString<N> {
+    /// Maximum length of a string in bytes.
+    pub const CAPACITY: usize = N;
+
+    /// Creates an empty string.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { bytes: [0u8; N] }
+    }
+
+    /// Creates string from raw bytes as they are encoded in MAVLink payload.
+    #[inline]
+    pub const fn from_raw(bytes: [u8; N]) -> Self {
+        Self { bytes }
+    }
+
+    /// Raw bytes as they are encoded in MAVLink payload (including NUL padding).
+    #[inline]
+    pub const fn raw(&self) -> &[u8; N] {
+        &self.bytes
+    }
+
+    /// Consumes string and returns raw bytes.
+    #[inline]
+    pub const fn into_raw(self) -> [u8; N] {
+        self.bytes
+    }
+
+    /// Creates string from `value` truncating it to at most `N` bytes.
+    ///
+    /// Truncation never splits a multibyte character, so the result is always valid UTF-8. The string is also
+    /// truncated at the first NUL character (if any) since NUL terminates MAVLink strings.
+    pub fn from_str_truncated(value: &str) -> Self {
+        let value = match value.find('\0') {
+            Some(position) => &value[..position],
+            None => value,
+        };
+
+        let mut len = value.len().min(N);
+        while !value.is_char_boundary(len) {
+            len -= 1;
+        }
+
+        let mut bytes = [0u8; N];
+        bytes[..len].copy_from_slice(&value.as_bytes()[..len]);
+        Self { bytes }
+    }
+
+    /// String bytes up to the first NUL byte.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len()]
+    }
+
+    /// String length in bytes (not including NUL padding).
+    pub fn len(&self) -> usize {
+        self.bytes.iter().position(|&b| b == 0).unwrap_or(N)
+    }
+
+    /// Returns `true` if string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// String contents as `&str`.
+    ///
+    /// Returns [`SpecError::InvalidUtf8`] if string bytes are not valid UTF-8.
+    pub fn as_str(&self) -> Result<&str, SpecError> {
+        core::str::from_utf8(self.as_bytes()).map_err(|err| SpecError::InvalidUtf8 {
+            valid_up_to: err.valid_up_to(),
+        })
+    }
+
+    /// The longest prefix of string which is valid UTF-8.
+    ///
+    /// Does not require memory allocation. For replacement of invalid sequences with `U+FFFD` use
+    /// [`MavString::to_string_lossy`] (requires `alloc` feature) or [`Display`].
+    pub fn as_str_lossy(&self) -> &str {
+        let bytes = self.as_bytes();
+        match core::str::from_utf8(bytes) {
+            Ok(value) => value,
+            // Bytes up to `valid_up_to` are guaranteed to be valid UTF-8
+            Err(err) => core::str::from_utf8(&bytes[..err.valid_up_to()]).unwrap_or_default(),
+        }
+    }
+
+    /// String contents with invalid UTF-8 sequences replaced by `U+FFFD`.
+    #[cfg(feature = "alloc")]
+    pub fn to_string_lossy(&self) -> alloc::borrow::Cow<'_, str> {
+        alloc::string::String::from_utf8_lossy(self.as_bytes())
+    }
+}
+
+impl<const N: usize> Default for MavString<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for MavString<N> {
+    #[inline]
+    fn from(bytes: [u8; N]) -> Self {
+        Self::from_raw(bytes)
+    }
+}
+
+impl<const N: usize> From<MavString<N>> for [u8; N] {
+    #[inline]
+    fn from(value: MavString<N>) -> Self {
+        value.into_raw()
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for MavString<N> {
+    type Error = SpecError;
+
+    /// Creates string from `value`.
+    ///
+    /// Returns [`SpecError::StringTooLong`] if `value` exceeds `N` bytes and [`SpecError::StringContainsNul`] if
+    /// `value` contains NUL characters.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.len() > N {
+            return Err(SpecError::StringTooLong {
+                actual: value.len(),
+                capacity: N,
+            });
+        }
+        if let Some(position) = value.find('\0') {
+            return Err(SpecError::StringContainsNul { position });
+        }
+
+        let mut bytes = [0u8; N];
+        bytes[..value.len()].copy_from_slice(value.as_bytes());
+        Ok(Self { bytes })
+    }
+}
+
+impl<const N: usize> FromStr for MavString<N> {
+    type Err = SpecError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+impl<const N: usize> PartialEq for MavString<N> {
+    /// Strings are equal when their contents are equal, bytes after NUL terminator are ignored.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<const N: usize> Eq for MavString<N> {}
+
+impl<const N: usize> PartialEq<str> for MavString<N> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for MavString<N> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<const N: usize> Hash for MavString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state)
+    }
+}
+
+impl<const N: usize> Display for MavString<N> {
+    /// Formats string replacing invalid UTF-8 sequences with `U+FFFD`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut bytes = self.as_bytes();
+        loop {
+            match core::str::from_utf8(bytes) {
+                Ok(valid) => return f.write_str(valid),
+                Err(err) => {
+                    let (valid, rest) = bytes.split_at(err.valid_up_to());
+                    f.write_str(core::str::from_utf8(valid).unwrap_or_default())?;
+                    f.write_char(char::REPLACEMENT_CHARACTER)?;
+                    match err.error_len() {
+                        Some(len) => bytes = &rest[len..],
+                        // Incomplete sequence at the end of the string
+                        None => return Ok(()),
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<const N: usize> Debug for MavString<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.as_str() {
+            Ok(value) => write!(f, "MavString<{N}>({value:?})"),
+            Err(_) => write!(f, "MavString<{N}>({:?})", self.as_bytes()),
+        }
+    }
+}
+
+/// Serializes as a string, invalid UTF-8 sequences are replaced with `U+FFFD` (as by [`Display`]).
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for MavString<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Ok(value) => serializer.serialize_str(value),
+            // Received strings may contain garbage, which should not prevent serialization of the whole message
+            Err(_) => serializer.collect_str(self),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for MavString<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MavStringVisitor<const N: usize>;
+
+        impl<const N: usize> serde::de::Visitor<'_> for MavStringVisitor<N> {
+            type Value = MavString<N>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+                write!(
+                    formatter,
+                    "a string of at most {N} bytes without NUL characters"
+                )
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                MavString::try_from(v)
+                    .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(MavStringVisitor)
+    }
+}
+
+/// Generates valid UTF-8 strings without NUL characters truncated to `N` bytes.
+#[cfg(feature = "arbitrary")]
+impl<'a, const N: usize> arbitrary::Arbitrary<'a> for MavString<N> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        <&str>::arbitrary(u).map(Self::from_str_truncated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wire_representation() {
+        assert_eq!(core::mem::size_of::<MavString<16>>(), 16);
+
+        let raw = *b"abc\0\0\0\0\0";
+        let value = MavString::from_raw(raw);
+        assert_eq!(value.len(), 3);
+        assert_eq!(value.as_str().unwrap(), "abc");
+        assert_eq!(value.into_raw(), raw);
+
+        // Strings which occupy the whole field are not NUL-terminated
+        let value = MavString::from_raw(*b"abcd");
+        assert_eq!(value, "abcd");
+        assert_eq!(value.len(), 4);
+
+        // Bytes after NUL are ignored
+        assert_eq!(
+            MavString::from_raw(*b"ab\0x"),
+            MavString::from_raw(*b"ab\0y")
+        );
+    }
+
+    #[test]
+    fn construction() {
+        let value: MavString<4> = "abcd".parse().unwrap();
+        assert_eq!(value.raw(), b"abcd");
+
+        let value = MavString::<4>::try_from("ab").unwrap();
+        assert_eq!(value.raw(), b"ab\0\0");
+
+        assert!(matches!(
+            MavString::<4>::try_from("abcde"),
+            Err(SpecError::StringTooLong {
+                actual: 5,
+                capacity: 4
+            })
+        ));
+        assert!(matches!(
+            MavString::<4>::try_from("a\0b"),
+            Err(SpecError::StringContainsNul { position: 1 })
+        ));
+    }
+
+    #[test]
+    fn truncation() {
+        assert_eq!(MavString::<4>::from_str_truncated("abcdef"), "abcd");
+        assert_eq!(MavString::<4>::from_str_truncated("ab\0cd"), "ab");
+        // `ä` takes two bytes and should not be split
+        assert_eq!(MavString::<4>::from_str_truncated("abcä"), "abc");
+        assert_eq!(MavString::<4>::from_str_truncated("äbc"), "äbc");
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let value = MavString::from_raw(*b"ab\xFFcd\0");
+        assert!(matches!(
+            value.as_str(),
+            Err(SpecError::InvalidUtf8 { valid_up_to: 2 })
+        ));
+        assert_eq!(value.as_str_lossy(), "ab");
+
+        #[cfg(feature = "std")]
+        {
+            assert_eq!(value.to_string_lossy(), "ab\u{FFFD}cd");
+            assert_eq!(format!("{value}"), "ab\u{FFFD}cd");
+
+            // Same replacement as `String::from_utf8_lossy`, including truncated sequence at the end
+            let value = MavString::from_raw(*b"\xF0\x9F\x98a\xE2\x82");
+            assert_eq!(format!("{value}"), value.to_string_lossy());
+            assert_eq!(format!("{value}"), "\u{FFFD}a\u{FFFD}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn arbitrary_strings() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let data: Vec<u8> = (0..=255u8).cycle().take(1024).collect();
+        let mut u = Unstructured::new(&data);
+        for _ in 0..16 {
+            let value = MavString::<8>::arbitrary(&mut u).unwrap();
+            assert!(value.as_str().is_ok());
+            assert!(!value.as_bytes().contains(&0));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_invalid_utf8() {
+        let value = MavString::from_raw(*b"ab\xffc\0\0");
+        assert_eq!(serde_json::to_string(&value).unwrap(), "\"ab\u{fffd}c\"");
+
+        let value: MavString<8> = serde_json::from_str("\"abc\"").unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), "\"abc\"");
+    }
+}
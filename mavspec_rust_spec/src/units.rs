@@ -0,0 +1,420 @@
+//! # MAVLink units
+//!
+//! Typed wrappers for MAVLink message fields with [units](https://mavlink.io/en/guide/xml_schema.html#units)
+//! specified in XML definitions.
+//!
+//! [`Quantity`] is a transparent newtype over raw field value tagged with one of the [`Unit`] marker types defined in
+//! this module. It has exactly the same memory layout and wire representation as the underlying raw value.
+
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+/// MAVLink unit.
+///
+/// Implemented by marker types which correspond to units recognised by MAVLink XML schema.
+pub trait Unit: Copy + 'static {
+    /// Unit symbol as it appears in MAVLink XML definitions (i.e. `degE7` or `cm/s`).
+    const SYMBOL: &'static str;
+}
+
+/// Raw value of a MAVLink field tagged with [`Unit`].
+///
+/// This type does not perform any conversions. It simply keeps track of units at the type level. Use
+/// [`Quantity::raw`] to access the value as it is transmitted over the wire.
+#[repr(transparent)]
+pub struct Quantity<T, U: Unit> {
+    value: T,
+    units: PhantomData<U>,
+}
+
+impl<T, U: Unit> Quantity<T, U> {
+    /// Creates quantity from raw value.
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            units: PhantomData,
+        }
+    }
+
+    /// Unit symbol.
+    #[inline]
+    pub const fn units(&self) -> &'static str {
+        U::SYMBOL
+    }
+
+    /// Consumes quantity and returns raw value.
+    #[inline]
+    pub fn into_raw(self) -> T {
+        self.value
+    }
+
+    /// Mutable reference to raw value.
+    #[inline]
+    pub fn raw_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: Copy, U: Unit> Quantity<T, U> {
+    /// Raw value as it is encoded in MAVLink payload.
+    #[inline]
+    pub const fn raw(&self) -> T {
+        self.value
+    }
+}
+
+impl<T, U: Unit> From<T> for Quantity<T, U> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Clone, U: Unit> Clone for Quantity<T, U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<T: Copy, U: Unit> Copy for Quantity<T, U> {}
+
+impl<T: Default, U: Unit> Default for Quantity<T, U> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: PartialEq, U: Unit> PartialEq for Quantity<T, U> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq, U: Unit> Eq for Quantity<T, U> {}
+
+impl<T: PartialOrd, U: Unit> PartialOrd for Quantity<T, U> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Hash, U: Unit> Hash for Quantity<T, U> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<T: Debug, U: Unit> Debug for Quantity<T, U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?} {}", self.value, U::SYMBOL)
+    }
+}
+
+impl<T: Display, U: Unit> Display for Quantity<T, U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}", self.value, U::SYMBOL)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, U: Unit> serde::Serialize for Quantity<T, U> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>, U: Unit> serde::Deserialize<'de> for Quantity<T, U> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T: arbitrary::Arbitrary<'a>, U: Unit> arbitrary::Arbitrary<'a> for Quantity<T, U> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        T::arbitrary(u).map(Self::new)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        T::size_hint(depth)
+    }
+}
+
+/// Rounds `value` to the nearest integer with ties rounded away from zero.
+///
+/// This is a `no_std` counterpart of `f64::round` used by generated scaled setters. `NaN` is rounded to `NaN` which
+/// is then converted to `0` by `as` casts. Together with saturating `as` casts this defines how scaled setters
+/// convert values into fixed-point fields:
+///
+/// * value is rounded to the nearest raw value, ties are rounded away from zero;
+/// * values outside the range of the field type are saturated to its minimum or maximum;
+/// * `NaN` becomes `0`.
+pub fn round_half_away_from_zero(value: f64) -> f64 {
+    // Values of this magnitude are already integers (or infinite)
+    const EXACT_INTEGER_THRESHOLD: f64 = 4503599627370496.0; // 2^52
+
+    if value.is_nan() || value.abs() >= EXACT_INTEGER_THRESHOLD {
+        return value;
+    }
+
+    let truncated = value as i64 as f64;
+    let fraction = value - truncated;
+
+    if fraction >= 0.5 {
+        truncated + 1.0
+    } else if fraction <= -0.5 {
+        truncated - 1.0
+    } else {
+        truncated
+    }
+}
+
+macro_rules! units {
+    ($($(#[$attr:meta])* $name:ident => $symbol:literal,)*) => {
+        $(
+            $(#[$attr])*
+            #[doc = concat!("\n\nMAVLink units: `", $symbol, "`.")]
+            #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+            pub struct $name;
+
+            impl Unit for $name {
+                const SYMBOL: &'static str = $symbol;
+            }
+        )*
+
+        /// Returns `true` if MAVLink unit `symbol` has a corresponding [`Unit`] marker type.
+        pub fn is_recognised(symbol: &str) -> bool {
+            matches!(symbol, $($symbol)|*)
+        }
+
+        /// Name of the [`Unit`] marker type which corresponds to MAVLink unit `symbol`.
+        ///
+        /// Returns [`None`] if unit is not recognised.
+        pub fn type_name(symbol: &str) -> Option<&'static str> {
+            match symbol {
+                $($symbol => Some(stringify!($name)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+units! {
+    // Time
+    /// Seconds.
+    Seconds => "s",
+    /// Deciseconds.
+    DeciSeconds => "ds",
+    /// Centiseconds.
+    CentiSeconds => "cs",
+    /// Milliseconds.
+    MilliSeconds => "ms",
+    /// Microseconds.
+    MicroSeconds => "us",
+    /// Nanoseconds.
+    NanoSeconds => "ns",
+    /// Hertz.
+    Hertz => "Hz",
+    /// Megahertz.
+    MegaHertz => "MHz",
+    // Distance
+    /// Kilometres.
+    KiloMetres => "km",
+    /// Decametres.
+    DecaMetres => "dam",
+    /// Metres.
+    Metres => "m",
+    /// Metres per second.
+    MetresPerSecond => "m/s",
+    /// Metres per second squared.
+    MetresPerSecondSquared => "m/s/s",
+    /// Metres per second times 5.
+    FiveMetresPerSecond => "m/s*5",
+    /// Decimetres.
+    DeciMetres => "dm",
+    /// Decimetres per second.
+    DeciMetresPerSecond => "dm/s",
+    /// Centimetres.
+    CentiMetres => "cm",
+    /// Square centimetres.
+    SquareCentiMetres => "cm^2",
+    /// Centimetres per second.
+    CentiMetresPerSecond => "cm/s",
+    /// Millimetres.
+    MilliMetres => "mm",
+    /// Millimetres per second.
+    MilliMetresPerSecond => "mm/s",
+    /// Millimetres per hour.
+    MilliMetresPerHour => "mm/h",
+    // Temperature
+    /// Kelvins.
+    Kelvins => "K",
+    /// Degrees Celsius.
+    DegreesCelsius => "degC",
+    /// Centidegrees Celsius.
+    CentiDegreesCelsius => "cdegC",
+    // Angles
+    /// Radians.
+    Radians => "rad",
+    /// Radians per second.
+    RadiansPerSecond => "rad/s",
+    /// Milliradians per second.
+    MilliRadiansPerSecond => "mrad/s",
+    /// Degrees.
+    Degrees => "deg",
+    /// Half degrees.
+    HalfDegrees => "deg/2",
+    /// Degrees per second.
+    DegreesPerSecond => "deg/s",
+    /// Centidegrees.
+    CentiDegrees => "cdeg",
+    /// Centidegrees per second.
+    CentiDegreesPerSecond => "cdeg/s",
+    /// Degrees times 10^5.
+    DegreesE5 => "degE5",
+    /// Degrees times 10^7.
+    DegreesE7 => "degE7",
+    /// Rotations per minute.
+    RotationsPerMinute => "rpm",
+    // Electricity
+    /// Volts.
+    Volts => "V",
+    /// Centivolts.
+    CentiVolts => "cV",
+    /// Millivolts.
+    MilliVolts => "mV",
+    /// Amperes.
+    Amperes => "A",
+    /// Ampere hours.
+    AmpereHours => "Ah",
+    /// Centiamperes.
+    CentiAmperes => "cA",
+    /// Milliamperes.
+    MilliAmperes => "mA",
+    /// Milliampere hours.
+    MilliAmpereHours => "mAh",
+    // Magnetism
+    /// Milliteslas.
+    MilliTeslas => "mT",
+    /// Gauss.
+    Gauss => "gauss",
+    /// Milligauss.
+    MilliGauss => "mgauss",
+    // Energy
+    /// Hectojoules.
+    HectoJoules => "hJ",
+    // Power
+    /// Watts.
+    Watts => "W",
+    // Force
+    /// Milli-G.
+    MilliG => "mG",
+    // Mass
+    /// Grams.
+    Grams => "g",
+    /// Kilograms.
+    KiloGrams => "kg",
+    // Pressure
+    /// Pascals.
+    Pascals => "Pa",
+    /// Hectopascals.
+    HectoPascals => "hPa",
+    /// Kilopascals.
+    KiloPascals => "kPa",
+    /// Millibars.
+    MilliBars => "mbar",
+    // Ratio
+    /// Percents.
+    Percents => "%",
+    /// Decipercents.
+    DeciPercents => "d%",
+    /// Centipercents.
+    CentiPercents => "c%",
+    /// Decibels.
+    DeciBels => "dB",
+    /// Decibel-milliwatts.
+    DeciBelMilliWatts => "dBm",
+    // Digital
+    /// Kibibytes.
+    KibiBytes => "KiB",
+    /// Kibibytes per second.
+    KibiBytesPerSecond => "KiB/s",
+    /// Mebibytes.
+    MebiBytes => "MiB",
+    /// Mebibytes per second.
+    MebiBytesPerSecond => "MiB/s",
+    /// Bytes.
+    Bytes => "bytes",
+    /// Bytes per second.
+    BytesPerSecond => "bytes/s",
+    /// Bits per second.
+    BitsPerSecond => "bits/s",
+    /// Pixels.
+    Pixels => "pix",
+    /// Decipixels.
+    DeciPixels => "dpix",
+    // Flow
+    /// Grams per minute.
+    GramsPerMinute => "g/min",
+    /// Cubic centimetres per minute.
+    CubicCentiMetresPerMinute => "cm^3/min",
+    // Volume
+    /// Cubic centimetres.
+    CubicCentiMetres => "cm^3",
+    /// Litres.
+    Litres => "l",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantity_is_transparent() {
+        assert_eq!(
+            core::mem::size_of::<Quantity<i32, DegreesE7>>(),
+            core::mem::size_of::<i32>()
+        );
+
+        let lat: Quantity<i32, DegreesE7> = Quantity::new(-353621474);
+        assert_eq!(lat.raw(), -353621474);
+        assert_eq!(lat.units(), "degE7");
+        assert_eq!(lat, Quantity::from(-353621474));
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!(round_half_away_from_zero(0.0), 0.0);
+        assert_eq!(round_half_away_from_zero(0.49), 0.0);
+        assert_eq!(round_half_away_from_zero(0.5), 1.0);
+        assert_eq!(round_half_away_from_zero(-0.5), -1.0);
+        assert_eq!(round_half_away_from_zero(-1.49), -1.0);
+        assert_eq!(round_half_away_from_zero(2.5), 3.0);
+        assert_eq!(round_half_away_from_zero(1e300), 1e300);
+        assert_eq!(round_half_away_from_zero(f64::INFINITY), f64::INFINITY);
+        assert!(round_half_away_from_zero(f64::NAN).is_nan());
+        assert_eq!(round_half_away_from_zero(f64::NAN) as i32, 0);
+    }
+
+    #[test]
+    fn recognised_units() {
+        assert!(is_recognised("cm/s"));
+        assert!(is_recognised("mV"));
+        assert!(!is_recognised("furlongs"));
+    }
+
+    #[test]
+    fn type_names() {
+        assert_eq!(type_name("cm/s"), Some("CentiMetresPerSecond"));
+        assert_eq!(type_name("dBm"), Some("DeciBelMilliWatts"));
+        assert_eq!(type_name("furlongs"), None);
+    }
+}
@@ -1,8 +1,10 @@
 use std::env::var;
-use std::path::Path;
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use mavspec::rust::gen::BuildHelper;
+use mavspec::rust::gen::test_vectors::write_test_vectors;
+use mavspec::rust::gen::{BuildHelper, OutputLayout, SerdeEnumRepr};
 
 /// Updates git submodules.
 ///
@@ -41,19 +43,91 @@ fn main() {
         included_dialects
     };
 
-    let destination = Path::new(&var("OUT_DIR").unwrap()).join("mavlink");
+    let out_dir = Path::new(&var("OUT_DIR").unwrap()).to_path_buf();
+    let destination = out_dir.join("mavlink");
     let sources = [
         manifest_dir.join("message_definitions").join("standard"),
         manifest_dir.join("message_definitions").join("extra"),
     ];
     let manifest_path = manifest_dir.join("Cargo.toml");
     let serde_feature_enabled = var("CARGO_FEATURE_SERDE").is_ok();
+    let arbitrary_feature_enabled = var("CARGO_FEATURE_ARBITRARY").is_ok();
+    let typed_units_feature_enabled = var("CARGO_FEATURE_TYPED_UNITS").is_ok();
+    let async_feature_enabled = var("CARGO_FEATURE_ASYNC").is_ok();
 
-    BuildHelper::builder(destination)
+    let mut builder = BuildHelper::builder(destination);
+    builder
         .set_sources(&sources)
         .set_manifest_path(&manifest_path)
         .set_include_dialects(&included_dialects)
         .set_serde(serde_feature_enabled)
+        .set_arbitrary(arbitrary_feature_enabled)
+        .set_async_handlers(async_feature_enabled)
+        .set_typed_units(typed_units_feature_enabled);
+
+    // Known-answer tests from test vectors specified in manifest
+    let helper = builder.build().unwrap();
+    let mut test_vectors: Vec<PathBuf> = helper
+        .test_vectors()
+        .unwrap_or_default()
+        .into_iter()
+        .map(Path::to_path_buf)
+        .collect();
+
+    // Test vectors for `common` dialect messages are available only when this dialect is generated
+    if var("CARGO_FEATURE_COMMON").is_ok() {
+        test_vectors.push(manifest_dir.join("test_vectors").join("common.json"));
+    }
+
+    // Sampled test vectors cross-check generated code against test vectors generator
+    let sampled_dir = out_dir.join("test_vectors");
+    create_dir_all(&sampled_dir).unwrap();
+    for dialect in &included_dialects {
+        let path = sampled_dir.join(format!("{dialect}.json"));
+        let vectors = helper.generate_test_vectors(dialect, 0).unwrap();
+        write_test_vectors(&vectors, &path).unwrap();
+        test_vectors.push(path);
+    }
+
+    builder
+        .set_test_vectors(&test_vectors)
+        .set_emit_rerun_if_changed(true)
         .generate()
         .unwrap();
+
+    // Alternative layouts of generated files should have the same public paths
+    for (layout, dir) in [
+        (OutputLayout::SingleFile, "single_file"),
+        (OutputLayout::FilePerDialect, "file_per_dialect"),
+    ] {
+        BuildHelper::builder(out_dir.join("layouts").join(dir))
+            .set_sources(&sources)
+            .set_manifest_path(&manifest_path)
+            .set_include_dialects(&["MAVInspect_test"])
+            .set_serde(serde_feature_enabled)
+            .set_arbitrary(arbitrary_feature_enabled)
+            .set_async_handlers(async_feature_enabled)
+            .set_typed_units(typed_units_feature_enabled)
+            .set_output_layout(layout)
+            .generate()
+            .unwrap();
+    }
+
+    // Enums and bitmasks in each serde representation should accept forms of all other representations
+    for (serde_enums, dir) in [
+        (SerdeEnumRepr::Derive, "derive"),
+        (SerdeEnumRepr::Numeric, "numeric"),
+        (SerdeEnumRepr::Name, "name"),
+        (SerdeEnumRepr::Flags, "flags"),
+    ] {
+        BuildHelper::builder(out_dir.join("serde_enums").join(dir))
+            .set_sources(&sources)
+            .set_include_dialects(&["MAVInspect_test"])
+            .set_messages(&["HEARTBEAT"])
+            .set_enums(&["SMALL_ENUM", "SMALL_BITMASK"])
+            .set_serde(serde_feature_enabled)
+            .set_serde_enums(serde_enums)
+            .generate()
+            .unwrap();
+    }
 }
@@ -11,3 +11,52 @@ mod mavlink {
     include!(concat!(env!("OUT_DIR"), "/mavlink/mod.rs"));
 }
 pub use mavlink::dialects;
+
+/// `MAVInspect_test` dialect generated with alternative layouts of output files.
+pub mod layouts {
+    /// All dialects in a single `mod.rs` file.
+    pub mod single_file {
+        include!(concat!(env!("OUT_DIR"), "/layouts/single_file/mod.rs"));
+    }
+
+    /// Each dialect in its own file.
+    pub mod file_per_dialect {
+        include!(concat!(env!("OUT_DIR"), "/layouts/file_per_dialect/mod.rs"));
+    }
+}
+
+/// `MAVInspect_test` enums generated with each `serde` representation of enums and bitmasks.
+pub mod serde_enums {
+    /// Representation defined by `serde` derive macros.
+    pub mod derive {
+        include!(concat!(env!("OUT_DIR"), "/serde_enums/derive/mod.rs"));
+    }
+
+    /// Numeric values.
+    pub mod numeric {
+        include!(concat!(env!("OUT_DIR"), "/serde_enums/numeric/mod.rs"));
+    }
+
+    /// Canonical names, bitmasks as `|`-separated flags.
+    pub mod name {
+        include!(concat!(env!("OUT_DIR"), "/serde_enums/name/mod.rs"));
+    }
+
+    /// Canonical names, bitmasks as lists of flags.
+    pub mod flags {
+        include!(concat!(env!("OUT_DIR"), "/serde_enums/flags/mod.rs"));
+    }
+}
+
+/// `MAVInspect_test` dialect generated at compile time by `include_dialects!` macro.
+pub mod included {
+    mavspec::rust::derive::include_dialects! {
+        sources = ["message_definitions/standard", "message_definitions/extra"],
+        manifest_path = "Cargo.toml",
+        include_dialects = ["MAVInspect_test"],
+        type_attributes = [
+            { derives = ["PartialEq"] },
+            { kinds = ["messages"], names = ["MAV_INSPECT_*"], attributes = ["#[doc(alias = \"mavspec_included_alias\")]"] },
+        ],
+    }
+}
@@ -70,6 +70,203 @@ mod tests {
         assert_eq!(msg.id(), msg_wrapped.0.id());
     }
 
+    #[test]
+    fn message_constants() {
+        use dialect::messages::{Heartbeat, MavInspectV1, ProtocolVersion};
+        use dialect::MavInspectTest;
+        use mavspec::rust::spec::MavLinkVersion;
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+
+        assert_eq!(Heartbeat::ID, 0);
+        assert_eq!(Heartbeat::CRC_EXTRA, 50);
+        assert_eq!(Heartbeat::PAYLOAD_SIZE_V1, 9);
+        assert_eq!(Heartbeat::PAYLOAD_SIZE_V2, 9);
+        assert_eq!(Heartbeat::MIN_VERSION, MavLinkVersion::V1);
+        assert_eq!(ProtocolVersion::MIN_VERSION, MavLinkVersion::V2);
+        const { assert!(MavInspectV1::PAYLOAD_SIZE_V2 > MavInspectV1::PAYLOAD_SIZE_V1) };
+
+        let message = MavInspectTest::from(ProtocolVersion::default());
+        let name = match message.id() {
+            Heartbeat::ID => "heartbeat",
+            ProtocolVersion::ID => "protocol version",
+            _ => "other",
+        };
+        assert_eq!(name, "protocol version");
+
+        let buffer = [0u8; MavInspectTest::MAX_PAYLOAD_SIZE];
+        assert!(buffer.len() >= MavInspectV1::PAYLOAD_SIZE_V2);
+        assert_eq!(MavInspectTest::MESSAGE_COUNT, 13);
+    }
+
+    #[test]
+    fn dialect_accessors() {
+        use dialect::messages::{Heartbeat, MavInspectV1};
+        use dialect::MavInspectTest;
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+
+        // `HEARTBEAT` is inherited from `minimal` dialect
+        let message = MavInspectTest::from(Heartbeat {
+            custom_mode: 42,
+            ..Default::default()
+        });
+        assert_eq!(message.message_name(), "HEARTBEAT");
+        assert_eq!(message.as_heartbeat().unwrap().custom_mode, 42);
+        assert!(message.as_mav_inspect_v1().is_none());
+
+        let message = MavInspectV1::try_from(message).unwrap_err();
+        assert_eq!(Heartbeat::try_from(message).unwrap().custom_mode, 42);
+
+        let message: MavInspectTest = MavInspectV1::default().into();
+        assert_eq!(message.message_name(), "MAV_INSPECT_V1");
+        assert!(message.as_1st_class_message().is_none());
+    }
+
+    #[test]
+    fn dialect_handlers() {
+        use dialect::messages::{Heartbeat, MavInspectV1, ProtocolVersion};
+        use dialect::{MavInspectTest, MavInspectTestHandler};
+        use mavspec::rust::spec::Message;
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+
+        #[derive(Default)]
+        struct Handler {
+            heartbeats: Vec<u32>,
+            unhandled: Vec<u32>,
+        }
+
+        impl MavInspectTestHandler for Handler {
+            fn on_unhandled(&mut self, message: &dyn Message) {
+                self.unhandled.push(message.id());
+            }
+
+            fn on_heartbeat(&mut self, message: &Heartbeat) {
+                self.heartbeats.push(message.custom_mode);
+            }
+        }
+
+        let mut handler = Handler::default();
+        for message in [
+            MavInspectTest::from(Heartbeat {
+                custom_mode: 42,
+                ..Default::default()
+            }),
+            MavInspectTest::from(MavInspectV1::default()),
+            MavInspectTest::from(ProtocolVersion::default()),
+        ] {
+            message.dispatch(&mut handler);
+        }
+
+        assert_eq!(handler.heartbeats, [42]);
+        assert_eq!(handler.unhandled, [255, 300]);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn dialect_async_handlers() {
+        use core::future::Future;
+        use core::pin::pin;
+        use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+        use dialect::messages::{Heartbeat, ProtocolVersion};
+        use dialect::{MavInspectTest, MavInspectTestAsyncHandler};
+        use mavspec::rust::spec::Message;
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+
+        #[derive(Default)]
+        struct Handler {
+            heartbeats: usize,
+            unhandled: usize,
+        }
+
+        impl MavInspectTestAsyncHandler for Handler {
+            async fn on_unhandled(&mut self, _: &(dyn Message + Sync)) {
+                self.unhandled += 1;
+            }
+
+            async fn on_heartbeat(&mut self, _: &Heartbeat) {
+                self.heartbeats += 1;
+            }
+        }
+
+        fn block_on<F: Future>(future: F) -> F::Output {
+            const VTABLE: RawWakerVTable = RawWakerVTable::new(
+                |_| RawWaker::new(core::ptr::null(), &VTABLE),
+                |_| {},
+                |_| {},
+                |_| {},
+            );
+            // SAFETY: all vtable functions are no-ops which never access data pointer
+            let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+            let mut future = pin!(future);
+            let mut context = Context::from_waker(&waker);
+            loop {
+                if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                    return output;
+                }
+            }
+        }
+
+        let mut handler = Handler::default();
+        block_on(async {
+            MavInspectTest::from(Heartbeat::default())
+                .dispatch_async(&mut handler)
+                .await;
+            MavInspectTest::from(ProtocolVersion::default())
+                .dispatch_async(&mut handler)
+                .await;
+        });
+
+        assert_eq!(handler.heartbeats, 1);
+        assert_eq!(handler.unhandled, 1);
+    }
+
+    #[test]
+    fn builders() {
+        use dialect::enums::SmallEnum;
+        use dialect::messages::{MavInspectV1, ProtocolVersion};
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+
+        let msg = ProtocolVersion::new(200, 100, 200, [1; 8], [2; 8]);
+        assert_eq!(msg.version, 200);
+        assert_eq!(msg.min_version, 100);
+        assert_eq!(msg.library_version_hash, [2; 8]);
+
+        let msg = MavInspectV1::default()
+            .with_type(42)
+            .with_1st_class_citizen(Default::default())
+            .with_extension_uint16_4([SmallEnum::Third; 4]);
+        assert_eq!(msg.type_, 42);
+        assert_eq!(msg.extension_uint16_4, [SmallEnum::Third; 4]);
+    }
+
+    #[test]
+    fn type_attributes() {
+        use dialect::enums::{SmallBitmask, SmallEnum};
+        use dialect::messages::{DefaultValues, MavInspectV1};
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+        use std::collections::HashSet;
+
+        fn assert_eq_hash<T: Eq + core::hash::Hash>() {}
+        assert_eq_hash::<MavInspectV1>();
+        assert_eq_hash::<SmallEnum>();
+        assert_eq_hash::<SmallBitmask>();
+
+        // `DEFAULT_VALUES` contains floats, so it is only `PartialEq` (`NaN` is not equal to itself)
+        assert_ne!(DefaultValues::default(), DefaultValues::default());
+        assert_eq!(
+            dialect::MavInspectTest::MavInspectV1(MavInspectV1::default()),
+            dialect::MavInspectTest::MavInspectV1(MavInspectV1::default()),
+        );
+
+        let flags: HashSet<SmallBitmask> = [
+            SmallBitmask::FIRST,
+            SmallBitmask::FIRST,
+            SmallBitmask::FIRST | SmallBitmask::SECOND,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(flags.len(), 2);
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn serde_support() {
@@ -86,4 +283,523 @@ mod tests {
 
         assert_eq!(msg.id(), msg_wrapped.0.id());
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_enums() {
+        use dialect::enums::{SmallBitmask, SmallEnum};
+        use dialect::messages::MavInspectV1;
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+
+        // Enums are represented by canonical names, bitmasks by lists of flags (`serde_enums = "flags"`)
+        let flags = SmallBitmask::FIRST | SmallBitmask::THIRD;
+        assert_eq!(
+            serde_json::to_string(&SmallEnum::Third).unwrap(),
+            "\"SMALL_ENUM_THIRD\""
+        );
+        assert_eq!(
+            serde_json::to_string(&flags).unwrap(),
+            "[\"SMALL_BITMASK_FIRST\",\"SMALL_BITMASK_THIRD\"]"
+        );
+
+        // Every representation is accepted
+        for json in ["2", "\"SMALL_ENUM_THIRD\"", "\"third\""] {
+            let value: SmallEnum = serde_json::from_str(json).unwrap();
+            assert_eq!(value as u8, SmallEnum::Third as u8);
+        }
+        for json in [
+            "5",
+            "\"FIRST|SMALL_BITMASK_THIRD\"",
+            "[\"SMALL_BITMASK_FIRST\", \"third\"]",
+        ] {
+            let value: SmallBitmask = serde_json::from_str(json).unwrap();
+            assert_eq!(value.bits(), flags.bits());
+        }
+        assert!(serde_json::from_str::<SmallEnum>("\"SMALL_ENUM_FIFTH\"").is_err());
+
+        // Round trip through JSON
+        let message = MavInspectV1 {
+            small_enum_native: SmallEnum::Fourth,
+            small_bitmask_native: flags,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(json.contains("\"small_enum_native\":\"SMALL_ENUM_FOURTH\""));
+        assert!(json.contains(
+            "\"small_bitmask_native\":[\"SMALL_BITMASK_FIRST\",\"SMALL_BITMASK_THIRD\"]"
+        ));
+
+        let decoded: MavInspectV1 = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.small_enum_native as u8, SmallEnum::Fourth as u8);
+        assert_eq!(decoded.small_bitmask_native.bits(), flags.bits());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_enums_representations() {
+        use mavspec_tests_rust::serde_enums::{derive, flags, name, numeric};
+
+        macro_rules! serialize {
+            ($repr:ident) => {{
+                use $repr::dialects::mav_inspect_test::enums::{SmallBitmask, SmallEnum};
+                (
+                    serde_json::to_string(&SmallEnum::Third).unwrap(),
+                    serde_json::to_string(&(SmallBitmask::FIRST | SmallBitmask::THIRD)).unwrap(),
+                )
+            }};
+        }
+
+        let forms = [
+            serialize!(derive),
+            serialize!(numeric),
+            serialize!(name),
+            serialize!(flags),
+        ];
+        assert_eq!(
+            forms,
+            [
+                ("\"Third\"", "5"),
+                ("2", "5"),
+                (
+                    "\"SMALL_ENUM_THIRD\"",
+                    "\"SMALL_BITMASK_FIRST|SMALL_BITMASK_THIRD\""
+                ),
+                (
+                    "\"SMALL_ENUM_THIRD\"",
+                    "[\"SMALL_BITMASK_FIRST\",\"SMALL_BITMASK_THIRD\"]"
+                ),
+            ]
+            .map(|(enum_json, bitmask_json)| (enum_json.to_string(), bitmask_json.to_string()))
+        );
+
+        // Each representation accepts forms of all representations
+        macro_rules! check_deserialize {
+            ($repr:ident) => {{
+                use $repr::dialects::mav_inspect_test::enums::{SmallBitmask, SmallEnum};
+                for (enum_json, bitmask_json) in &forms {
+                    let value: SmallEnum = serde_json::from_str(enum_json).unwrap();
+                    assert_eq!(value as u8, SmallEnum::Third as u8, "{enum_json}");
+                    let value: SmallBitmask = serde_json::from_str(bitmask_json).unwrap();
+                    assert_eq!(value.bits(), 5, "{bitmask_json}");
+                }
+                assert!(serde_json::from_str::<SmallEnum>("\"SMALL_ENUM_FIFTH\"").is_err());
+                assert!(serde_json::from_str::<SmallEnum>("\"Fifth\"").is_err());
+            }};
+        }
+
+        check_deserialize!(derive);
+        check_deserialize!(numeric);
+        check_deserialize!(name);
+        check_deserialize!(flags);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_dialect() {
+        use dialect::enums::SmallEnum;
+        use dialect::messages::{MavInspectV1, ProtocolVersion};
+        use dialect::MavInspectTest;
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+
+        let messages = vec![
+            MavInspectTest::MavInspectV1(MavInspectV1 {
+                small_enum_native: SmallEnum::Second,
+                ..Default::default()
+            }),
+            MavInspectTest::ProtocolVersion(ProtocolVersion {
+                version: 200,
+                ..Default::default()
+            }),
+        ];
+
+        // Messages are tagged with MAVLink message names
+        let json = serde_json::to_value(&messages).unwrap();
+        assert_eq!(json[0]["message"], "MAV_INSPECT_V1");
+        assert_eq!(json[0]["fields"]["small_enum_native"], "SMALL_ENUM_SECOND");
+        assert_eq!(json[1]["message"], "PROTOCOL_VERSION");
+        assert_eq!(json[1]["fields"]["version"], 200);
+
+        // Mixed stream can be replayed
+        let decoded: Vec<MavInspectTest> = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.len(), 2);
+        for (message, decoded) in messages.iter().zip(decoded.iter()) {
+            assert_eq!(message.id(), decoded.id());
+            assert_eq!(
+                message.encode(MavLinkVersion::V2).unwrap().bytes(),
+                decoded.encode(MavLinkVersion::V2).unwrap().bytes()
+            );
+        }
+        match &decoded[0] {
+            MavInspectTest::MavInspectV1(message) => {
+                assert_eq!(message.small_enum_native as u8, SmallEnum::Second as u8)
+            }
+            _ => panic!("invalid message"),
+        }
+
+        // Order of keys does not matter
+        let message: MavInspectTest = serde_json::from_str(
+            r#"{
+                "fields": {
+                    "version": 200,
+                    "min_version": 100,
+                    "max_version": 200,
+                    "spec_version_hash": [0, 0, 0, 0, 0, 0, 0, 0],
+                    "library_version_hash": [0, 0, 0, 0, 0, 0, 0, 0]
+                },
+                "message": "PROTOCOL_VERSION"
+            }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            message,
+            MavInspectTest::ProtocolVersion(ProtocolVersion {
+                min_version: 100,
+                ..
+            })
+        ));
+        assert!(
+            serde_json::from_str::<MavInspectTest>(r#"{"message": "UNKNOWN", "fields": {}}"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn command_metadata() {
+        use dialect::enums::MavCmd;
+        use mavspec_tests_rust::dialects::common as dialect;
+
+        let info = MavCmd::NavWaypoint.info();
+        assert_eq!(info.value(), MavCmd::NavWaypoint as u32);
+        assert_eq!(info.name(), "MAV_CMD_NAV_WAYPOINT");
+        assert_eq!(info.params().len(), 7);
+
+        let hold = info.param(1).unwrap();
+        assert_eq!(hold.label(), Some("Hold"));
+        assert_eq!(hold.units(), Some("s"));
+        assert_eq!(hold.min_value(), Some(0.0));
+
+        for (idx, command) in MavCmd::COMMANDS.iter().enumerate() {
+            let cmd = MavCmd::try_from(command.value() as u16).unwrap();
+            assert_eq!(cmd.info().name(), command.name());
+            if idx > 0 {
+                assert!(MavCmd::COMMANDS[idx - 1].value() < command.value());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn field_units_metadata() {
+        use mavspec_tests_rust::dialects::common as dialect;
+
+        let fields = dialect::messages::debug::FIELDS;
+        assert_eq!(fields[0].name(), "time_boot_ms");
+        assert_eq!(fields[0].units(), Some("ms"));
+        assert_eq!(fields[1].name(), "ind");
+        assert_eq!(fields[1].units(), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "common", feature = "typed_units"))]
+    fn typed_units() {
+        use mavspec::rust::spec::units::{MilliSeconds, Quantity};
+
+        use dialect::messages::Debug;
+        use mavspec_tests_rust::dialects::common as dialect;
+
+        let time_boot_ms: Quantity<u32, MilliSeconds> = Quantity::new(123456);
+        let message = Debug {
+            time_boot_ms,
+            ind: 1,
+            value: 0.5,
+        };
+
+        let payload = message.encode(MavLinkVersion::V2).unwrap();
+        assert_eq!(&payload.bytes()[0..4], 123456u32.to_le_bytes().as_slice());
+
+        let decoded = Debug::try_from(&payload).unwrap();
+        assert_eq!(decoded.time_boot_ms.raw(), 123456);
+        assert_eq!(decoded.time_boot_ms.units(), "ms");
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn scaled_accessors() {
+        use core::time::Duration;
+
+        use dialect::messages::{GlobalPositionInt, HighLatency2};
+        use mavspec_tests_rust::dialects::common as dialect;
+
+        let mut message = GlobalPositionInt::default();
+        message.set_time_boot_ms_duration(Duration::from_secs(2));
+        message.set_lat_deg(47.397742);
+        message.set_alt_m(-12.3456);
+        message.set_hdg_deg(f64::MAX);
+
+        assert_eq!(message.time_boot_ms_duration(), Duration::from_millis(2000));
+        assert!((message.lat_deg() - 47.397742).abs() < 1e-7);
+        assert!((message.alt_m() + 12.346).abs() < 1e-9);
+        assert_eq!(message.hdg_deg(), u16::MAX as f64 / 100.0);
+
+        let payload = message.encode(MavLinkVersion::V2).unwrap();
+        assert_eq!(&payload.bytes()[0..4], 2000u32.to_le_bytes().as_slice());
+        assert_eq!(
+            &payload.bytes()[4..8],
+            473977420i32.to_le_bytes().as_slice()
+        );
+
+        // `deg/2` and `m/s*5`
+        let mut message = HighLatency2 {
+            heading: 90.into(),
+            airspeed: 50.into(),
+            ..Default::default()
+        };
+        assert_eq!(message.heading_deg(), 180.0);
+        assert_eq!(message.airspeed_m_s(), 10.0);
+
+        // Raw values become 180 and 62 after rounding
+        message.set_heading_deg(359.0);
+        message.set_groundspeed_m_s(12.3);
+        assert_eq!(message.heading_deg(), 360.0);
+        assert_eq!(message.groundspeed_m_s(), 12.4);
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn invalid_accessors() {
+        use mavspec::rust::spec::{InvalidValue, Sentinel};
+
+        use dialect::messages::{sys_status, SysStatus};
+        use mavspec_tests_rust::dialects::common as dialect;
+
+        let mut message = SysStatus::default();
+        assert!(message.current_battery_opt().is_none());
+
+        message.set_current_battery_a(1.5);
+        assert!(message.current_battery_opt().is_some());
+
+        message.set_current_battery_opt(None);
+        message.set_voltage_battery_opt(None);
+        assert!(message.current_battery_opt().is_none());
+        assert!(message.voltage_battery_opt().is_none());
+
+        let payload = message.encode(MavLinkVersion::V2).unwrap();
+        assert_eq!(&payload.bytes()[14..16], u16::MAX.to_le_bytes().as_slice());
+        assert_eq!(&payload.bytes()[16..18], (-1i16).to_le_bytes().as_slice());
+
+        let field = sys_status::FIELDS
+            .iter()
+            .find(|field| field.name() == "current_battery")
+            .unwrap();
+        assert_eq!(field.invalid(), Some(InvalidValue::Value(Sentinel::Int(-1))));
+        assert!(field.invalid().unwrap().matches(-1i16));
+    }
+
+    #[test]
+    fn default_values() {
+        use dialect::enums::SmallEnum;
+        use dialect::messages::DefaultValues;
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+
+        let message = DefaultValues::default();
+
+        // Explicit XML default has priority over invalid value
+        assert_eq!(message.xml_default, 42);
+        // Fields with invalid values are not provided by default
+        assert!(message.not_provided.is_nan());
+        assert_eq!(message.items, [i16::MAX; 4]);
+        assert!(matches!(message.small_enum, SmallEnum::Second));
+
+        assert!(message.not_provided_opt().is_none());
+        assert!(message.items_opt().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn string_fields() {
+        use dialect::messages::Statustext;
+        use mavspec::rust::spec::MavString;
+        use mavspec_tests_rust::dialects::common as dialect;
+
+        let message = Statustext {
+            text: "Preflight check".parse().unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(message.text.as_str().unwrap(), "Preflight check");
+
+        // Wire representation is the same as for `[u8; 50]`
+        let payload = message.encode(MavLinkVersion::V2).unwrap();
+        assert_eq!(&payload.bytes()[1..16], b"Preflight check");
+
+        let decoded = Statustext::try_from(&payload).unwrap();
+        assert_eq!(decoded.text, message.text);
+        assert_eq!(decoded.text, "Preflight check");
+
+        // Strings that occupy the whole field are not NUL-terminated
+        let text = MavString::<50>::from_str_truncated(&"x".repeat(60));
+        assert_eq!(text.len(), 50);
+        assert!(MavString::<50>::try_from("x".repeat(60).as_str()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "common")]
+    fn stream_keys() {
+        use dialect::messages::{BatteryStatus, NamedValueFloat, SysStatus};
+        use mavspec::rust::spec::{MessageInstance, StreamKey};
+        use mavspec_tests_rust::dialects::common as dialect;
+
+        let battery_1 = dialect::Common::from(BatteryStatus {
+            id: 0,
+            ..Default::default()
+        });
+        let battery_2 = dialect::Common::from(BatteryStatus {
+            id: 1,
+            ..Default::default()
+        });
+
+        assert_eq!(battery_2.as_battery_status().unwrap().instance(), 1);
+        assert_eq!(
+            battery_2.stream_key(),
+            StreamKey::new(BatteryStatus::ID, Some(MessageInstance::Index(1)))
+        );
+        assert_ne!(battery_1.stream_key(), battery_2.stream_key());
+
+        // String instances
+        let named = dialect::Common::from(NamedValueFloat {
+            name: "temp".parse().unwrap(),
+            ..Default::default()
+        });
+        assert_eq!(named.as_named_value_float().unwrap().instance(), "temp");
+        assert_eq!(
+            named.stream_key().instance(),
+            Some(MessageInstance::from_name(b"temp"))
+        );
+
+        // Messages without instance fields
+        let status = dialect::Common::from(SysStatus::default());
+        assert_eq!(status.stream_key(), StreamKey::new(SysStatus::ID, None));
+    }
+
+    #[test]
+    fn enum_metadata() {
+        use dialect::enums::{_1stClassCitizen, SmallBitmask, SmallEnum};
+        use mavspec::rust::spec::EnumSpec;
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+
+        assert_eq!(SmallEnum::NAME, "SMALL_ENUM");
+        assert_eq!(SmallEnum::ENTRIES.len(), 4);
+        assert_eq!(SmallEnum::ENTRIES[1].name(), "SMALL_ENUM_SECOND");
+        assert_eq!(SmallEnum::ENTRIES[1].value() as u8, 1);
+
+        assert_eq!(SmallEnum::Third.name(), "SMALL_ENUM_THIRD");
+        assert_eq!(SmallEnum::Third.name_stripped(), "THIRD");
+        assert_eq!(SmallEnum::Third.description(), "Third entry");
+        assert_eq!(
+            SmallEnum::Third.entry_info().unwrap().name(),
+            "SMALL_ENUM_THIRD"
+        );
+        assert_eq!(_1stClassCitizen::_1stOption.name_stripped(), "1ST_OPTION");
+
+        assert_eq!(SmallBitmask::NAME, "SMALL_BITMASK");
+        assert_eq!(SmallBitmask::ENTRIES.len(), 4);
+        assert_eq!(SmallBitmask::SECOND.name(), Some("SMALL_BITMASK_SECOND"));
+        assert_eq!(SmallBitmask::SECOND.name_stripped(), Some("SECOND"));
+        assert_eq!(
+            SmallBitmask::SECOND.description(),
+            Some("7th bit: 00000010.")
+        );
+        assert_eq!((SmallBitmask::FIRST | SmallBitmask::SECOND).name(), None);
+    }
+
+    #[test]
+    fn enum_names() {
+        use dialect::enums::{SmallBitmask, SmallEnum};
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+
+        assert_eq!(SmallEnum::Third.to_string(), "SMALL_ENUM_THIRD");
+        for name in ["SMALL_ENUM_THIRD", "THIRD", "small_enum_third", "Third"] {
+            assert_eq!(name.parse::<SmallEnum>().unwrap() as u8, 2);
+        }
+        assert!("SMALL_ENUM_FIFTH".parse::<SmallEnum>().is_err());
+
+        let flags = SmallBitmask::FIRST | SmallBitmask::THIRD;
+        assert_eq!(flags.to_string(), "SMALL_BITMASK_FIRST|SMALL_BITMASK_THIRD");
+        assert_eq!(SmallBitmask::empty().to_string(), "");
+        assert_eq!(
+            SmallBitmask::from_bits_retain(0b10010).to_string(),
+            "SMALL_BITMASK_SECOND|0x10"
+        );
+
+        for repr in [
+            "SMALL_BITMASK_FIRST|SMALL_BITMASK_THIRD",
+            "first | third",
+            "THIRD|SMALL_BITMASK_FIRST",
+        ] {
+            assert_eq!(repr.parse::<SmallBitmask>().unwrap().bits(), flags.bits());
+        }
+        assert!("FIRST|FIFTH".parse::<SmallBitmask>().is_err());
+    }
+
+    #[test]
+    fn output_layouts() {
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+        use mavspec_tests_rust::layouts::{file_per_dialect, single_file};
+
+        let payload = dialect::messages::MavInspectV1 {
+            plain_uint8: 10,
+            small_enum_native: dialect::enums::SmallEnum::First,
+            ..Default::default()
+        }
+        .encode(MavLinkVersion::V2)
+        .unwrap();
+
+        let single_file_payload = single_file::dialects::mav_inspect_test::messages::MavInspectV1 {
+            plain_uint8: 10,
+            small_enum_native: single_file::dialects::mav_inspect_test::enums::SmallEnum::First,
+            ..Default::default()
+        }
+        .encode(MavLinkVersion::V2)
+        .unwrap();
+        assert_eq!(single_file_payload.bytes(), payload.bytes());
+
+        let message =
+            file_per_dialect::dialects::mav_inspect_test::messages::MavInspectV1::try_from(
+                &payload,
+            )
+            .unwrap();
+        assert_eq!(message.plain_uint8, 10);
+
+        assert_eq!(
+            single_file::dialects::minimal::messages::Heartbeat::ID,
+            file_per_dialect::dialects::minimal::messages::Heartbeat::ID,
+        );
+    }
+
+    #[test]
+    fn included_dialects() {
+        use mavspec_tests_rust::dialects::mav_inspect_test as dialect;
+        use mavspec_tests_rust::included::dialects::mav_inspect_test as included;
+
+        let included_message = included::messages::MavInspectV1 {
+            plain_uint8: 10,
+            small_enum_native: included::enums::SmallEnum::First,
+            ..Default::default()
+        };
+        let payload = included_message.encode(MavLinkVersion::V2).unwrap();
+
+        // Derives are set by `type_attributes` option
+        assert_eq!(
+            included::messages::MavInspectV1::try_from(&payload).unwrap(),
+            included_message
+        );
+
+        let message = dialect::messages::MavInspectV1::try_from(&payload).unwrap();
+        assert_eq!(message.plain_uint8, 10);
+        assert_eq!(message.small_enum_native, dialect::enums::SmallEnum::First);
+        assert_eq!(
+            included::messages::MavInspectV1::CRC_EXTRA,
+            dialect::messages::MavInspectV1::CRC_EXTRA,
+        );
+    }
 }